@@ -0,0 +1,139 @@
+use chrono::{Date, DateTime, Duration, TimeZone, Utc};
+use criterion::{criterion_group, criterion_main, Criterion};
+use dateless::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::hint::black_box;
+
+/// `StartEnd` hidden from the built-in kinds, so events hold it as `PeriodKind::Custom`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+struct Opaque(StartEnd);
+
+impl Opaque {
+    fn boxed(period: Box<dyn Period>) -> Box<dyn Period> {
+        Box::new(Self(StartEnd(
+            period.get_date_time_start(),
+            period.get_date_time_end(),
+        )))
+    }
+}
+
+#[typetag::serde]
+impl Period for Opaque {
+    fn contains(&self, date: Date<Utc>) -> bool {
+        self.0.contains(date)
+    }
+
+    fn get_date_time_start(&self) -> DateTime<Utc> {
+        self.0.get_date_time_start()
+    }
+
+    fn get_date_time_end(&self) -> DateTime<Utc> {
+        self.0.get_date_time_end()
+    }
+
+    fn starts_before(&self, date: Date<Utc>) -> bool {
+        self.0.starts_before(date)
+    }
+
+    fn with_new_start(&self, date: Date<Utc>) -> Box<dyn Period> {
+        Self::boxed(self.0.with_new_start(date))
+    }
+
+    fn with_new_date_time_start(&self, start: DateTime<Utc>) -> Box<dyn Period> {
+        Self::boxed(self.0.with_new_date_time_start(start))
+    }
+
+    fn cloned(&self) -> Box<dyn Period> {
+        Box::new(self.clone())
+    }
+
+    fn as_weekdays(&self) -> (u32, u32) {
+        self.0.as_weekdays()
+    }
+
+    fn as_days_of_month(&self) -> (u32, u32) {
+        self.0.as_days_of_month()
+    }
+
+    fn as_months(&self) -> (u32, u32) {
+        self.0.as_months()
+    }
+
+    fn with_new_month(&self, month: u32) -> Date<Utc> {
+        self.0.with_new_month(month)
+    }
+}
+
+/// `DailyCycle` hidden from the built-in cycles, so events hold it as `Recurrence::Custom`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+struct OpaqueDaily;
+
+#[typetag::serde]
+impl Cyclicity for OpaqueDaily {
+    fn next_start_after(&self, period: &dyn Period, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        DailyCycle.next_start_after(period, after)
+    }
+
+    fn previous_start_before(
+        &self,
+        period: &dyn Period,
+        before: DateTime<Utc>,
+    ) -> Option<DateTime<Utc>> {
+        DailyCycle.previous_start_before(period, before)
+    }
+
+    fn cloned(&self) -> Box<dyn Cyclicity> {
+        Box::new(self.clone())
+    }
+}
+
+fn calendar(custom: bool) -> Calendar {
+    let start = Utc.ymd(2021, 5, 3).and_hms(9, 0, 0);
+    let mut calendar = Calendar::new();
+
+    for hour in 0..100 {
+        let from = start + Duration::minutes(hour * 7);
+        let period = StartEnd(from, from + Duration::hours(1));
+        let event = EventPartial::new(format!("Event {}", hour));
+
+        let event = if custom {
+            event
+                .with_period(PeriodKind::Custom(Box::new(Opaque(period))))
+                .with_cyclicity(Recurrence::Custom(Box::new(OpaqueDaily)))
+        } else {
+            event.with_period(period).with_cyclicity(DailyCycle)
+        };
+
+        calendar.add_event(event.complete());
+    }
+
+    calendar
+}
+
+fn day(c: &mut Criterion) {
+    let date = Utc.ymd(2022, 5, 3);
+    let mut group = c.benchmark_group("Calendar::day");
+
+    for (name, custom) in [("built-in", false), ("custom", true)] {
+        let calendar = calendar(custom);
+
+        group.bench_function(name, |b| b.iter(|| calendar.day(black_box(date))));
+    }
+
+    group.finish();
+}
+
+fn clone(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Calendar::clone");
+
+    for (name, custom) in [("built-in", false), ("custom", true)] {
+        let calendar = calendar(custom);
+
+        group.bench_function(name, |b| b.iter(|| calendar.clone()));
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, day, clone);
+criterion_main!(benches);
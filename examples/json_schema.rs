@@ -0,0 +1,5 @@
+fn main() {
+    let schema = dateless::schema::json_schema();
+
+    println!("{}", serde_json::to_string_pretty(&schema).unwrap());
+}
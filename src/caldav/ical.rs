@@ -0,0 +1,751 @@
+/*!
+   Conversion of events from and to iCalendar (RFC 5545) objects holding a single `VEVENT`,
+   as CalDAV collections store them.
+
+   Periods map onto `DTSTART` and `DTEND`: `WholeDays` as dates, `FloatingTime` as local
+   times, the other ones in UTC. Daily, weekly, monthly, annual and week of the year cycles,
+   as well as hourly and minutely ones without a time window, become `RRULE`s. What iCalendar cannot express,
+   such as open-ended periods, custom periods and the other cyclicities, is kept in
+   `X-DATELESS-*` properties next to the closest standard ones, so other clients still see
+   the first occurrence.
+
+   Instants are written at a precision of a second. Objects using features dateless cannot
+   represent, e.g. other recurrence rules or overridden occurrences, are rejected as
+   `Unsupported` rather than read partially.
+
+   **Time zones are not supported**: instants must be dates, UTC times or floating local
+   times, and any property with a `TZID` parameter is rejected, even when the `VTIMEZONE`
+   it refers to is part of the object. Most clients write events in the time zone of their
+   user, so expect many of their objects to be skipped.
+
+   # Examples
+
+   ```rust
+   use dateless::{caldav::ical, prelude::*};
+   use chrono::{Duration, TimeZone, Utc};
+
+   let start = Utc.ymd(2021, 5, 3).and_hms(9, 0, 0);
+   let event = EventPartial::new("Standup")
+       .from_to(start, start + Duration::minutes(15))
+       .weekly()
+       .complete();
+
+   let text = ical::to_ical(&event).unwrap();
+
+   assert!(text.contains("RRULE:FREQ=WEEKLY\r\n"));
+   assert_eq!(ical::from_ical(&text).unwrap(), event);
+   ```
+*/
+
+use crate::{chrono::DateTimeDef, event::*, week::WeekStart};
+use chrono::{
+    Date, DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Utc, Weekday,
+};
+use std::{error::Error, fmt};
+use uuid::Uuid;
+
+pub const PRODID: &str = concat!("-//dateless//dateless ", env!("CARGO_PKG_VERSION"), "//EN");
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum IcalError {
+    /// The text is not a well-formed iCalendar object holding a `VEVENT`.
+    Malformed(String),
+    /// The object uses a feature dateless cannot represent.
+    Unsupported(String),
+}
+
+impl fmt::Display for IcalError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            IcalError::Malformed(reason) => write!(f, "malformed iCalendar object: {}", reason),
+            IcalError::Unsupported(feature) => {
+                write!(f, "unsupported iCalendar feature: {}", feature)
+            }
+        }
+    }
+}
+
+impl Error for IcalError {}
+
+fn malformed(reason: impl fmt::Display) -> IcalError {
+    IcalError::Malformed(reason.to_string())
+}
+
+/// Writes the event as an iCalendar object, with its `uuid` as the `UID`.
+pub fn to_ical(event: &Event) -> Result<String, IcalError> {
+    write(event, &event.uuid.to_string())
+}
+
+/**
+   Reads an event from an iCalendar object. A `UID` which is not a UUID is mapped onto a
+   name-based (version 5) one, so that reading the same object twice yields the same `uuid`.
+*/
+pub fn from_ical(text: &str) -> Result<Event, IcalError> {
+    parse(text).map(|(_, event)| event)
+}
+
+pub(crate) fn write(event: &Event, uid: &str) -> Result<String, IcalError> {
+    let mut text = String::new();
+    let mut line = |name: &str, value: &str| push_line(&mut text, &format!("{}:{}", name, value));
+
+    line("BEGIN", "VCALENDAR");
+    line("VERSION", "2.0");
+    line("PRODID", PRODID);
+    line("BEGIN", "VEVENT");
+    line("UID", &escape(uid));
+    line("DTSTAMP", &format_utc(event.last_modified()));
+    line("CREATED", &format_utc(event.created()));
+    line("LAST-MODIFIED", &format_utc(event.last_modified()));
+    line("SEQUENCE", &event.sequence().to_string());
+    line("SUMMARY", &escape(event.name()));
+
+    if let Some(description) = event.description() {
+        line("DESCRIPTION", &escape(description));
+    }
+
+    match event.period() {
+        PeriodKind::WholeDays(days) => {
+            line("DTSTART;VALUE=DATE", &format_date(days.start().naive_utc()));
+            line(
+                "DTEND;VALUE=DATE",
+                &format_date(days.exclusive_end().naive_utc()),
+            );
+        }
+        PeriodKind::StartEnd(StartEnd(start, end)) => {
+            line("DTSTART", &format_utc(*start));
+            line("DTEND", &format_utc(*end));
+        }
+        PeriodKind::Moment(Moment(at)) => line("DTSTART", &format_utc(*at)),
+        PeriodKind::OpenEnded(OpenEnded(start)) => {
+            line("DTSTART", &format_utc(*start));
+            line("X-DATELESS-OPEN-ENDED", "TRUE");
+        }
+        PeriodKind::FloatingTime(FloatingTime(start, end)) => {
+            line("DTSTART", &format_floating(*start));
+            line("DTEND", &format_floating(*end));
+        }
+        period @ PeriodKind::Custom(_) => {
+            line("DTSTART", &format_utc(period.get_date_time_start()));
+            line("DTEND", &format_utc(period.get_date_time_end()));
+            line("X-DATELESS-PERIOD", &escape(&to_json(period)?));
+        }
+    }
+
+    if let Some(recurrence) = event.cyclicity() {
+        match rule(recurrence, event.period().get_date_time_start().weekday()) {
+            Some(rule) => line("RRULE", &rule),
+            None => line("X-DATELESS-CYCLICITY", &escape(&to_json(recurrence)?)),
+        }
+    }
+
+    for exception in event.exceptions() {
+        match event.period() {
+            PeriodKind::WholeDays(_) => {
+                line("EXDATE;VALUE=DATE", &format_date(exception.date_naive()))
+            }
+            PeriodKind::FloatingTime(_) => line("EXDATE", &format_floating(exception.naive_utc())),
+            _ => line("EXDATE", &format_utc(exception)),
+        }
+    }
+
+    for alarm in event.alarms() {
+        line("BEGIN", "VALARM");
+        line("ACTION", "DISPLAY");
+        line("DESCRIPTION", &escape(event.name()));
+
+        match alarm.anchor {
+            AlarmAnchor::Start => line("TRIGGER", &format_duration(alarm.offset.0)),
+            AlarmAnchor::End => line("TRIGGER;RELATED=END", &format_duration(alarm.offset.0)),
+        }
+
+        if let Some(time) = alarm.at_time {
+            line("X-DATELESS-AT-TIME", &time.format("%H%M%S").to_string());
+        }
+
+        line("END", "VALARM");
+    }
+
+    line("END", "VEVENT");
+    line("END", "VCALENDAR");
+
+    Ok(text)
+}
+
+/// Reads the `UID` and the event of an iCalendar object.
+pub(crate) fn parse(text: &str) -> Result<(String, Event), IcalError> {
+    let mut components: Vec<String> = Vec::new();
+    let mut properties = Vec::new();
+    let mut alarms: Vec<Vec<Property>> = Vec::new();
+    let mut events = 0;
+
+    for line in unfold(text) {
+        let property = Property::parse(&line)?;
+
+        match property.name.as_str() {
+            "BEGIN" => {
+                let component = property.value.to_uppercase();
+
+                match component.as_str() {
+                    "VEVENT" => events += 1,
+                    "VALARM" => alarms.push(Vec::new()),
+                    _ => {}
+                }
+
+                components.push(component);
+            }
+            "END" => match components.pop() {
+                Some(component) if component.eq_ignore_ascii_case(&property.value) => {}
+                _ => return Err(malformed(format!("unexpected END:{}", property.value))),
+            },
+            _ => match components.last().map(String::as_str) {
+                Some("VEVENT") => properties.push(property),
+                Some("VALARM") if components.iter().any(|component| component == "VEVENT") => {
+                    alarms.last_mut().unwrap().push(property)
+                }
+                _ => {}
+            },
+        }
+    }
+
+    match events {
+        0 => return Err(malformed("no VEVENT")),
+        1 => {}
+        _ => return Err(IcalError::Unsupported(String::from("several VEVENTs"))),
+    }
+
+    let find = |name: &str| properties.iter().find(|property| property.name == name);
+
+    for name in &["RECURRENCE-ID", "RDATE"] {
+        if find(name).is_some() {
+            return Err(IcalError::Unsupported(name.to_string()));
+        }
+    }
+
+    let uid = unescape(&find("UID").ok_or_else(|| malformed("missing UID"))?.value);
+    let uuid = Uuid::parse_str(&uid)
+        .unwrap_or_else(|_| Uuid::new_v5(&Uuid::NAMESPACE_OID, uid.as_bytes()));
+
+    let name = find("SUMMARY").map_or_else(String::new, |summary| unescape(&summary.value));
+    let period = parse_period(&find)?;
+    let weekday = period.get_date_time_start().weekday();
+    let mut partial = EventPartial::new(name).with_period(period);
+
+    if let Some(description) = find("DESCRIPTION") {
+        partial = partial.with_description(unescape(&description.value));
+    }
+
+    if let Some(cyclicity) = find("X-DATELESS-CYCLICITY") {
+        partial = partial.with_cyclicity(from_json::<Recurrence>(&cyclicity.value)?);
+    } else if let Some(rule) = find("RRULE") {
+        if properties
+            .iter()
+            .filter(|property| property.name == "RRULE")
+            .count()
+            > 1
+        {
+            return Err(IcalError::Unsupported(String::from("several RRULEs")));
+        }
+
+        partial = partial.with_cyclicity(parse_rule(&rule.value, weekday)?);
+    }
+
+    for exceptions in properties
+        .iter()
+        .filter(|property| property.name == "EXDATE")
+    {
+        for value in exceptions.value.split(',') {
+            partial = partial.with_exception(match parse_instant(exceptions, value)? {
+                Instant::Date(date) => {
+                    DateTime::from_naive_utc_and_offset(date.and_time(NaiveTime::MIN), Utc)
+                }
+                Instant::Floating(at) => DateTime::from_naive_utc_and_offset(at, Utc),
+                Instant::Utc(at) => at,
+            });
+        }
+    }
+
+    for alarm in &alarms {
+        partial = partial.with_alarm(parse_alarm(alarm)?);
+    }
+
+    let revision_time = |name: &str| match find(name) {
+        Some(property) => parse_utc(property).map(Some),
+        None => Ok(None),
+    };
+    let epoch = DateTimeDef::default().0;
+    let created = revision_time("CREATED")?.unwrap_or(epoch);
+    let last_modified = match revision_time("LAST-MODIFIED")? {
+        Some(at) => at,
+        None => revision_time("DTSTAMP")?.unwrap_or(epoch),
+    };
+    let sequence = match find("SEQUENCE") {
+        Some(sequence) => sequence.value.trim().parse().map_err(malformed)?,
+        None => 0,
+    };
+
+    let mut event = partial.complete();
+    event.uuid = uuid;
+    event.restore_revision(created, last_modified, sequence);
+
+    Ok((uid, event))
+}
+
+fn parse_period<'a>(find: &impl Fn(&str) -> Option<&'a Property>) -> Result<PeriodKind, IcalError> {
+    if let Some(period) = find("X-DATELESS-PERIOD") {
+        return from_json(&period.value);
+    }
+
+    let start = find("DTSTART").ok_or_else(|| malformed("missing DTSTART"))?;
+    let start = parse_instant(start, &start.value)?;
+    let end = match (find("DTEND"), find("DURATION")) {
+        (Some(end), _) => Some(End::At(parse_instant(end, &end.value)?)),
+        (None, Some(duration)) => Some(End::After(parse_duration(&duration.value)?)),
+        (None, None) => None,
+    };
+
+    Ok(match (start, end) {
+        (Instant::Date(start), end) => {
+            let end = match end {
+                Some(End::At(Instant::Date(end))) => end,
+                Some(End::After(duration)) => start + duration,
+                None => start
+                    .succ_opt()
+                    .ok_or_else(|| malformed("DTSTART is the last date"))?,
+                Some(End::At(_)) => return Err(malformed("DTEND is not a date like DTSTART")),
+            };
+
+            WholeDays::from_exclusive_end(Date::from_utc(start, Utc), Date::from_utc(end, Utc))
+                .into()
+        }
+        (Instant::Utc(start), None) if find("X-DATELESS-OPEN-ENDED").is_some() => {
+            OpenEnded(start).into()
+        }
+        (Instant::Utc(start), None) => Moment(start).into(),
+        (Instant::Utc(start), Some(End::At(Instant::Utc(end)))) => StartEnd(start, end).into(),
+        (Instant::Utc(start), Some(End::After(duration))) => {
+            StartEnd(start, start + duration).into()
+        }
+        (Instant::Floating(start), None) => FloatingTime(start, start).into(),
+        (Instant::Floating(start), Some(End::At(Instant::Floating(end)))) => {
+            FloatingTime(start, end).into()
+        }
+        (Instant::Floating(start), Some(End::After(duration))) => {
+            FloatingTime(start, start + duration).into()
+        }
+        _ => return Err(malformed("DTEND is not of the kind of DTSTART")),
+    })
+}
+
+/// The rule of the recurrence of a period starting on the given weekday.
+fn rule(recurrence: &Recurrence, weekday: Weekday) -> Option<String> {
+    let interval = |frequency: &str, interval: u32| match interval {
+        1 => format!("FREQ={}", frequency),
+        _ => format!("FREQ={};INTERVAL={}", frequency, interval),
+    };
+
+    Some(match recurrence {
+        Recurrence::DailyCycle(_) => interval("DAILY", 1),
+        Recurrence::WeeklyCycle(_) => interval("WEEKLY", 1),
+        Recurrence::MonthlyCycle(_) => interval("MONTHLY", 1),
+        Recurrence::AnnualCycle(_) => interval("YEARLY", 1),
+        Recurrence::HourlyCycle(cycle) if cycle.window.is_none() => {
+            interval("HOURLY", cycle.interval.get())
+        }
+        Recurrence::MinutelyCycle(cycle) if cycle.window.is_none() => {
+            interval("MINUTELY", cycle.interval.get())
+        }
+        Recurrence::WeekOfYearCycle(cycle) => format!(
+            "FREQ=YEARLY;BYWEEKNO={};BYDAY={};WKST={}",
+            cycle.week,
+            weekday_code(weekday),
+            weekday_code(cycle.week_start.0)
+        ),
+        _ => return None,
+    })
+}
+
+/// Reads the rule of a period starting on the given weekday, which `BYDAY` has to match.
+fn parse_rule(value: &str, weekday: Weekday) -> Result<Recurrence, IcalError> {
+    let mut frequency = None;
+    let mut interval = 1;
+    let mut week = None;
+    let mut week_start = WeekStart::ISO;
+    let mut by_day = None;
+
+    for part in value.split(';') {
+        let (key, value) = split_once(part, '=').ok_or_else(|| malformed(part))?;
+
+        match key.to_uppercase().as_str() {
+            "FREQ" => frequency = Some(value.to_uppercase()),
+            "INTERVAL" => {
+                interval = value
+                    .parse()
+                    .ok()
+                    .filter(|interval| *interval > 0)
+                    .ok_or_else(|| malformed(format!("INTERVAL={}", value)))?
+            }
+            "WKST" => week_start = WeekStart(parse_weekday(value)?),
+            "BYWEEKNO" if value.contains(',') => {
+                return Err(IcalError::Unsupported(String::from(
+                    "several BYWEEKNO weeks",
+                )))
+            }
+            "BYWEEKNO" => week = Some(value.parse::<i32>().map_err(malformed)?),
+            "BYDAY" => by_day = Some(parse_weekday(value)?),
+            _ => return Err(IcalError::Unsupported(format!("RRULE part {}", key))),
+        }
+    }
+
+    let frequency = frequency.ok_or_else(|| malformed("RRULE without FREQ"))?;
+
+    match (week, by_day) {
+        (None, Some(_)) => return Err(IcalError::Unsupported(String::from("RRULE part BYDAY"))),
+        (_, Some(day)) if day != weekday => {
+            return Err(IcalError::Unsupported(String::from(
+                "BYDAY other than the weekday of DTSTART",
+            )))
+        }
+        _ => {}
+    }
+
+    if let Some(week) = week {
+        return match (frequency.as_str(), interval) {
+            ("YEARLY", 1) => Ok(WeekOfYearCycle { week, week_start }.into()),
+            _ => Err(IcalError::Unsupported(format!(
+                "RRULE FREQ={};INTERVAL={} with BYWEEKNO",
+                frequency, interval
+            ))),
+        };
+    }
+
+    Ok(match (frequency.as_str(), interval) {
+        ("DAILY", 1) => DailyCycle.into(),
+        ("WEEKLY", 1) => WeeklyCycle.into(),
+        ("MONTHLY", 1) => MonthlyCycle.into(),
+        ("YEARLY", 1) => AnnualCycle.into(),
+        ("HOURLY", interval) => HourlyCycle::new(interval).into(),
+        ("MINUTELY", interval) => MinutelyCycle::new(interval).into(),
+        _ => {
+            return Err(IcalError::Unsupported(format!(
+                "RRULE FREQ={};INTERVAL={}",
+                frequency, interval
+            )))
+        }
+    })
+}
+
+fn weekday_code(weekday: Weekday) -> &'static str {
+    match weekday {
+        Weekday::Mon => "MO",
+        Weekday::Tue => "TU",
+        Weekday::Wed => "WE",
+        Weekday::Thu => "TH",
+        Weekday::Fri => "FR",
+        Weekday::Sat => "SA",
+        Weekday::Sun => "SU",
+    }
+}
+
+fn parse_weekday(code: &str) -> Result<Weekday, IcalError> {
+    [
+        Weekday::Mon,
+        Weekday::Tue,
+        Weekday::Wed,
+        Weekday::Thu,
+        Weekday::Fri,
+        Weekday::Sat,
+        Weekday::Sun,
+    ]
+    .iter()
+    .copied()
+    .find(|weekday| weekday_code(*weekday).eq_ignore_ascii_case(code))
+    .ok_or_else(|| IcalError::Unsupported(format!("weekday {}", code)))
+}
+
+fn parse_alarm(properties: &[Property]) -> Result<Alarm, IcalError> {
+    let find = |name: &str| properties.iter().find(|property| property.name == name);
+    let trigger = find("TRIGGER").ok_or_else(|| malformed("VALARM without TRIGGER"))?;
+
+    if trigger.param("VALUE") == Some("DATE-TIME") {
+        return Err(IcalError::Unsupported(String::from(
+            "absolute alarm triggers",
+        )));
+    }
+
+    let anchor = match trigger.param("RELATED") {
+        Some("END") => AlarmAnchor::End,
+        _ => AlarmAnchor::Start,
+    };
+    let alarm = Alarm::new(anchor, parse_duration(&trigger.value)?);
+
+    Ok(match find("X-DATELESS-AT-TIME") {
+        Some(time) => {
+            alarm.at(NaiveTime::parse_from_str(&time.value, "%H%M%S").map_err(malformed)?)
+        }
+        None => alarm,
+    })
+}
+
+enum Instant {
+    Date(NaiveDate),
+    Floating(NaiveDateTime),
+    Utc(DateTime<Utc>),
+}
+
+enum End {
+    At(Instant),
+    After(Duration),
+}
+
+fn parse_instant(property: &Property, value: &str) -> Result<Instant, IcalError> {
+    if property.param("TZID").is_some() {
+        return Err(IcalError::Unsupported(String::from("time zones (TZID)")));
+    }
+
+    let value = value.trim();
+
+    if property.param("VALUE") == Some("DATE") || value.len() == 8 {
+        return NaiveDate::parse_from_str(value, "%Y%m%d")
+            .map(Instant::Date)
+            .map_err(malformed);
+    }
+
+    match value.strip_suffix('Z') {
+        Some(value) => NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S")
+            .map(|at| Instant::Utc(DateTime::from_naive_utc_and_offset(at, Utc))),
+        None => NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S").map(Instant::Floating),
+    }
+    .map_err(malformed)
+}
+
+fn parse_utc(property: &Property) -> Result<DateTime<Utc>, IcalError> {
+    match parse_instant(property, &property.value)? {
+        Instant::Utc(at) => Ok(at),
+        _ => Err(malformed(format!("{} is not in UTC", property.name))),
+    }
+}
+
+fn format_utc(at: DateTime<Utc>) -> String {
+    at.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+fn format_floating(at: NaiveDateTime) -> String {
+    at.format("%Y%m%dT%H%M%S").to_string()
+}
+
+fn format_date(date: NaiveDate) -> String {
+    date.format("%Y%m%d").to_string()
+}
+
+fn format_duration(duration: Duration) -> String {
+    let seconds = duration.num_seconds();
+    let mut text = String::from(if seconds < 0 { "-P" } else { "P" });
+    let seconds = seconds.abs();
+    let (days, hours, minutes, seconds) = (
+        seconds / 86400,
+        seconds % 86400 / 3600,
+        seconds % 3600 / 60,
+        seconds % 60,
+    );
+
+    if days > 0 {
+        text.push_str(&format!("{}D", days));
+    }
+
+    if days == 0 || hours + minutes + seconds > 0 {
+        text.push('T');
+
+        if hours > 0 {
+            text.push_str(&format!("{}H", hours));
+        }
+
+        if minutes > 0 {
+            text.push_str(&format!("{}M", minutes));
+        }
+
+        if seconds > 0 || hours + minutes == 0 {
+            text.push_str(&format!("{}S", seconds));
+        }
+    }
+
+    text
+}
+
+fn parse_duration(value: &str) -> Result<Duration, IcalError> {
+    let value = value.trim();
+    let (sign, rest) = match value.strip_prefix('-') {
+        Some(rest) => (-1, rest),
+        None => (1, value.strip_prefix('+').unwrap_or(value)),
+    };
+    let rest = rest
+        .strip_prefix('P')
+        .ok_or_else(|| malformed(format!("duration {}", value)))?;
+
+    let mut seconds = 0;
+    let mut number = String::new();
+    let mut in_time = false;
+
+    for c in rest.chars() {
+        let unit = match (c, in_time) {
+            ('0'..='9', _) => {
+                number.push(c);
+                continue;
+            }
+            ('T', false) => {
+                in_time = true;
+                continue;
+            }
+            ('W', false) => 604_800,
+            ('D', false) => 86_400,
+            ('H', true) => 3_600,
+            ('M', true) => 60,
+            ('S', true) => 1,
+            _ => return Err(malformed(format!("duration {}", value))),
+        };
+
+        seconds += number.parse::<i64>().map_err(malformed)? * unit;
+        number.clear();
+    }
+
+    if !number.is_empty() {
+        return Err(malformed(format!("duration {}", value)));
+    }
+
+    Ok(Duration::seconds(sign * seconds))
+}
+
+fn to_json<T: serde::Serialize>(value: &T) -> Result<String, IcalError> {
+    serde_json::to_string(value).map_err(|error| IcalError::Unsupported(error.to_string()))
+}
+
+fn from_json<T: serde::de::DeserializeOwned>(value: &str) -> Result<T, IcalError> {
+    serde_json::from_str(&unescape(value)).map_err(malformed)
+}
+
+/// Property of a content line, with upper-cased names of itself and of its parameters.
+struct Property {
+    name: String,
+    params: Vec<(String, String)>,
+    value: String,
+}
+
+impl Property {
+    fn parse(line: &str) -> Result<Self, IcalError> {
+        let mut quoted = false;
+        let colon = line
+            .char_indices()
+            .find(|(_, c)| {
+                if *c == '"' {
+                    quoted = !quoted;
+                }
+
+                *c == ':' && !quoted
+            })
+            .map(|(index, _)| index)
+            .ok_or_else(|| malformed(format!("content line {}", line)))?;
+
+        let mut head = split_unquoted(&line[..colon], ';').into_iter();
+        let name = head.next().unwrap_or_default().to_uppercase();
+        let params = head
+            .filter_map(|param| split_once(param, '='))
+            .map(|(key, value)| (key.to_uppercase(), value.trim_matches('"').to_uppercase()))
+            .collect();
+
+        Ok(Self {
+            name,
+            params,
+            value: line[colon + 1..].to_string(),
+        })
+    }
+
+    fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+fn split_unquoted(text: &str, separator: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut quoted = false;
+    let mut start = 0;
+
+    for (index, c) in text.char_indices() {
+        if c == '"' {
+            quoted = !quoted;
+        } else if c == separator && !quoted {
+            parts.push(&text[start..index]);
+            start = index + 1;
+        }
+    }
+
+    parts.push(&text[start..]);
+    parts
+}
+
+fn split_once(text: &str, separator: char) -> Option<(&str, &str)> {
+    let index = text.find(separator)?;
+
+    Some((&text[..index], &text[index + 1..]))
+}
+
+/// Joins folded lines, i.e. ones continued on lines starting with a space or a tab.
+fn unfold(text: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+
+    for line in text.lines() {
+        match (
+            line.strip_prefix(|c| c == ' ' || c == '\t'),
+            lines.last_mut(),
+        ) {
+            (Some(continuation), Some(last)) => last.push_str(continuation),
+            _ if line.is_empty() => {}
+            _ => lines.push(line.to_string()),
+        }
+    }
+
+    lines
+}
+
+/// Appends the content line, folded into lines of at most 75 octets.
+fn push_line(text: &mut String, line: &str) {
+    let mut width = 0;
+
+    for c in line.chars() {
+        if width + c.len_utf8() > 75 {
+            text.push_str("\r\n ");
+            width = 1;
+        }
+
+        text.push(c);
+        width += c.len_utf8();
+    }
+
+    text.push_str("\r\n");
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+fn unescape(text: &str) -> String {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+
+    while let Some(c) = chars.next() {
+        match (c, c == '\\') {
+            (_, true) => match chars.next() {
+                Some('n') | Some('N') => unescaped.push('\n'),
+                Some(escaped) => unescaped.push(escaped),
+                None => {}
+            },
+            (c, false) => unescaped.push(c),
+        }
+    }
+
+    unescaped
+}
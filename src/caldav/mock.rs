@@ -0,0 +1,244 @@
+use super::{Condition, Resource, SyncCollection, Transport, TransportError};
+use std::{cell::RefCell, collections::BTreeMap, rc::Rc};
+
+/**
+   In-process CalDAV collection, for testing syncs without a server. Clones are handles to
+   the same collection, so each client of a test gets one.
+
+   Every write gets a new revision, which is both the ETag of the resource and the sync
+   token returned afterwards. Tokens can be expired and `sync-collection` reports disabled
+   to exercise the fallbacks of `CalDavSync`.
+
+   # Examples
+
+   ```rust
+   use dateless::caldav::{Condition, MockServer, Transport, TransportError};
+
+   let mut server = MockServer::new();
+   let etag = server.put("standup.ics", "BEGIN:VCALENDAR", Condition::Create).unwrap();
+
+   assert_eq!(
+       server.put("standup.ics", "BEGIN:VCALENDAR", Condition::Create),
+       Err(TransportError::PreconditionFailed)
+   );
+   assert!(server.delete("standup.ics", etag.as_deref()).is_ok());
+   ```
+*/
+#[derive(Debug, Clone, Default)]
+pub struct MockServer(Rc<RefCell<Collection>>);
+
+#[derive(Debug)]
+struct Collection {
+    /// Resources by href, along with their revision.
+    resources: BTreeMap<String, (u64, String)>,
+    /// Href written at each revision, the first one being 1.
+    changes: Vec<String>,
+    /// Revisions before which sync tokens are rejected.
+    expired_before: u64,
+    supports_sync_collection: bool,
+    requests: Vec<String>,
+}
+
+impl Default for Collection {
+    fn default() -> Self {
+        Self {
+            resources: Default::default(),
+            changes: Default::default(),
+            expired_before: 0,
+            supports_sync_collection: true,
+            requests: Default::default(),
+        }
+    }
+}
+
+impl MockServer {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Answers `sync-collection` reports with `TransportError::Unsupported`.
+    pub fn without_sync_collection(self) -> Self {
+        self.0.borrow_mut().supports_sync_collection = false;
+        self
+    }
+
+    /// Rejects every sync token issued so far.
+    pub fn expire_tokens(&self) {
+        let mut collection = self.0.borrow_mut();
+
+        collection.expired_before = collection.revision() + 1;
+    }
+
+    pub fn resource(&self, href: &str) -> Option<Resource> {
+        self.0.borrow().resource(href)
+    }
+
+    pub fn resources(&self) -> Vec<Resource> {
+        self.0.borrow().resources()
+    }
+
+    /// Requests received so far, as a method and an href or a report name.
+    pub fn requests(&self) -> Vec<String> {
+        self.0.borrow().requests.clone()
+    }
+}
+
+impl Transport for MockServer {
+    fn sync_collection(&mut self, token: Option<&str>) -> Result<SyncCollection, TransportError> {
+        self.0.borrow_mut().sync_collection(token)
+    }
+
+    fn calendar_query(&mut self) -> Result<Vec<Resource>, TransportError> {
+        self.0.borrow_mut().calendar_query()
+    }
+
+    fn put(
+        &mut self,
+        href: &str,
+        body: &str,
+        condition: Condition,
+    ) -> Result<Option<String>, TransportError> {
+        self.0.borrow_mut().put(href, body, condition)
+    }
+
+    fn delete(&mut self, href: &str, etag: Option<&str>) -> Result<(), TransportError> {
+        self.0.borrow_mut().delete(href, etag)
+    }
+}
+
+impl Collection {
+    fn resource(&self, href: &str) -> Option<Resource> {
+        self.resources
+            .get(href)
+            .map(|(revision, data)| Self::resource_at(href, *revision, data))
+    }
+
+    fn resources(&self) -> Vec<Resource> {
+        self.resources
+            .iter()
+            .map(|(href, (revision, data))| Self::resource_at(href, *revision, data))
+            .collect()
+    }
+
+    fn revision(&self) -> u64 {
+        self.changes.len() as u64
+    }
+
+    fn etag(revision: u64) -> String {
+        format!("\"{}\"", revision)
+    }
+
+    fn resource_at(href: &str, revision: u64, data: &str) -> Resource {
+        Resource {
+            href: href.to_string(),
+            etag: Self::etag(revision),
+            data: data.to_string(),
+        }
+    }
+
+    fn write(&mut self, href: &str, data: Option<&str>) -> u64 {
+        self.changes.push(href.to_string());
+
+        match data {
+            Some(data) => {
+                self.resources
+                    .insert(href.to_string(), (self.revision(), data.to_string()));
+            }
+            None => {
+                self.resources.remove(href);
+            }
+        }
+
+        self.revision()
+    }
+
+    fn check_etag(&self, href: &str, etag: &str) -> Result<(), TransportError> {
+        match self.resources.get(href) {
+            Some((revision, _)) if Self::etag(*revision) == etag => Ok(()),
+            _ => Err(TransportError::PreconditionFailed),
+        }
+    }
+}
+
+impl Transport for Collection {
+    fn sync_collection(&mut self, token: Option<&str>) -> Result<SyncCollection, TransportError> {
+        self.requests.push(String::from("REPORT sync-collection"));
+
+        if !self.supports_sync_collection {
+            return Err(TransportError::Unsupported);
+        }
+
+        let since = match token {
+            Some(token) => match token.strip_prefix("mock-sync-").map(str::parse::<u64>) {
+                Some(Ok(since)) if since >= self.expired_before && since <= self.revision() => {
+                    since
+                }
+                _ => return Err(TransportError::InvalidSyncToken),
+            },
+            None => {
+                return Ok(SyncCollection {
+                    token: format!("mock-sync-{}", self.revision()),
+                    changed: self.resources(),
+                    removed: Vec::new(),
+                })
+            }
+        };
+
+        let mut hrefs: Vec<&String> = self.changes[since as usize..].iter().collect();
+        hrefs.sort();
+        hrefs.dedup();
+
+        let (changed, removed) = hrefs
+            .into_iter()
+            .partition::<Vec<_>, _>(|href| self.resources.contains_key(*href));
+
+        Ok(SyncCollection {
+            token: format!("mock-sync-{}", self.revision()),
+            changed: changed
+                .into_iter()
+                .filter_map(|href| self.resource(href))
+                .collect(),
+            removed: removed.into_iter().cloned().collect(),
+        })
+    }
+
+    fn calendar_query(&mut self) -> Result<Vec<Resource>, TransportError> {
+        self.requests.push(String::from("REPORT calendar-query"));
+
+        Ok(self.resources())
+    }
+
+    fn put(
+        &mut self,
+        href: &str,
+        body: &str,
+        condition: Condition,
+    ) -> Result<Option<String>, TransportError> {
+        self.requests.push(format!("PUT {}", href));
+
+        match condition {
+            Condition::Create if self.resources.contains_key(href) => {
+                return Err(TransportError::PreconditionFailed)
+            }
+            Condition::Match(etag) => self.check_etag(href, &etag)?,
+            _ => {}
+        }
+
+        Ok(Some(Self::etag(self.write(href, Some(body)))))
+    }
+
+    fn delete(&mut self, href: &str, etag: Option<&str>) -> Result<(), TransportError> {
+        self.requests.push(format!("DELETE {}", href));
+
+        if !self.resources.contains_key(href) {
+            return Err(TransportError::NotFound);
+        }
+
+        if let Some(etag) = etag {
+            self.check_etag(href, etag)?;
+        }
+
+        self.write(href, None);
+        Ok(())
+    }
+}
@@ -0,0 +1,465 @@
+/*!
+   Two-way synchronization of a `Calendar` with a CalDAV collection (RFC 4791), each event
+   being stored as a `VEVENT` resource named after its `uuid`.
+
+   HTTP is left to an implementation of [`Transport`](Transport), so any client library
+   (or none, see [`MockServer`](MockServer)) can be plugged in. A sync pulls the resources
+   changed since the previous one through a `sync-collection` report (RFC 6578), falling
+   back to a `calendar-query` when the server lacks support for it or forgot the token,
+   then pushes the local changes with conditional `PUT`s and `DELETE`s.
+
+   Changes made on both sides to the same event are resolved in favour of the newer
+   revision (see `Event::is_newer_than`), a modification winning over a deletion. Writes
+   the server refuses because the resource changed meanwhile are deferred to the next sync,
+   which pulls the remote version first.
+
+   # Examples
+
+   ```rust
+   use dateless::{caldav::{CalDavSync, MockServer}, prelude::*};
+   use chrono::Utc;
+
+   let mut phone = Calendar::new();
+   phone.add_event(EventPartial::new("Standup").whole_day(Utc::today()).complete());
+
+   let mut phone_sync = CalDavSync::new(MockServer::new());
+   assert_eq!(phone_sync.sync(&mut phone).unwrap().pushed, 1);
+
+   let mut laptop = Calendar::new();
+   let mut laptop_sync = CalDavSync::new(phone_sync.transport().clone());
+   assert_eq!(laptop_sync.sync(&mut laptop).unwrap().pulled, 1);
+
+   assert_eq!(laptop, phone);
+   ```
+*/
+
+pub mod ical;
+mod mock;
+
+use crate::{calendar::Calendar, event::ChangeSet, event::Event};
+use ical::IcalError;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, error::Error, fmt};
+use uuid::Uuid;
+
+pub use mock::MockServer;
+
+/// Calendar object resource of the collection, `href` being relative to the collection.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Resource {
+    pub href: String,
+    pub etag: String,
+    pub data: String,
+}
+
+/// Response of a `sync-collection` report.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SyncCollection {
+    /// Token to pass to the next report.
+    pub token: String,
+    /// Resources created or modified since the given token, or all of them without one.
+    pub changed: Vec<Resource>,
+    /// Hrefs of the resources removed since the given token.
+    pub removed: Vec<String>,
+}
+
+/// Precondition of a `PUT`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Condition {
+    /// `If-None-Match: *`, the resource must not exist.
+    Create,
+    /// `If-Match`, the resource must have the given ETag.
+    Match(String),
+    /// No precondition.
+    Overwrite,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum TransportError {
+    /// `412 Precondition Failed`: the resource changed since its ETag was read.
+    PreconditionFailed,
+    /// The server no longer accepts the sync token (`valid-sync-token` precondition).
+    InvalidSyncToken,
+    /// The server does not support the request, e.g. `sync-collection` reports.
+    Unsupported,
+    NotFound,
+    Other(String),
+}
+
+impl fmt::Display for TransportError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TransportError::PreconditionFailed => write!(f, "precondition failed"),
+            TransportError::InvalidSyncToken => write!(f, "invalid sync token"),
+            TransportError::Unsupported => write!(f, "unsupported request"),
+            TransportError::NotFound => write!(f, "resource not found"),
+            TransportError::Other(reason) => write!(f, "{}", reason),
+        }
+    }
+}
+
+impl Error for TransportError {}
+
+/// Requests to a CalDAV collection, whose resources are addressed by their `href`.
+pub trait Transport {
+    /// `REPORT` `sync-collection` of the collection, since the given token if any.
+    fn sync_collection(&mut self, token: Option<&str>) -> Result<SyncCollection, TransportError>;
+
+    /// `REPORT` `calendar-query` of every `VEVENT` of the collection.
+    fn calendar_query(&mut self) -> Result<Vec<Resource>, TransportError>;
+
+    /// `PUT` of the resource, returning its new ETag if the server sent one.
+    fn put(
+        &mut self,
+        href: &str,
+        body: &str,
+        condition: Condition,
+    ) -> Result<Option<String>, TransportError>;
+
+    /// `DELETE` of the resource, `If-Match` the given ETag if any.
+    fn delete(&mut self, href: &str, etag: Option<&str>) -> Result<(), TransportError>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+struct Remote {
+    href: String,
+    etag: Option<String>,
+    /// The `UID` of the resource, kept for events created by other clients.
+    uid: String,
+}
+
+/**
+   What a `CalDavSync` remembers between syncs: the sync token, the resource of every
+   event, and the events as they were on the server, against which local changes are
+   computed. Persist it alongside the calendar.
+*/
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub struct SyncState {
+    token: Option<String>,
+    resources: BTreeMap<Uuid, Remote>,
+    base: Calendar,
+}
+
+impl SyncState {
+    pub fn token(&self) -> Option<&str> {
+        self.token.as_deref()
+    }
+
+    /// Href of the resource of the event of the given `uuid`.
+    pub fn href(&self, uuid: Uuid) -> Option<&str> {
+        self.resources.get(&uuid).map(|remote| remote.href.as_str())
+    }
+
+    fn uuid_of(&self, href: &str) -> Option<Uuid> {
+        self.resources
+            .iter()
+            .find(|(_, remote)| remote.href == href)
+            .map(|(uuid, _)| *uuid)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Side {
+    Local,
+    Remote,
+}
+
+/// Event changed on both sides since the previous sync, and the side whose version was kept.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Conflict {
+    pub uuid: Uuid,
+    pub kept: Side,
+}
+
+/// Resource which could not be converted from or into an event, and was left as is.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Skipped {
+    pub href: String,
+    pub error: IcalError,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct SyncReport {
+    /// Events added to or updated in the calendar.
+    pub pulled: usize,
+    /// Events removed from the calendar.
+    pub removed: usize,
+    /// Events written to the server.
+    pub pushed: usize,
+    /// Events removed from the server.
+    pub deleted: usize,
+    pub conflicts: Vec<Conflict>,
+    /// Events whose resource changed while being written, left for the next sync.
+    pub deferred: Vec<Uuid>,
+    pub skipped: Vec<Skipped>,
+}
+
+/// Synchronizes calendars with the collection behind the transport.
+#[derive(Debug, Clone)]
+pub struct CalDavSync<T: Transport> {
+    transport: T,
+    state: SyncState,
+}
+
+impl<T: Transport> CalDavSync<T> {
+    pub fn new(transport: T) -> Self {
+        Self {
+            transport,
+            state: Default::default(),
+        }
+    }
+
+    /// Resumes from the state of a previous session.
+    pub fn with_state(self, state: SyncState) -> Self {
+        Self { state, ..self }
+    }
+
+    pub fn state(&self) -> &SyncState {
+        &self.state
+    }
+
+    pub fn transport(&self) -> &T {
+        &self.transport
+    }
+
+    pub fn transport_mut(&mut self) -> &mut T {
+        &mut self.transport
+    }
+
+    /**
+       Pulls the remote changes into the calendar, then pushes the local ones. Fails only on
+       transport errors other than the ones the sync recovers from, in which case the state
+       still reflects what was done.
+    */
+    pub fn sync(&mut self, calendar: &mut Calendar) -> Result<SyncReport, TransportError> {
+        let mut report = SyncReport::default();
+
+        self.pull(calendar, &mut report)?;
+        self.push(calendar, &mut report)?;
+
+        Ok(report)
+    }
+
+    fn pull(
+        &mut self,
+        calendar: &mut Calendar,
+        report: &mut SyncReport,
+    ) -> Result<(), TransportError> {
+        let (changed, removed) = self.fetch()?;
+        let local = self.state.base.diff(calendar);
+        let locally_changed = |uuid: Uuid| {
+            local.added.iter().any(|event| event.uuid == uuid)
+                || local.modified.iter().any(|change| change.uuid == uuid)
+        };
+
+        let mut to_calendar = ChangeSet::default();
+        let mut to_base = ChangeSet::default();
+
+        for resource in changed {
+            let known = self.state.uuid_of(&resource.href);
+
+            if known.and_then(|uuid| self.state.resources[&uuid].etag.as_deref())
+                == Some(resource.etag.as_str())
+            {
+                continue;
+            }
+
+            let (uid, event) = match ical::parse(&resource.data) {
+                Ok(parsed) => parsed,
+                Err(error) => {
+                    // Writes are then conditioned on the version actually on the server
+                    if let Some(remote) = known.and_then(|uuid| self.state.resources.get_mut(&uuid))
+                    {
+                        remote.etag = Some(resource.etag);
+                    }
+
+                    report.skipped.push(Skipped {
+                        href: resource.href,
+                        error,
+                    });
+                    continue;
+                }
+            };
+            let uuid = event.uuid;
+
+            self.state.resources.insert(
+                uuid,
+                Remote {
+                    href: resource.href,
+                    etag: Some(resource.etag),
+                    uid,
+                },
+            );
+
+            let kept = match calendar.event(uuid) {
+                Some(own) if locally_changed(uuid) => {
+                    let kept = if event.is_newer_than(own) {
+                        Side::Remote
+                    } else {
+                        Side::Local
+                    };
+
+                    report.conflicts.push(Conflict { uuid, kept });
+                    kept
+                }
+                Some(own) if *own == event => Side::Local,
+                Some(_) => Side::Remote,
+                None if local.removed.contains(&uuid) => {
+                    report.conflicts.push(Conflict {
+                        uuid,
+                        kept: Side::Remote,
+                    });
+                    Side::Remote
+                }
+                None => Side::Remote,
+            };
+
+            if kept == Side::Remote {
+                to_calendar.added.push(event.clone());
+                report.pulled += 1;
+            }
+
+            to_base.added.push(event);
+        }
+
+        for href in removed {
+            let uuid = match self.state.uuid_of(&href) {
+                Some(uuid) => uuid,
+                None => continue,
+            };
+
+            self.state.resources.remove(&uuid);
+            to_base.removed.push(uuid);
+
+            if locally_changed(uuid) {
+                report.conflicts.push(Conflict {
+                    uuid,
+                    kept: Side::Local,
+                });
+            } else if calendar.event(uuid).is_some() {
+                to_calendar.removed.push(uuid);
+                report.removed += 1;
+            }
+        }
+
+        calendar.apply(to_calendar);
+        self.state.base.apply(to_base);
+
+        Ok(())
+    }
+
+    /// Lists the changed resources and the removed hrefs, recovering from lost tokens.
+    fn fetch(&mut self) -> Result<(Vec<Resource>, Vec<String>), TransportError> {
+        let response = match self.transport.sync_collection(self.state.token.as_deref()) {
+            Err(TransportError::InvalidSyncToken) => {
+                self.state.token = None;
+                self.transport.sync_collection(None)
+            }
+            response => response,
+        };
+
+        let (changed, mut removed, complete) = match response {
+            Ok(collection) => {
+                let complete = self.state.token.is_none();
+
+                self.state.token = Some(collection.token);
+                (collection.changed, collection.removed, complete)
+            }
+            Err(TransportError::Unsupported) => {
+                self.state.token = None;
+                (self.transport.calendar_query()?, Vec::new(), true)
+            }
+            Err(error) => return Err(error),
+        };
+
+        if complete {
+            removed.extend(
+                self.state
+                    .resources
+                    .values()
+                    .map(|remote| &remote.href)
+                    .filter(|href| !changed.iter().any(|resource| &resource.href == *href))
+                    .cloned(),
+            );
+        }
+
+        Ok((changed, removed))
+    }
+
+    fn push(&mut self, calendar: &Calendar, report: &mut SyncReport) -> Result<(), TransportError> {
+        let changes = self.state.base.diff(calendar);
+        let modified = changes
+            .modified
+            .iter()
+            .filter_map(|change| calendar.event(change.uuid));
+
+        for event in changes.added.iter().chain(modified) {
+            self.put(event, report)?;
+        }
+
+        for uuid in changes.removed {
+            let remote = match self.state.resources.get(&uuid) {
+                Some(remote) => remote,
+                None => {
+                    self.state.base.remove_event(uuid);
+                    continue;
+                }
+            };
+
+            match self.transport.delete(&remote.href, remote.etag.as_deref()) {
+                Ok(()) | Err(TransportError::NotFound) => {
+                    self.state.resources.remove(&uuid);
+                    self.state.base.remove_event(uuid);
+                    report.deleted += 1;
+                }
+                Err(TransportError::PreconditionFailed) => report.deferred.push(uuid),
+                Err(error) => return Err(error),
+            }
+        }
+
+        Ok(())
+    }
+
+    fn put(&mut self, event: &Event, report: &mut SyncReport) -> Result<(), TransportError> {
+        let (href, uid, condition) = match self.state.resources.get(&event.uuid) {
+            Some(remote) => (
+                remote.href.clone(),
+                remote.uid.clone(),
+                match &remote.etag {
+                    Some(etag) => Condition::Match(etag.clone()),
+                    None => Condition::Overwrite,
+                },
+            ),
+            None => (
+                format!("{}.ics", event.uuid),
+                event.uuid.to_string(),
+                Condition::Create,
+            ),
+        };
+
+        let body = match ical::write(event, &uid) {
+            Ok(body) => body,
+            Err(error) => {
+                report.skipped.push(Skipped { href, error });
+                return Ok(());
+            }
+        };
+
+        match self.transport.put(&href, &body, condition) {
+            Ok(etag) => {
+                self.state
+                    .resources
+                    .insert(event.uuid, Remote { href, etag, uid });
+                self.state.base.apply(ChangeSet {
+                    added: vec![event.clone()],
+                    ..Default::default()
+                });
+                report.pushed += 1;
+            }
+            Err(TransportError::PreconditionFailed) => report.deferred.push(event.uuid),
+            Err(error) => return Err(error),
+        }
+
+        Ok(())
+    }
+}
@@ -1,12 +1,58 @@
-use super::event::{occurrence::EventOccurrence, Event};
-use chrono::{Date, Utc};
+use super::event::{occurrence::EventOccurrence, AlarmOccurrence, ChangeSet, Event, EventChange};
+use super::journal::{Journal, Operation};
+use super::week::WeekStart;
+use chrono::{Date, DateTime, Duration, Utc};
+use std::hash::{Hash, Hasher};
+use uuid::Uuid;
+
 #[cfg(feature = "serde_support")]
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Default)]
+/**
+   Representation of instants in a serialized calendar.
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 #[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
+pub enum TimestampFormat {
+    /// Unix timestamps in seconds, dropping fractions of a second.
+    #[default]
+    Seconds,
+    /// Unix timestamps in milliseconds, dropping fractions of a millisecond.
+    Milliseconds,
+    /// RFC 3339 strings in UTC, keeping nanoseconds.
+    Rfc3339,
+}
+
+/**
+   Collection of events. See the [`schema`](crate::schema) module for its serialized form.
+
+   The journal, if any, is neither serialized with the calendar nor compared.
+*/
+#[derive(Debug, Clone, Default)]
 pub struct Calendar {
-    events: Vec<Event>,
+    pub(crate) events: Vec<Event>,
+    pub(crate) timestamp_format: TimestampFormat,
+    pub(crate) week_start: WeekStart,
+    pub(crate) journal: Option<Journal>,
+}
+
+impl PartialEq for Calendar {
+    fn eq(&self, other: &Self) -> bool {
+        self.events == other.events
+            && self.timestamp_format == other.timestamp_format
+            && self.week_start == other.week_start
+    }
+}
+
+impl Eq for Calendar {}
+
+impl Hash for Calendar {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.events.hash(state);
+        self.timestamp_format.hash(state);
+        self.week_start.hash(state);
+    }
 }
 
 impl Calendar {
@@ -30,6 +76,117 @@ impl Calendar {
         Default::default()
     }
 
+    /**
+       Sets the representation of instants used when serializing the calendar.
+
+       # Examples
+
+       ```rust
+       use dateless::prelude::*;
+
+       let calendar = Calendar::new().with_timestamp_format(TimestampFormat::Rfc3339);
+       ```
+    */
+    pub fn with_timestamp_format(self, timestamp_format: TimestampFormat) -> Self {
+        Self {
+            timestamp_format,
+            ..self
+        }
+    }
+
+    pub fn timestamp_format(&self) -> TimestampFormat {
+        self.timestamp_format
+    }
+
+    /**
+       Sets the first day of the weeks of the calendar, Monday by default. It lays out `week`
+       and numbers weeks, but cycles don't follow it: `WeeklyCycle` repeats every 7 days from
+       the period whatever day weeks start on, descriptions list weekdays from Monday, and
+       `WeekOfYearCycle` holds a week start of its own.
+    */
+    pub fn with_week_start(self, week_start: WeekStart) -> Self {
+        Self { week_start, ..self }
+    }
+
+    pub fn week_start(&self) -> WeekStart {
+        self.week_start
+    }
+
+    /// The year the week of the date is numbered in, along with its number, see `WeekStart`.
+    pub fn week_number(&self, date: Date<Utc>) -> (i32, u32) {
+        self.week_start.week(date)
+    }
+
+    /**
+       Records further mutations in the given journal, so that they can be undone. A journal
+       serialized beforehand can be passed to keep the history of the calendar it was taken
+       from.
+
+       # Examples
+
+       ```rust
+       use dateless::prelude::*;
+       use chrono::Utc;
+
+       let mut calendar = Calendar::new().with_journal(Journal::new());
+       let event = EventPartial::new("Standup").whole_day(Utc::today()).complete();
+       let uuid = event.uuid;
+
+       calendar.add_event(event);
+       calendar.add_exception(uuid, Utc::today().and_hms(0, 0, 0));
+
+       assert!(calendar.day(Utc::today()).is_empty());
+
+       calendar.undo();
+
+       assert_eq!(calendar.day(Utc::today()).len(), 1);
+
+       calendar.undo();
+
+       assert!(calendar.event(uuid).is_none());
+
+       calendar.redo();
+
+       assert!(calendar.event(uuid).is_some());
+       ```
+    */
+    pub fn with_journal(self, journal: Journal) -> Self {
+        Self {
+            journal: Some(journal),
+            ..self
+        }
+    }
+
+    pub fn journal(&self) -> Option<&Journal> {
+        self.journal.as_ref()
+    }
+
+    /// Reverts the last recorded operation, returning whether there was any.
+    pub fn undo(&mut self) -> bool {
+        match self.journal.as_mut().and_then(Journal::undo) {
+            Some(operation) => {
+                self.perform(&operation);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Does again the last undone operation, returning whether there was any.
+    pub fn redo(&mut self) -> bool {
+        match self.journal.as_mut().and_then(Journal::redo) {
+            Some(operation) => {
+                self.perform(&operation);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn event(&self, uuid: Uuid) -> Option<&Event> {
+        self.events.iter().find(|event| event.uuid == uuid)
+    }
+
     /**
        Adds passed event to the instance.
 
@@ -49,9 +206,79 @@ impl Calendar {
        ```
     */
     pub fn add_event(&mut self, event: Event) {
+        if self.journal.is_some() {
+            self.record(Operation::Insert {
+                index: self.events.len(),
+                event: event.clone(),
+            });
+        }
+
         self.events.push(event);
     }
 
+    /// Removes the event of the given `uuid`, returning it.
+    pub fn remove_event(&mut self, uuid: Uuid) -> Option<Event> {
+        let index = self.index_of(uuid)?;
+        let event = self.events.remove(index);
+
+        if self.journal.is_some() {
+            self.record(Operation::Remove {
+                index,
+                event: event.clone(),
+            });
+        }
+
+        Some(event)
+    }
+
+    /**
+       Replaces the event of the same `uuid`, returning the previous one. Nothing is replaced
+       if the calendar holds no such event.
+
+       The new version keeps the creation time of the previous one, and gets a `sequence`
+       higher than both of them.
+    */
+    pub fn update_event(&mut self, event: Event) -> Option<Event> {
+        let index = self.index_of(event.uuid)?;
+        let previous = std::mem::replace(&mut self.events[index], event);
+
+        self.events[index].modified_after(&previous);
+
+        if self.journal.is_some() {
+            self.record(Operation::Update {
+                from: Box::new(previous.clone()),
+                to: Box::new(self.events[index].clone()),
+            });
+        }
+
+        Some(previous)
+    }
+
+    /// Excludes the occurrence starting at the given instant from the event of the given
+    /// `uuid`, returning whether it was not excluded yet. It counts as a modification of the
+    /// event.
+    pub fn add_exception(&mut self, uuid: Uuid, at: DateTime<Utc>) -> bool {
+        let added = self.perform(&Operation::AddException { uuid, at });
+
+        if added {
+            self.record(Operation::AddException { uuid, at });
+        }
+
+        added
+    }
+
+    /// Restores the occurrence starting at the given instant of the event of the given
+    /// `uuid`, returning whether it was excluded. It counts as a modification of the event.
+    pub fn remove_exception(&mut self, uuid: Uuid, at: DateTime<Utc>) -> bool {
+        let removed = self.perform(&Operation::RemoveException { uuid, at });
+
+        if removed {
+            self.record(Operation::RemoveException { uuid, at });
+        }
+
+        removed
+    }
+
     /**
        Returns `Vec` of `EventOccurrence`s for the given day.
 
@@ -75,7 +302,304 @@ impl Calendar {
     pub fn day(&self, date: Date<Utc>) -> Vec<EventOccurrence> {
         self.events
             .iter()
-            .filter_map(|event| event.get_occurrence_at(date))
+            .flat_map(|event| event.get_occurrences_at(date))
             .collect()
     }
+
+    /**
+       Returns `Vec` of `EventOccurrence`s lasting at any day of the week the given date falls
+       in, according to the week start of the calendar. Occurrences are listed once, by the
+       first day of the week they last at, so ones started during the previous week come
+       first.
+
+       # Examples
+
+       ```rust
+       use dateless::prelude::*;
+       use chrono::{TimeZone, Utc, Weekday};
+
+       let mut calendar = Calendar::new().with_week_start(WeekStart(Weekday::Sun));
+
+       calendar.add_event(
+           EventPartial::new(String::from("Hiking trip"))
+               .whole_days(Utc.ymd(2021, 5, 1), Utc.ymd(2021, 5, 3))
+               .complete()
+       );
+
+       // From Sunday, May 2nd to Saturday, May 8th
+       let week = calendar.week(Utc.ymd(2021, 5, 5));
+
+       assert_eq!(week.len(), 1);
+       ```
+    */
+    pub fn week(&self, date: Date<Utc>) -> Vec<EventOccurrence> {
+        let first_day = self.week_start.first_day(date);
+        let mut occurrences: Vec<EventOccurrence> = Vec::new();
+
+        for day in 0..7 {
+            for occurrence in self.day(first_day + Duration::days(day)) {
+                if !occurrences.contains(&occurrence) {
+                    occurrences.push(occurrence);
+                }
+            }
+        }
+
+        occurrences
+    }
+
+    /**
+       Returns `Vec` of `AlarmOccurrence`s firing in the `[from, to)` range, ordered by
+       the time they fire at.
+
+       # Examples
+
+       ```rust
+       use dateless::prelude::*;
+       use chrono::{Duration, Utc};
+
+       let mut calendar = Calendar::new();
+
+       calendar.add_event(
+           EventPartial::new(String::from("Standup"))
+               .from_to(Utc::now() + Duration::hours(1), Utc::now() + Duration::hours(2))
+               .daily()
+               .with_alarm(Alarm::before_start(Duration::minutes(15)))
+               .complete()
+       );
+
+       let alarms = calendar.alarms_between(Utc::now(), Utc::now() + Duration::days(1));
+       ```
+    */
+    pub fn alarms_between(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> Vec<AlarmOccurrence> {
+        let mut alarms: Vec<AlarmOccurrence> = self
+            .events
+            .iter()
+            .flat_map(|event| event.get_alarms_between(from, to))
+            .collect();
+
+        alarms.sort_by_key(|alarm| alarm.fires_at);
+        alarms
+    }
+
+    /**
+       Returns the changes turning this calendar into the given one: events added, removed
+       and modified, matched by their `uuid`. Events are listed in the order of the calendar
+       holding them.
+
+       # Examples
+
+       ```rust
+       use dateless::prelude::*;
+       use chrono::Utc;
+
+       let mut calendar = Calendar::new();
+       calendar.add_event(EventPartial::new("Standup").whole_day(Utc::today()).complete());
+
+       let mut updated = calendar.clone();
+       updated.add_event(EventPartial::new("Review").whole_day(Utc::today()).complete());
+
+       let changes = calendar.diff(&updated);
+
+       assert_eq!(changes.added.len(), 1);
+       assert!(changes.removed.is_empty() && changes.modified.is_empty());
+
+       calendar.apply(changes);
+
+       assert_eq!(calendar, updated);
+       ```
+    */
+    pub fn diff(&self, other: &Calendar) -> ChangeSet {
+        let mut changes = ChangeSet::default();
+
+        for event in &self.events {
+            match other.event(event.uuid) {
+                Some(other_event) => {
+                    let fields = event.diff(other_event);
+
+                    if !fields.is_empty() {
+                        changes.modified.push(EventChange {
+                            uuid: event.uuid,
+                            fields,
+                            sequence: other_event.sequence(),
+                            last_modified: other_event.last_modified(),
+                        });
+                    }
+                }
+                None => changes.removed.push(event.uuid),
+            }
+        }
+
+        changes.added = other
+            .events
+            .iter()
+            .filter(|event| self.event(event.uuid).is_none())
+            .cloned()
+            .collect();
+
+        changes
+    }
+
+    /**
+       Replays the changes, e.g. ones computed by `diff`. Modifications of events missing
+       from the calendar are skipped, and added events replace the ones of the same `uuid`.
+       Events get the revisions listed by the changes rather than new ones.
+    */
+    pub fn apply(&mut self, changes: ChangeSet) {
+        let mut operations = Vec::new();
+
+        for uuid in changes.removed {
+            if let Some(index) = self.index_of(uuid) {
+                operations.push(Operation::Remove {
+                    index,
+                    event: self.events.remove(index),
+                });
+            }
+        }
+
+        for change in changes.modified {
+            if let Some(index) = self.index_of(change.uuid) {
+                let from = self.events[index].clone();
+
+                self.events[index].apply(change.fields);
+                self.events[index].revise(change.sequence, change.last_modified);
+                operations.push(Operation::Update {
+                    from: Box::new(from),
+                    to: Box::new(self.events[index].clone()),
+                });
+            }
+        }
+
+        for event in changes.added {
+            operations.push(match self.index_of(event.uuid) {
+                Some(index) => Operation::Update {
+                    from: Box::new(std::mem::replace(&mut self.events[index], event.clone())),
+                    to: Box::new(event),
+                },
+                None => {
+                    self.events.push(event.clone());
+
+                    Operation::Insert {
+                        index: self.events.len() - 1,
+                        event,
+                    }
+                }
+            });
+        }
+
+        if !operations.is_empty() {
+            self.record(Operation::Batch(operations));
+        }
+    }
+
+    /**
+       Adds the events of the other calendar missing from this one, and replaces the ones
+       of which the other calendar holds a newer version (see `Event::is_newer_than`).
+       Events removed from either calendar cannot be told apart from the ones added to the
+       other, so they are kept.
+
+       # Examples
+
+       ```rust
+       use dateless::prelude::*;
+       use chrono::Utc;
+
+       let mut phone = Calendar::new();
+       phone.add_event(EventPartial::new("Standup").whole_day(Utc::today()).complete());
+
+       let mut laptop = phone.clone();
+       let uuid = laptop.day(Utc::today())[0].origin;
+       laptop.add_exception(uuid, Utc::today().and_hms(0, 0, 0));
+
+       phone.merge(&laptop);
+
+       assert_eq!(phone, laptop);
+       ```
+    */
+    pub fn merge(&mut self, other: &Calendar) {
+        let added = other
+            .events
+            .iter()
+            .filter(|event| match self.event(event.uuid) {
+                Some(own) => event.is_newer_than(own),
+                None => true,
+            })
+            .cloned()
+            .collect();
+
+        self.apply(ChangeSet {
+            added,
+            ..Default::default()
+        });
+    }
+
+    fn index_of(&self, uuid: Uuid) -> Option<usize> {
+        self.events.iter().position(|event| event.uuid == uuid)
+    }
+
+    fn record(&mut self, operation: Operation) {
+        if let Some(journal) = &mut self.journal {
+            journal.record(operation);
+        }
+    }
+
+    /// Performs the operation without recording it, returning whether it changed anything.
+    fn perform(&mut self, operation: &Operation) -> bool {
+        match operation {
+            Operation::Insert { index, event } => {
+                self.events
+                    .insert((*index).min(self.events.len()), event.clone());
+                true
+            }
+            Operation::Remove { index, event } => {
+                let index = match self.events.get(*index) {
+                    Some(other) if other.uuid == event.uuid => Some(*index),
+                    _ => self.index_of(event.uuid),
+                };
+
+                index.map(|index| self.events.remove(index)).is_some()
+            }
+            Operation::Update { to, .. } => match self.index_of(to.uuid) {
+                Some(index) => {
+                    let mut event = Event::clone(to);
+
+                    event.modified_after(&self.events[index]);
+                    self.events[index] = event;
+                    true
+                }
+                None => false,
+            },
+            Operation::AddException { uuid, at } => {
+                self.modify(*uuid, |event| event.add_exception(*at))
+            }
+            Operation::RemoveException { uuid, at } => {
+                self.modify(*uuid, |event| event.remove_exception(*at))
+            }
+            Operation::Batch(operations) => {
+                let mut changed = false;
+
+                for operation in operations {
+                    changed |= self.perform(operation);
+                }
+
+                changed
+            }
+        }
+    }
+
+    /// Runs the modification on the event of the given `uuid`, touching the event if it
+    /// returns that anything changed.
+    fn modify(&mut self, uuid: Uuid, modification: impl FnOnce(&mut Event) -> bool) -> bool {
+        let event = match self.index_of(uuid) {
+            Some(index) => &mut self.events[index],
+            None => return false,
+        };
+
+        let changed = modification(event);
+
+        if changed {
+            event.touch();
+        }
+
+        changed
+    }
 }
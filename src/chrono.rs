@@ -1,15 +1,15 @@
-use chrono::{Date, DateTime, Utc};
-
-#[cfg(feature = "serde_support")]
-use chrono::serde::ts_seconds;
+use chrono::{Date, DateTime, Duration, TimeZone, Utc};
 
 #[cfg(feature = "serde_support")]
 use serde::{Deserialize, Serialize};
 
 #[cfg(feature = "serde_support")]
-use crate::serde::{from_date_into_string, from_string_into_date};
+use crate::serde::{
+    from_date_into_string, from_duration_into_seconds, from_seconds_into_duration,
+    from_string_into_date,
+};
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
 pub struct DateDef(
     #[cfg_attr(
@@ -22,8 +22,33 @@ pub struct DateDef(
     Date<Utc>,
 );
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct DateTimeDef(
-    #[cfg_attr(feature = "serde_support", serde(with = "ts_seconds"))] pub DateTime<Utc>,
+    #[cfg_attr(feature = "serde_support", serde(with = "crate::serde::timestamp"))]
+    #[cfg_attr(feature = "json_schema", schemars(with = "crate::schema::Timestamp"))]
+    pub DateTime<Utc>,
+);
+
+/// The Unix epoch.
+impl Default for DateTimeDef {
+    fn default() -> Self {
+        Self(Utc.timestamp_opt(0, 0).unwrap())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
+pub struct DurationDef(
+    #[cfg_attr(
+        feature = "serde_support",
+        serde(
+            serialize_with = "from_duration_into_seconds",
+            deserialize_with = "from_seconds_into_duration"
+        )
+    )]
+    #[cfg_attr(feature = "json_schema", schemars(with = "i64"))]
+    pub Duration,
 );
@@ -5,7 +5,7 @@ macro_rules! test {
         #[test]
         fn $name() {
             use crate::{
-                event::{PeriodDef, WholeDays},
+                event::{PeriodKind, WholeDays},
                 prelude::*,
             };
 
@@ -19,7 +19,7 @@ macro_rules! test {
 
             helper!(
                 event,
-                PeriodDef(Box::new(WholeDays(today, today))),
+                PeriodKind::from(WholeDays(today, today)),
                 today,
                 $ne_items
             );
@@ -28,7 +28,7 @@ macro_rules! test {
     ($name:ident, $period:ident, $period_struct:ident, $today_or_now:ident, $dur:expr, $ne_items:expr) => {
         #[test]
         fn $name() {
-            use crate::{event::PeriodDef, prelude::*};
+            use crate::{event::PeriodKind, prelude::*};
 
             use chrono::{Duration, Utc};
 
@@ -40,10 +40,7 @@ macro_rules! test {
 
             helper!(
                 event,
-                PeriodDef(Box::new(crate::event::$period_struct(
-                    moment,
-                    moment + $dur
-                ))),
+                PeriodKind::from(crate::event::$period_struct(moment, moment + $dur)),
                 moment,
                 $ne_items
             );
@@ -60,22 +57,18 @@ macro_rules! helper {
         let mut calendar = Calendar::new();
         calendar.add_event($event);
 
-        let expected_occurrence = serde_json::to_string(&vec![EventOccurrence {
+        let expected_occurrence = vec![EventOccurrence {
             origin: event_uuid,
             name: "Date".into(),
             description: None,
             period: $period,
-        }])
-        .unwrap();
+        }];
 
-        assert_eq!(
-            serde_json::to_string(&calendar.day(Utc::today())).unwrap(),
-            expected_occurrence,
-        );
+        assert_eq!(calendar.day(Utc::today()), expected_occurrence);
 
         for item in $ne_items.iter() {
             assert_ne!(
-                serde_json::to_string(&calendar.day(Utc::today() + item.clone())).unwrap(),
+                calendar.day(Utc::today() + item.clone()),
                 expected_occurrence,
             );
         }
@@ -100,17 +93,6 @@ macro_rules! bind_partial_filler {
     };
 }
 
-#[macro_export]
-macro_rules! bind_partial_filler_boxed {
-    ($name:ident, $field:ident, $type:ident) => {
-        fn $name(self, $field: Box<dyn $type>) -> Self {
-            let $field = Some($field);
-
-            Self { $field, ..self }
-        }
-    };
-}
-
 #[macro_export]
 macro_rules! bind_partial_filler_default {
     ($name:ident, $field:ident) => {
@@ -129,15 +111,17 @@ macro_rules! bind_partial_filler_default {
 macro_rules! bind_partial_trait_filler {
     ($name:ident, $type:ident, $method:ident) => {
         pub fn $name(self) -> Self {
-            self.$method(Box::new($type))
+            self.$method($type)
         }
     };
 }
 
 #[macro_export]
 macro_rules! impl_period_boundaries {
-    ($type:ident, $pointer:ty) => {
+    ($type:ident, $pointer:ty, $end:ident) => {
         impl super::WithBoundaries<$pointer> for $type {
+            const END: super::EndBoundary = super::EndBoundary::$end;
+
             fn start(&self) -> $pointer {
                 self.0
             }
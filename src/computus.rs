@@ -0,0 +1,43 @@
+use chrono::{Duration, NaiveDate};
+
+/**
+   Date of Western (Gregorian) Easter Sunday, computed with the anonymous Gregorian
+   algorithm.
+*/
+pub fn western_easter(year: i32) -> NaiveDate {
+    let a = year.rem_euclid(19);
+    let b = year.div_euclid(100);
+    let c = year.rem_euclid(100);
+    let d = b / 4;
+    let e = b % 4;
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let i = c / 4;
+    let k = c % 4;
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let month = (h + l - 7 * m + 114) / 31;
+    let day = (h + l - 7 * m + 114) % 31 + 1;
+
+    NaiveDate::from_ymd_opt(year, month as u32, day as u32).expect("Easter falls in March or April")
+}
+
+/**
+   Date of Orthodox Easter Sunday, computed with the Meeus Julian algorithm and converted
+   into the Gregorian calendar.
+*/
+pub fn orthodox_easter(year: i32) -> NaiveDate {
+    let a = year.rem_euclid(4);
+    let b = year.rem_euclid(7);
+    let c = year.rem_euclid(19);
+    let d = (19 * c + 15) % 30;
+    let e = (2 * a + 4 * b - d + 34) % 7;
+    let month = (d + e + 114) / 31;
+    let day = (d + e + 114) % 31 + 1;
+    let century = year.div_euclid(100);
+    let calendars_drift = century - century.div_euclid(4) - 2;
+
+    NaiveDate::from_ymd_opt(year, month as u32, day as u32).expect("Easter falls in March or April")
+        + Duration::days(calendars_drift as i64)
+}
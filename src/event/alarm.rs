@@ -0,0 +1,105 @@
+use super::{EventOccurrence, Period};
+use crate::chrono::DurationDef;
+use chrono::{DateTime, Duration, NaiveTime, TimeZone, Utc};
+
+#[cfg(feature = "serde_support")]
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
+pub enum AlarmAnchor {
+    Start,
+    End,
+}
+
+/**
+   A reminder fired relative to the start or the end of every occurrence of an event.
+
+   The `offset` is signed: negative values fire before the anchor, positive ones after it. It
+   is serialized in whole seconds, so any sub-second part is lost on a round trip.
+   When `at_time` is set, the time of day of the computed instant is replaced with it, which
+   is handy for whole day events (e.g. "1 day before at 09:00").
+*/
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
+pub struct Alarm {
+    pub anchor: AlarmAnchor,
+    pub offset: DurationDef,
+    pub at_time: Option<NaiveTime>,
+}
+
+impl Alarm {
+    pub fn new(anchor: AlarmAnchor, offset: Duration) -> Self {
+        Self {
+            anchor,
+            offset: DurationDef(offset),
+            at_time: None,
+        }
+    }
+
+    pub fn before_start(offset: Duration) -> Self {
+        Self::new(AlarmAnchor::Start, -offset)
+    }
+
+    pub fn after_start(offset: Duration) -> Self {
+        Self::new(AlarmAnchor::Start, offset)
+    }
+
+    pub fn before_end(offset: Duration) -> Self {
+        Self::new(AlarmAnchor::End, -offset)
+    }
+
+    pub fn after_end(offset: Duration) -> Self {
+        Self::new(AlarmAnchor::End, offset)
+    }
+
+    pub fn at(self, time: NaiveTime) -> Self {
+        Self {
+            at_time: Some(time),
+            ..self
+        }
+    }
+
+    /**
+       Returns the instant at which the alarm fires for the given occurrence period, `None` when
+       it is out of the range of `DateTime`, e.g. after the end of an open-ended period.
+
+       # Examples
+
+       ```rust
+       use dateless::prelude::*;
+       use chrono::{Duration, NaiveTime, TimeZone, Utc};
+
+       let alarm = Alarm::before_start(Duration::days(1)).at(NaiveTime::from_hms(9, 0, 0));
+       let period = WholeDays(Utc.ymd(2021, 5, 8), Utc.ymd(2021, 5, 8));
+
+       assert_eq!(alarm.fires_at(&period), Some(Utc.ymd(2021, 5, 7).and_hms(9, 0, 0)));
+       ```
+    */
+    pub fn fires_at(&self, period: &dyn Period) -> Option<DateTime<Utc>> {
+        let anchor = match self.anchor {
+            AlarmAnchor::Start => period.get_date_time_start(),
+            AlarmAnchor::End => period.get_date_time_end(),
+        };
+
+        if anchor == DateTime::<Utc>::MAX_UTC {
+            return None;
+        }
+
+        let moment = anchor.checked_add_signed(self.offset.0)?;
+
+        match self.at_time {
+            Some(time) => Some(Utc.from_utc_datetime(&moment.date_naive().and_time(time))),
+            None => Some(moment),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+pub struct AlarmOccurrence {
+    pub fires_at: DateTime<Utc>,
+    pub occurrence: EventOccurrence,
+}
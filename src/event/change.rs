@@ -0,0 +1,180 @@
+use super::{Alarm, Event, PeriodKind, Recurrence};
+use crate::chrono::DateTimeDef;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+#[cfg(feature = "serde_support")]
+use serde::{Deserialize, Serialize};
+
+/**
+   Change of a single field of an event, holding both the previous and the new value of it.
+   Exceptions, which are a set of instants, are described by the ones added and removed.
+*/
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+pub enum FieldChange {
+    Name {
+        from: String,
+        to: String,
+    },
+    Description {
+        from: Option<String>,
+        to: Option<String>,
+    },
+    Period {
+        from: PeriodKind,
+        to: PeriodKind,
+    },
+    Cyclicity {
+        from: Option<Recurrence>,
+        to: Option<Recurrence>,
+    },
+    Exceptions {
+        #[cfg_attr(
+            feature = "serde_support",
+            serde(with = "crate::serde::timestamp::vec")
+        )]
+        added: Vec<DateTime<Utc>>,
+        #[cfg_attr(
+            feature = "serde_support",
+            serde(with = "crate::serde::timestamp::vec")
+        )]
+        removed: Vec<DateTime<Utc>>,
+    },
+    Alarms {
+        from: Vec<Alarm>,
+        to: Vec<Alarm>,
+    },
+}
+
+/// Changed fields of the event of the given `uuid`, along with its new revision.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+pub struct EventChange {
+    pub uuid: Uuid,
+    pub fields: Vec<FieldChange>,
+    pub sequence: u32,
+    #[cfg_attr(feature = "serde_support", serde(with = "crate::serde::timestamp"))]
+    pub last_modified: DateTime<Utc>,
+}
+
+/**
+   Difference between two calendars, as computed by `Calendar::diff`. Events are matched by
+   their `uuid`.
+
+   Serialized, its instants are RFC 3339 strings, down to the nanosecond.
+*/
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+pub struct ChangeSet {
+    pub added: Vec<Event>,
+    pub removed: Vec<Uuid>,
+    pub modified: Vec<EventChange>,
+}
+
+impl ChangeSet {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty()
+    }
+}
+
+impl Event {
+    /**
+       Lists the fields changed between this event and the given one, regardless of their
+       `uuid`s and revisions.
+
+       # Examples
+
+       ```rust
+       use dateless::prelude::*;
+       use chrono::Utc;
+
+       let event = EventPartial::new("Standup").whole_day(Utc::today()).complete();
+       let renamed = EventPartial::new("Daily").whole_day(Utc::today()).complete();
+
+       assert_eq!(
+           event.diff(&renamed),
+           vec![FieldChange::Name {
+               from: String::from("Standup"),
+               to: String::from("Daily"),
+           }]
+       );
+       ```
+    */
+    pub fn diff(&self, other: &Event) -> Vec<FieldChange> {
+        let mut fields = Vec::new();
+
+        if self.name != other.name {
+            fields.push(FieldChange::Name {
+                from: self.name.clone(),
+                to: other.name.clone(),
+            });
+        }
+
+        if self.description != other.description {
+            fields.push(FieldChange::Description {
+                from: self.description.clone(),
+                to: other.description.clone(),
+            });
+        }
+
+        if self.period != other.period {
+            fields.push(FieldChange::Period {
+                from: self.period.clone(),
+                to: other.period.clone(),
+            });
+        }
+
+        if self.cyclicity != other.cyclicity {
+            fields.push(FieldChange::Cyclicity {
+                from: self.cyclicity.clone(),
+                to: other.cyclicity.clone(),
+            });
+        }
+
+        let missing_from = |exceptions: &[DateTimeDef], others: &[DateTimeDef]| {
+            exceptions
+                .iter()
+                .filter(|exception| !others.contains(exception))
+                .map(|exception| exception.0)
+                .collect::<Vec<_>>()
+        };
+        let added = missing_from(&other.exceptions, &self.exceptions);
+        let removed = missing_from(&self.exceptions, &other.exceptions);
+
+        if !added.is_empty() || !removed.is_empty() {
+            fields.push(FieldChange::Exceptions { added, removed });
+        }
+
+        if self.alarms != other.alarms {
+            fields.push(FieldChange::Alarms {
+                from: self.alarms.clone(),
+                to: other.alarms.clone(),
+            });
+        }
+
+        fields
+    }
+
+    /// Sets the fields to their new values. Exceptions already present aren't added twice.
+    pub fn apply(&mut self, fields: Vec<FieldChange>) {
+        for field in fields {
+            match field {
+                FieldChange::Name { to, .. } => self.name = to,
+                FieldChange::Description { to, .. } => self.description = to,
+                FieldChange::Period { to, .. } => self.period = to,
+                FieldChange::Cyclicity { to, .. } => self.cyclicity = to,
+                FieldChange::Exceptions { added, removed } => {
+                    for at in removed {
+                        self.remove_exception(at);
+                    }
+
+                    for at in added {
+                        self.add_exception(at);
+                    }
+                }
+                FieldChange::Alarms { to, .. } => self.alarms = to,
+            }
+        }
+    }
+}
@@ -1,39 +1,67 @@
-use chrono::{Date, Datelike, Duration, Utc};
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
 
 #[cfg(feature = "serde_support")]
 use serde::{Deserialize, Serialize};
 
 use super::Cyclicity;
-use crate::event::period::Period;
+use crate::event::{period::Period, Localization};
 
-#[derive(Debug)]
+/**
+   Repeats the period on the same day of every year. Periods starting on February 29th occur
+   in leap years only.
+*/
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct AnnualCycle;
 
+impl AnnualCycle {
+    fn start_in_year(origin: DateTime<Utc>, year: i32) -> Option<DateTime<Utc>> {
+        let date = NaiveDate::from_ymd_opt(year, origin.month(), origin.day())?;
+
+        Some(DateTime::from_naive_utc_and_offset(
+            date.and_time(origin.time()),
+            Utc,
+        ))
+    }
+}
+
 #[cfg_attr(feature = "serde_support", typetag::serde)]
 impl Cyclicity for AnnualCycle {
-    fn same_period_at(
+    fn next_start_after(&self, period: &dyn Period, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let origin = period.get_date_time_start();
+
+        if after < origin {
+            return Some(origin);
+        }
+
+        (after.year()..after.year() + 9)
+            .filter_map(|year| Self::start_in_year(origin, year))
+            .find(|start| *start > after)
+    }
+
+    fn previous_start_before(
         &self,
-        same_period: Box<dyn Period>,
-        at_date: Date<Utc>,
-    ) -> Option<Box<dyn Period>> {
-        let (starting_month, ending_month) = same_period.as_months();
-        let (starting_month_day, ending_month_day) = same_period.as_days_of_month();
-        let day_difference = at_date - same_period.with_new_month(at_date.month());
-
-        if at_date.day() < starting_month_day || at_date.day() > ending_month_day {
+        period: &dyn Period,
+        before: DateTime<Utc>,
+    ) -> Option<DateTime<Utc>> {
+        let origin = period.get_date_time_start();
+
+        if before <= origin {
             return None;
         }
 
-        let day_difference = day_difference.num_days();
+        (origin.year()..=before.year())
+            .rev()
+            .filter_map(|year| Self::start_in_year(origin, year))
+            .find(|start| *start < before)
+    }
 
-        match at_date.month() {
-            value if value >= starting_month && value <= ending_month => {
-                let at_date = at_date - Duration::days(day_difference);
+    fn describe_in(&self, period: &dyn Period, locale: &dyn Localization) -> String {
+        let start = period.get_date_time_start();
 
-                Some(same_period.with_new_start(at_date))
-            }
-            _ => None,
-        }
+        locale.annual(start.month(), start.day())
     }
+
+    impl_cloned!(Cyclicity);
 }
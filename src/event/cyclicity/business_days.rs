@@ -0,0 +1,220 @@
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+
+#[cfg(feature = "serde_support")]
+use serde::{Deserialize, Serialize};
+
+use super::{month_after, months_between, step_after, step_before, Cyclicity};
+use crate::{
+    event::{period::Period, Localization},
+    working_days::{BusinessDayAdjustment, WorkingDays},
+};
+
+/// Upper bound of candidate starts checked while looking for a working day.
+const SEARCH_LIMIT: usize = 4096;
+
+/// How far an adjustment is expected to move a start at most.
+const ADJUSTMENT_REACH: i64 = 31;
+
+/**
+   Repeats the period every working day, at the time it starts. Non-working days are skipped,
+   the day of the period itself included.
+*/
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
+pub struct WorkingDayCycle(pub WorkingDays);
+
+#[cfg_attr(feature = "serde_support", typetag::serde)]
+impl Cyclicity for WorkingDayCycle {
+    fn next_start_after(&self, period: &dyn Period, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let mut start = step_after(period.get_date_time_start(), Duration::days(1), after);
+
+        for _ in 0..SEARCH_LIMIT {
+            if self.0.is_working_day(start.date()) {
+                return Some(start);
+            }
+
+            start += Duration::days(1);
+        }
+
+        None
+    }
+
+    fn previous_start_before(
+        &self,
+        period: &dyn Period,
+        before: DateTime<Utc>,
+    ) -> Option<DateTime<Utc>> {
+        let origin = period.get_date_time_start();
+        let mut start = step_before(origin, Duration::days(1), before)?;
+
+        for _ in 0..SEARCH_LIMIT {
+            if self.0.is_working_day(start.date()) {
+                return Some(start);
+            }
+
+            start = step_before(origin, Duration::days(1), start)?;
+        }
+
+        None
+    }
+
+    fn describe_in(&self, _period: &dyn Period, locale: &dyn Localization) -> String {
+        locale.working_days()
+    }
+
+    impl_cloned!(Cyclicity);
+}
+
+/**
+   Repeats the period on the last working day of every month, at the time it starts.
+*/
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
+pub struct LastWorkingDayOfMonth(pub WorkingDays);
+
+impl LastWorkingDayOfMonth {
+    fn start_in_month(&self, origin: DateTime<Utc>, months: i32) -> Option<DateTime<Utc>> {
+        let (year, month) = month_after(origin, months + 1);
+        let last_day = NaiveDate::from_ymd_opt(year, month, 1)?.pred_opt()?;
+        let date = self.0.preceding(chrono::Date::from_utc(last_day, Utc))?;
+
+        date.and_time(origin.time())
+    }
+}
+
+#[cfg_attr(feature = "serde_support", typetag::serde)]
+impl Cyclicity for LastWorkingDayOfMonth {
+    fn next_start_after(&self, period: &dyn Period, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let origin = period.get_date_time_start();
+        let months = months_between(origin, after).max(0);
+
+        (months..months + 13)
+            .filter_map(|months| self.start_in_month(origin, months))
+            .find(|start| *start >= origin && *start > after)
+    }
+
+    fn previous_start_before(
+        &self,
+        period: &dyn Period,
+        before: DateTime<Utc>,
+    ) -> Option<DateTime<Utc>> {
+        let origin = period.get_date_time_start();
+        let months = months_between(origin, before);
+
+        (0..=months)
+            .rev()
+            .filter_map(|months| self.start_in_month(origin, months))
+            .find(|start| *start >= origin && *start < before)
+    }
+
+    fn describe_in(&self, _period: &dyn Period, locale: &dyn Localization) -> String {
+        locale.last_working_day_of_month()
+    }
+
+    impl_cloned!(Cyclicity);
+}
+
+/**
+   Moves every start of the rule landing on a non-working day onto a working one, keeping
+   its time, e.g. a payment due on the 15th made on the following business day. Starts
+   without any working day within a year are dropped, and starts moved before the period
+   start at the period itself instead.
+*/
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
+pub struct Adjusted {
+    #[cfg_attr(feature = "serde_support", serde(with = "crate::serde::cyclicity"))]
+    #[cfg_attr(feature = "json_schema", schemars(with = "Box<dyn Cyclicity>"))]
+    pub rule: Box<dyn Cyclicity>,
+    pub working_days: WorkingDays,
+    pub adjustment: BusinessDayAdjustment,
+}
+
+impl Adjusted {
+    fn adjust(&self, origin: DateTime<Utc>, start: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let adjusted = self
+            .working_days
+            .adjust(start.date(), self.adjustment)?
+            .and_time(start.time())?;
+
+        Some(adjusted.max(origin))
+    }
+}
+
+#[cfg_attr(feature = "serde_support", typetag::serde)]
+impl Cyclicity for Adjusted {
+    fn next_start_after(&self, period: &dyn Period, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let origin = period.get_date_time_start();
+        let reach = Duration::days(ADJUSTMENT_REACH);
+        let mut earliest: Option<DateTime<Utc>> = None;
+        let mut start = self.rule.next_start_after(period, after - reach);
+
+        for _ in 0..SEARCH_LIMIT {
+            let raw_start = match start {
+                Some(value) => value,
+                None => break,
+            };
+
+            if let Some(earliest) = earliest {
+                if raw_start - reach > earliest {
+                    break;
+                }
+            }
+
+            if let Some(adjusted) = self
+                .adjust(origin, raw_start)
+                .filter(|adjusted| *adjusted > after)
+            {
+                earliest = Some(earliest.map_or(adjusted, |earliest| earliest.min(adjusted)));
+            }
+
+            start = self.rule.next_start_after(period, raw_start);
+        }
+
+        earliest
+    }
+
+    fn previous_start_before(
+        &self,
+        period: &dyn Period,
+        before: DateTime<Utc>,
+    ) -> Option<DateTime<Utc>> {
+        let origin = period.get_date_time_start();
+        let reach = Duration::days(ADJUSTMENT_REACH);
+        let mut latest: Option<DateTime<Utc>> = None;
+        let mut start = self.rule.previous_start_before(period, before + reach);
+
+        for _ in 0..SEARCH_LIMIT {
+            let raw_start = match start {
+                Some(value) => value,
+                None => break,
+            };
+
+            if let Some(latest) = latest {
+                if raw_start + reach < latest {
+                    break;
+                }
+            }
+
+            if let Some(adjusted) = self
+                .adjust(origin, raw_start)
+                .filter(|adjusted| *adjusted < before)
+            {
+                latest = Some(latest.map_or(adjusted, |latest| latest.max(adjusted)));
+            }
+
+            start = self.rule.previous_start_before(period, raw_start);
+        }
+
+        latest
+    }
+
+    fn describe_in(&self, period: &dyn Period, locale: &dyn Localization) -> String {
+        locale.adjusted(&self.rule.describe_in(period, locale), self.adjustment)
+    }
+
+    impl_cloned!(Cyclicity);
+}
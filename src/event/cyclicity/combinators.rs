@@ -0,0 +1,298 @@
+use chrono::{DateTime, Duration, Utc};
+
+#[cfg(feature = "serde_support")]
+use serde::{Deserialize, Serialize};
+
+use super::Cyclicity;
+use crate::{
+    chrono::DurationDef,
+    event::{
+        describe::{describe_merged, follows_period},
+        period::Period,
+        Localization,
+    },
+};
+
+/// Upper bound of steps taken while looking for a start satisfying all the combined rules.
+const SEARCH_LIMIT: usize = 1024;
+
+fn describe_all(
+    rules: &[Box<dyn Cyclicity>],
+    period: &dyn Period,
+    locale: &dyn Localization,
+) -> Vec<String> {
+    rules
+        .iter()
+        .map(|rule| rule.describe_in(period, locale))
+        .collect()
+}
+
+fn is_start_of(rule: &dyn Cyclicity, period: &dyn Period, start: DateTime<Utc>) -> bool {
+    rule.next_start_after(period, start - Duration::nanoseconds(1)) == Some(start)
+}
+
+fn never_starts(rule: &dyn Cyclicity, period: &dyn Period) -> bool {
+    rule.next_start_after(
+        period,
+        period.get_date_time_start() - Duration::nanoseconds(1),
+    )
+    .is_none()
+}
+
+/**
+   Starts whenever any of the rules does, e.g. "the 1st and the 15th of each month".
+*/
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
+pub struct AnyOf(
+    #[cfg_attr(
+        feature = "serde_support",
+        serde(with = "crate::serde::cyclicity::vec")
+    )]
+    #[cfg_attr(feature = "json_schema", schemars(with = "Vec<Box<dyn Cyclicity>>"))]
+    pub Vec<Box<dyn Cyclicity>>,
+);
+
+#[cfg_attr(feature = "serde_support", typetag::serde)]
+impl Cyclicity for AnyOf {
+    fn next_start_after(&self, period: &dyn Period, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        self.0
+            .iter()
+            .filter_map(|rule| rule.next_start_after(period, after))
+            .min()
+    }
+
+    fn previous_start_before(
+        &self,
+        period: &dyn Period,
+        before: DateTime<Utc>,
+    ) -> Option<DateTime<Utc>> {
+        self.0
+            .iter()
+            .filter_map(|rule| rule.previous_start_before(period, before))
+            .max()
+    }
+
+    fn describe_in(&self, period: &dyn Period, locale: &dyn Localization) -> String {
+        if self.0.is_empty() {
+            return locale.never();
+        }
+
+        match describe_merged(&self.0, period, locale) {
+            Some(described) => described,
+            None => locale.any_of(&describe_all(&self.0, period, locale)),
+        }
+    }
+
+    impl_cloned!(Cyclicity);
+}
+
+/**
+   Starts only when all of the rules do.
+*/
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
+pub struct AllOf(
+    #[cfg_attr(
+        feature = "serde_support",
+        serde(with = "crate::serde::cyclicity::vec")
+    )]
+    #[cfg_attr(feature = "json_schema", schemars(with = "Vec<Box<dyn Cyclicity>>"))]
+    pub Vec<Box<dyn Cyclicity>>,
+);
+
+#[cfg_attr(feature = "serde_support", typetag::serde)]
+impl Cyclicity for AllOf {
+    fn next_start_after(&self, period: &dyn Period, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let mut after = after;
+
+        for _ in 0..SEARCH_LIMIT {
+            let starts = self
+                .0
+                .iter()
+                .map(|rule| rule.next_start_after(period, after))
+                .collect::<Option<Vec<_>>>()?;
+            let latest = *starts.iter().max()?;
+
+            if starts.iter().all(|start| *start == latest) {
+                return Some(latest);
+            }
+
+            after = latest - Duration::nanoseconds(1);
+        }
+
+        None
+    }
+
+    fn previous_start_before(
+        &self,
+        period: &dyn Period,
+        before: DateTime<Utc>,
+    ) -> Option<DateTime<Utc>> {
+        let mut before = before;
+
+        for _ in 0..SEARCH_LIMIT {
+            let starts = self
+                .0
+                .iter()
+                .map(|rule| rule.previous_start_before(period, before))
+                .collect::<Option<Vec<_>>>()?;
+            let earliest = *starts.iter().min()?;
+
+            if starts.iter().all(|start| *start == earliest) {
+                return Some(earliest);
+            }
+
+            before = earliest + Duration::nanoseconds(1);
+        }
+
+        None
+    }
+
+    fn describe_in(&self, period: &dyn Period, locale: &dyn Localization) -> String {
+        if never_starts(self, period) {
+            return locale.never();
+        }
+
+        locale.all_of(&describe_all(&self.0, period, locale))
+    }
+
+    impl_cloned!(Cyclicity);
+}
+
+/**
+   Starts when the first rule does, unless the second one starts at the same instant,
+   e.g. "every weekday except public holidays".
+*/
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
+pub struct Except(
+    #[cfg_attr(feature = "serde_support", serde(with = "crate::serde::cyclicity"))]
+    #[cfg_attr(feature = "json_schema", schemars(with = "Box<dyn Cyclicity>"))]
+    pub Box<dyn Cyclicity>,
+    #[cfg_attr(feature = "serde_support", serde(with = "crate::serde::cyclicity"))]
+    #[cfg_attr(feature = "json_schema", schemars(with = "Box<dyn Cyclicity>"))]
+    pub Box<dyn Cyclicity>,
+);
+
+#[cfg_attr(feature = "serde_support", typetag::serde)]
+impl Cyclicity for Except {
+    fn next_start_after(&self, period: &dyn Period, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let mut start = self.0.next_start_after(period, after)?;
+
+        for _ in 0..SEARCH_LIMIT {
+            if !is_start_of(self.1.as_ref(), period, start) {
+                return Some(start);
+            }
+
+            start = self.0.next_start_after(period, start)?;
+        }
+
+        None
+    }
+
+    fn previous_start_before(
+        &self,
+        period: &dyn Period,
+        before: DateTime<Utc>,
+    ) -> Option<DateTime<Utc>> {
+        let mut start = self.0.previous_start_before(period, before)?;
+
+        for _ in 0..SEARCH_LIMIT {
+            if !is_start_of(self.1.as_ref(), period, start) {
+                return Some(start);
+            }
+
+            start = self.0.previous_start_before(period, start)?;
+        }
+
+        None
+    }
+
+    fn describe_in(&self, period: &dyn Period, locale: &dyn Localization) -> String {
+        if never_starts(self.1.as_ref(), period) {
+            return self.0.describe_in(period, locale);
+        }
+
+        locale.except(
+            &self.0.describe_in(period, locale),
+            &self.1.describe_in(period, locale),
+        )
+    }
+
+    impl_cloned!(Cyclicity);
+}
+
+/**
+   Moves every start of the rule by a fixed duration. Like any built-in cycle it starts with the
+   repeated period itself, and moved starts preceding it are ignored. Combined with `AnyOf`, it
+   repeats the same period at several points of a cycle, e.g. on the 1st and, shifted by 14
+   days, on the 15th of each month.
+*/
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
+pub struct Shifted(
+    pub DurationDef,
+    #[cfg_attr(feature = "serde_support", serde(with = "crate::serde::cyclicity"))]
+    #[cfg_attr(feature = "json_schema", schemars(with = "Box<dyn Cyclicity>"))]
+    pub Box<dyn Cyclicity>,
+);
+
+impl Shifted {
+    pub fn new(by: Duration, rule: Box<dyn Cyclicity>) -> Self {
+        Self(DurationDef(by), rule)
+    }
+}
+
+#[cfg_attr(feature = "serde_support", typetag::serde)]
+impl Cyclicity for Shifted {
+    fn next_start_after(&self, period: &dyn Period, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let origin = period.get_date_time_start();
+        let by = (self.0).0;
+
+        if after < origin {
+            return Some(origin);
+        }
+
+        self.1
+            .next_start_after(period, after - by)
+            .map(|start| start + by)
+    }
+
+    fn previous_start_before(
+        &self,
+        period: &dyn Period,
+        before: DateTime<Utc>,
+    ) -> Option<DateTime<Utc>> {
+        let origin = period.get_date_time_start();
+        let by = (self.0).0;
+
+        if before <= origin {
+            return None;
+        }
+
+        self.1
+            .previous_start_before(period, before - by)
+            .map(|start| start + by)
+            .filter(|start| *start > origin)
+            .or(Some(origin))
+    }
+
+    fn describe_in(&self, period: &dyn Period, locale: &dyn Localization) -> String {
+        let by = (self.0).0;
+
+        if by.num_seconds() % 86400 == 0 && follows_period(self.1.as_ref()) {
+            let moved = period.with_new_date_time_start(period.get_date_time_start() + by);
+
+            self.1.describe_in(moved.as_ref(), locale)
+        } else {
+            locale.shifted(&self.1.describe_in(period, locale), by)
+        }
+    }
+
+    impl_cloned!(Cyclicity);
+}
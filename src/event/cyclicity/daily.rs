@@ -1,22 +1,37 @@
-use chrono::{Date, Utc};
+use chrono::{DateTime, Duration, Utc};
 
 #[cfg(feature = "serde_support")]
 use serde::{Deserialize, Serialize};
 
-use super::Cyclicity;
-use crate::event::period::Period;
+use super::{step_after, step_before, Cyclicity};
+use crate::event::{period::Period, Localization};
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct DailyCycle;
 
 #[cfg_attr(feature = "serde_support", typetag::serde)]
 impl Cyclicity for DailyCycle {
-    fn same_period_at(
+    fn next_start_after(&self, period: &dyn Period, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        Some(step_after(
+            period.get_date_time_start(),
+            Duration::days(1),
+            after,
+        ))
+    }
+
+    fn previous_start_before(
         &self,
-        same_period: Box<dyn Period>,
-        at_date: Date<Utc>,
-    ) -> Option<Box<dyn Period>> {
-        Some(same_period.with_new_start(at_date))
+        period: &dyn Period,
+        before: DateTime<Utc>,
+    ) -> Option<DateTime<Utc>> {
+        step_before(period.get_date_time_start(), Duration::days(1), before)
     }
+
+    fn describe_in(&self, _period: &dyn Period, locale: &dyn Localization) -> String {
+        locale.daily(1)
+    }
+
+    impl_cloned!(Cyclicity);
 }
@@ -0,0 +1,90 @@
+use chrono::{DateTime, Datelike, Duration, Utc};
+
+#[cfg(feature = "serde_support")]
+use serde::{Deserialize, Serialize};
+
+use super::Cyclicity;
+use crate::{
+    computus::{orthodox_easter, western_easter},
+    event::{period::Period, Localization},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
+pub enum EasterReckoning {
+    /// Easter of the Gregorian calendar, as Western churches observe it.
+    Western,
+    /// Easter of the Julian calendar, as Orthodox churches observe it.
+    Orthodox,
+}
+
+/**
+   Repeats the period every year a fixed number of days after Easter Sunday, e.g. `-2` for
+   Good Friday or `60` for Corpus Christi, at the time the period starts. A period starting
+   on another day is skipped, the first instance being the next matching day.
+*/
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
+pub struct EasterOffsetCycle {
+    pub offset: i64,
+    pub reckoning: EasterReckoning,
+}
+
+impl EasterOffsetCycle {
+    pub fn western(offset: i64) -> Self {
+        Self {
+            offset,
+            reckoning: EasterReckoning::Western,
+        }
+    }
+
+    pub fn orthodox(offset: i64) -> Self {
+        Self {
+            offset,
+            reckoning: EasterReckoning::Orthodox,
+        }
+    }
+
+    fn start_in_year(&self, origin: DateTime<Utc>, year: i32) -> DateTime<Utc> {
+        let easter = match self.reckoning {
+            EasterReckoning::Western => western_easter(year),
+            EasterReckoning::Orthodox => orthodox_easter(year),
+        };
+        let date = easter + Duration::days(self.offset);
+
+        DateTime::from_naive_utc_and_offset(date.and_time(origin.time()), Utc)
+    }
+}
+
+#[cfg_attr(feature = "serde_support", typetag::serde)]
+impl Cyclicity for EasterOffsetCycle {
+    fn next_start_after(&self, period: &dyn Period, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let origin = period.get_date_time_start();
+        let year = after.year().max(origin.year());
+
+        (year - 1..=year + 2)
+            .map(|year| self.start_in_year(origin, year))
+            .find(|start| *start >= origin && *start > after)
+    }
+
+    fn previous_start_before(
+        &self,
+        period: &dyn Period,
+        before: DateTime<Utc>,
+    ) -> Option<DateTime<Utc>> {
+        let origin = period.get_date_time_start();
+
+        (origin.year() - 1..=before.year() + 1)
+            .rev()
+            .map(|year| self.start_in_year(origin, year))
+            .find(|start| *start >= origin && *start < before)
+    }
+
+    fn describe_in(&self, _period: &dyn Period, locale: &dyn Localization) -> String {
+        locale.easter(self.offset, self.reckoning)
+    }
+
+    impl_cloned!(Cyclicity);
+}
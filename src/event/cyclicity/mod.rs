@@ -1,23 +1,178 @@
-use chrono::{Date, Utc};
-use std::fmt::Debug;
+use chrono::{Date, DateTime, Datelike, Duration, Utc};
+use std::{
+    fmt::Debug,
+    hash::{Hash, Hasher},
+};
 
-use super::Period;
+use super::{DynEq, DynHash, English, Localization, Period};
 
+/**
+   Rule repeating a period. Instances of a cycle never start before the period they repeat.
+   Built-in cycles stepping from the period, e.g. `DailyCycle` or `HourlyCycle`, also start
+   with the period itself, while those picking days by a rule, e.g. `WorkingDayCycle`,
+   `LastWorkingDayOfMonth`, `EasterOffsetCycle` or `WeekOfYearCycle`, start on the first
+   matching day at or after it.
+*/
 #[cfg_attr(feature = "serde_support", typetag::serde(tag = "type"))]
-pub trait Cyclicity: Debug + Send {
+pub trait Cyclicity: DynEq + DynHash + Debug + Send {
+    /// Start of the first instance starting strictly after the given instant.
+    fn next_start_after(&self, period: &dyn Period, after: DateTime<Utc>) -> Option<DateTime<Utc>>;
+
+    /// Start of the last instance starting strictly before the given instant.
+    fn previous_start_before(
+        &self,
+        period: &dyn Period,
+        before: DateTime<Utc>,
+    ) -> Option<DateTime<Utc>>;
+
+    fn cloned(&self) -> Box<dyn Cyclicity>;
+
+    /**
+       Describes the cycle in words for the given repeated period, e.g. "every week on
+       Monday". Cycles which don't override it are described by
+       `Localization::custom_cycle`.
+    */
+    fn describe_in(&self, _period: &dyn Period, locale: &dyn Localization) -> String {
+        locale.custom_cycle()
+    }
+
+    /// Describes the cycle in English.
+    fn describe(&self, period: &dyn Period) -> String {
+        self.describe_in(period, &English)
+    }
+
+    /// The latest started instance lasting at the given date.
     fn same_period_at(
         &self,
         same_period: Box<dyn Period>,
         at_date: Date<Utc>,
-    ) -> Option<Box<dyn Period>>;
+    ) -> Option<Box<dyn Period>> {
+        let next_midnight = (at_date + Duration::days(1)).and_hms_opt(0, 0, 0)?;
+        let start = self.previous_start_before(same_period.as_ref(), next_midnight)?;
+        let period = same_period.with_new_date_time_start(start);
+
+        if period.contains(at_date) {
+            Some(period)
+        } else {
+            None
+        }
+    }
+
+    /// Every instance starting at the given date.
+    fn periods_at(&self, same_period: Box<dyn Period>, at_date: Date<Utc>) -> Vec<Box<dyn Period>> {
+        let midnight = match at_date.and_hms_opt(0, 0, 0) {
+            Some(midnight) => midnight,
+            None => return Vec::new(),
+        };
+        let next_midnight = midnight + Duration::days(1);
+        let mut periods = Vec::new();
+        let mut start =
+            self.next_start_after(same_period.as_ref(), midnight - Duration::nanoseconds(1));
+
+        while let Some(value) = start.filter(|value| *value < next_midnight) {
+            periods.push(same_period.with_new_date_time_start(value));
+            start = self.next_start_after(same_period.as_ref(), value);
+        }
+
+        periods
+    }
+}
+
+impl PartialEq for dyn Cyclicity {
+    fn eq(&self, other: &Self) -> bool {
+        self.dyn_eq(other.as_any())
+    }
+}
+
+impl Eq for dyn Cyclicity {}
+
+impl Hash for dyn Cyclicity {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.dyn_hash(state);
+    }
+}
+
+// Derived comparisons of boxed fields coerce the right-hand side to a reference
+// (rust-lang/rust#31740).
+impl PartialEq<&Self> for Box<dyn Cyclicity> {
+    fn eq(&self, other: &&Self) -> bool {
+        self.as_ref() == other.as_ref()
+    }
+}
+
+impl Clone for Box<dyn Cyclicity> {
+    fn clone(&self) -> Self {
+        self.cloned()
+    }
+}
+
+fn step_after(origin: DateTime<Utc>, step: Duration, after: DateTime<Utc>) -> DateTime<Utc> {
+    if after < origin {
+        return origin;
+    }
+
+    let step = step.num_seconds();
+    let passed_steps = (after - origin).num_seconds().div_euclid(step) + 1;
+
+    origin + Duration::seconds(passed_steps * step)
+}
+
+fn step_before(
+    origin: DateTime<Utc>,
+    step: Duration,
+    before: DateTime<Utc>,
+) -> Option<DateTime<Utc>> {
+    if before <= origin {
+        return None;
+    }
+
+    let step = step.num_seconds();
+    let elapsed = before - origin;
+    let mut elapsed_seconds = elapsed.num_seconds();
+
+    if elapsed > Duration::seconds(elapsed_seconds) {
+        elapsed_seconds += 1;
+    }
+
+    let passed_steps = (elapsed_seconds + step - 1).div_euclid(step) - 1;
+
+    Some(origin + Duration::seconds(passed_steps * step))
+}
+
+/// Year and month lying the given number of months after the moment's one.
+fn month_after(moment: DateTime<Utc>, months: i32) -> (i32, u32) {
+    let month_index = moment.year() * 12 + moment.month0() as i32 + months;
+
+    (
+        month_index.div_euclid(12),
+        month_index.rem_euclid(12) as u32 + 1,
+    )
+}
+
+fn months_between(origin: DateTime<Utc>, moment: DateTime<Utc>) -> i32 {
+    (moment.year() - origin.year()) * 12 + moment.month0() as i32 - origin.month0() as i32
 }
 
 mod annual;
+mod business_days;
+mod combinators;
 mod daily;
+mod easter;
 mod monthly;
+mod on_dates;
+mod recurrence;
+mod sub_daily;
+mod week_of_year;
 mod weekly;
 
 pub use annual::*;
+pub use business_days::*;
+pub use combinators::*;
 pub use daily::*;
+pub use easter::*;
 pub use monthly::*;
+pub use on_dates::*;
+pub use recurrence::*;
+pub use sub_daily::{HourlyCycle, MinutelyCycle, TimeWindow};
+pub use week_of_year::*;
 pub use weekly::*;
@@ -1,32 +1,70 @@
-use chrono::{Date, Datelike, Duration, Utc};
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
 
 #[cfg(feature = "serde_support")]
 use serde::{Deserialize, Serialize};
 
-use super::Cyclicity;
-use crate::event::period::Period;
+use super::{month_after, months_between, Cyclicity};
+use crate::event::{period::Period, Localization};
 
-#[derive(Debug)]
+/**
+   Repeats the period on the same day of every month. Months lacking that day (e.g. the 31st)
+   are skipped.
+*/
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct MonthlyCycle;
 
+impl MonthlyCycle {
+    fn start_in_month(origin: DateTime<Utc>, months: i32) -> Option<DateTime<Utc>> {
+        let (year, month) = month_after(origin, months);
+        let date = NaiveDate::from_ymd_opt(year, month, origin.day())?;
+
+        Some(DateTime::from_naive_utc_and_offset(
+            date.and_time(origin.time()),
+            Utc,
+        ))
+    }
+}
+
 #[cfg_attr(feature = "serde_support", typetag::serde)]
 impl Cyclicity for MonthlyCycle {
-    fn same_period_at(
+    fn next_start_after(&self, period: &dyn Period, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let origin = period.get_date_time_start();
+
+        if after < origin {
+            return Some(origin);
+        }
+
+        let months = months_between(origin, after);
+
+        (months..months + 13)
+            .filter_map(|months| Self::start_in_month(origin, months))
+            .find(|start| *start > after)
+    }
+
+    fn previous_start_before(
         &self,
-        same_period: Box<dyn Period>,
-        at_date: Date<Utc>,
-    ) -> Option<Box<dyn Period>> {
-        let (starting_month_day, ending_month_day) = same_period.as_days_of_month();
-
-        match at_date.day() {
-            value if value >= starting_month_day && value <= ending_month_day => {
-                let day_difference = (value - starting_month_day) as i64;
-                let at_date = at_date - Duration::days(day_difference);
-
-                Some(same_period.with_new_start(at_date))
-            }
-            _ => None,
+        period: &dyn Period,
+        before: DateTime<Utc>,
+    ) -> Option<DateTime<Utc>> {
+        let origin = period.get_date_time_start();
+
+        if before <= origin {
+            return None;
         }
+
+        let months = months_between(origin, before);
+
+        (0..=months)
+            .rev()
+            .filter_map(|months| Self::start_in_month(origin, months))
+            .find(|start| *start < before)
+    }
+
+    fn describe_in(&self, period: &dyn Period, locale: &dyn Localization) -> String {
+        locale.monthly(&[period.get_date_time_start().day()])
     }
+
+    impl_cloned!(Cyclicity);
 }
@@ -0,0 +1,74 @@
+use chrono::{DateTime, Utc};
+use std::iter;
+
+#[cfg(feature = "serde_support")]
+use serde::{Deserialize, Serialize};
+
+use super::Cyclicity;
+use crate::{
+    chrono::DateTimeDef,
+    event::{period::Period, Localization},
+};
+
+/**
+   Starts at explicitly listed instants besides the start of the repeated period, like
+   iCalendar's `RDATE`. Starts preceding the repeated period are ignored. Combine it with
+   `AnyOf` to add extra starts to another rule.
+*/
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
+pub struct OnDates(Vec<DateTimeDef>);
+
+impl OnDates {
+    pub fn new(mut starts: Vec<DateTime<Utc>>) -> Self {
+        starts.sort();
+        starts.dedup();
+
+        Self(starts.into_iter().map(DateTimeDef).collect())
+    }
+
+    pub fn starts(&self) -> impl Iterator<Item = DateTime<Utc>> + '_ {
+        self.0.iter().map(|start| start.0)
+    }
+}
+
+#[cfg_attr(feature = "serde_support", typetag::serde)]
+impl Cyclicity for OnDates {
+    fn next_start_after(&self, period: &dyn Period, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let origin = period.get_date_time_start();
+
+        iter::once(origin)
+            .chain(self.starts())
+            .filter(|start| *start >= origin && *start > after)
+            .min()
+    }
+
+    fn previous_start_before(
+        &self,
+        period: &dyn Period,
+        before: DateTime<Utc>,
+    ) -> Option<DateTime<Utc>> {
+        let origin = period.get_date_time_start();
+
+        iter::once(origin)
+            .chain(self.starts())
+            .filter(|start| *start >= origin && *start < before)
+            .max()
+    }
+
+    fn describe_in(&self, period: &dyn Period, locale: &dyn Localization) -> String {
+        let origin = period.get_date_time_start();
+        let mut dates: Vec<_> = iter::once(origin)
+            .chain(self.starts())
+            .filter(|start| *start >= origin)
+            .map(|start| start.date_naive())
+            .collect();
+
+        dates.dedup();
+
+        locale.on_dates(&dates)
+    }
+
+    impl_cloned!(Cyclicity);
+}
@@ -0,0 +1,127 @@
+use std::ops::Deref;
+
+#[cfg(feature = "serde_support")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use super::*;
+
+/**
+   A cyclicity held by value: one of the built-in cycles, or any other `Cyclicity` boxed as
+   `Custom`.
+
+   It dereferences to `dyn Cyclicity`, so built-in cycles are queried without a trip through
+   the heap. Serialized, it is indistinguishable from the boxed cyclicity.
+
+   # Examples
+
+   ```rust
+   use dateless::prelude::*;
+
+   let boxed: Box<dyn Cyclicity> = Box::new(WeeklyCycle);
+
+   assert_eq!(Recurrence::from(boxed), Recurrence::WeeklyCycle(WeeklyCycle));
+   ```
+*/
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Recurrence {
+    DailyCycle(DailyCycle),
+    WeeklyCycle(WeeklyCycle),
+    MonthlyCycle(MonthlyCycle),
+    AnnualCycle(AnnualCycle),
+    HourlyCycle(HourlyCycle),
+    MinutelyCycle(MinutelyCycle),
+    AnyOf(AnyOf),
+    AllOf(AllOf),
+    Except(Except),
+    Shifted(Shifted),
+    OnDates(OnDates),
+    WorkingDayCycle(WorkingDayCycle),
+    LastWorkingDayOfMonth(LastWorkingDayOfMonth),
+    Adjusted(Adjusted),
+    EasterOffsetCycle(EasterOffsetCycle),
+    WeekOfYearCycle(WeekOfYearCycle),
+    Custom(Box<dyn Cyclicity>),
+}
+
+macro_rules! impl_built_in {
+    ($($kind:ident),*) => {
+        impl Deref for Recurrence {
+            type Target = dyn Cyclicity;
+
+            fn deref(&self) -> &Self::Target {
+                match self {
+                    $(Self::$kind(cyclicity) => cyclicity,)*
+                    Self::Custom(cyclicity) => cyclicity.as_ref(),
+                }
+            }
+        }
+
+        $(
+            impl From<$kind> for Recurrence {
+                fn from(cyclicity: $kind) -> Self {
+                    Self::$kind(cyclicity)
+                }
+            }
+        )*
+
+        /// Unboxes built-in cycles, keeping the other cyclicities as `Custom`.
+        impl From<Box<dyn Cyclicity>> for Recurrence {
+            fn from(cyclicity: Box<dyn Cyclicity>) -> Self {
+                let any = cyclicity.as_ref().as_any();
+
+                $(
+                    if let Some(cyclicity) = any.downcast_ref::<$kind>() {
+                        return Self::$kind(cyclicity.clone());
+                    }
+                )*
+
+                Self::Custom(cyclicity)
+            }
+        }
+
+        impl<T: Cyclicity> From<Box<T>> for Recurrence {
+            fn from(cyclicity: Box<T>) -> Self {
+                Self::from(cyclicity as Box<dyn Cyclicity>)
+            }
+        }
+    };
+}
+
+impl_built_in!(
+    DailyCycle,
+    WeeklyCycle,
+    MonthlyCycle,
+    AnnualCycle,
+    HourlyCycle,
+    MinutelyCycle,
+    AnyOf,
+    AllOf,
+    Except,
+    Shifted,
+    OnDates,
+    WorkingDayCycle,
+    LastWorkingDayOfMonth,
+    Adjusted,
+    EasterOffsetCycle,
+    WeekOfYearCycle
+);
+
+#[cfg(feature = "serde_support")]
+impl Serialize for Recurrence {
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        crate::serde::cyclicity::serialize_dyn(&**self, s)
+    }
+}
+
+#[cfg(feature = "serde_support")]
+impl<'de> Deserialize<'de> for Recurrence {
+    fn deserialize<D>(d: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        crate::serde::cyclicity::deserialize(d).map(Self::from)
+    }
+}
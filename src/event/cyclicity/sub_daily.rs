@@ -0,0 +1,177 @@
+use chrono::{DateTime, Duration, NaiveTime, Utc};
+use std::num::NonZeroU32;
+
+#[cfg(feature = "serde_support")]
+use serde::{Deserialize, Serialize};
+
+use super::{step_after, step_before, Cyclicity};
+use crate::event::{describe::describe_sub_daily, period::Period, Localization};
+
+/**
+   Time of day range `[from, to)` in which sub-daily cycles are allowed to start. When `from`
+   is later than `to`, the window wraps over midnight.
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
+pub struct TimeWindow(pub NaiveTime, pub NaiveTime);
+
+impl TimeWindow {
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        if self.0 <= self.1 {
+            time >= self.0 && time < self.1
+        } else {
+            time >= self.0 || time < self.1
+        }
+    }
+}
+
+fn is_within(window: Option<TimeWindow>, start: DateTime<Utc>) -> bool {
+    match window {
+        Some(window) => window.contains(start.time()),
+        None => true,
+    }
+}
+
+/// Number of steps after which starts fall at the same times of day again.
+fn steps_per_cycle(step: Duration) -> i64 {
+    let day = Duration::days(1).num_seconds();
+    let (mut a, mut b) = (step.num_seconds(), day);
+
+    while b != 0 {
+        let rest = a % b;
+        a = b;
+        b = rest;
+    }
+
+    day / a
+}
+
+fn next_start_within(
+    origin: DateTime<Utc>,
+    step: Duration,
+    window: Option<TimeWindow>,
+    after: DateTime<Utc>,
+) -> Option<DateTime<Utc>> {
+    let mut start = step_after(origin, step, after);
+
+    for _ in 0..=steps_per_cycle(step) {
+        if start == origin || is_within(window, start) {
+            return Some(start);
+        }
+
+        start = step_after(origin, step, start);
+    }
+
+    None
+}
+
+fn previous_start_within(
+    origin: DateTime<Utc>,
+    step: Duration,
+    window: Option<TimeWindow>,
+    before: DateTime<Utc>,
+) -> Option<DateTime<Utc>> {
+    let mut start = step_before(origin, step, before)?;
+
+    for _ in 0..=steps_per_cycle(step) {
+        if start == origin || is_within(window, start) {
+            return Some(start);
+        }
+
+        start = step_before(origin, step, start)?;
+    }
+
+    None
+}
+
+/**
+   Declares a cycle repeating the period every `interval` units shorter than a day, optionally
+   restricted to a time window. The cycles only differ in their unit, but each one keeps its own
+   type so that its serialized tag stays the same.
+*/
+macro_rules! sub_daily_cycle {
+    ($name:ident, $unit:literal, $duration:path, $minutes:expr) => {
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        #[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+        #[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
+        pub struct $name {
+            pub interval: NonZeroU32,
+            #[cfg_attr(feature = "serde_support", serde(default))]
+            pub window: Option<TimeWindow>,
+        }
+
+        impl $name {
+            #[doc = concat!("Repeats the period every `interval` ", $unit, ".")]
+            ///
+            /// # Panics
+            ///
+            /// Panics if `interval` is 0; see `new_opt`.
+            pub fn new(interval: u32) -> Self {
+                Self::new_opt(interval).expect("the interval of a cycle must be positive")
+            }
+
+            #[doc = concat!("Repeats the period every `interval` ", $unit, ", unless `interval` is 0.")]
+            pub fn new_opt(interval: u32) -> Option<Self> {
+                Some(Self {
+                    interval: NonZeroU32::new(interval)?,
+                    window: None,
+                })
+            }
+
+            pub fn within(self, window: TimeWindow) -> Self {
+                Self {
+                    window: Some(window),
+                    ..self
+                }
+            }
+
+            fn step(&self) -> Duration {
+                $duration(self.interval.get() as i64)
+            }
+        }
+
+        #[cfg_attr(feature = "serde_support", typetag::serde)]
+        impl Cyclicity for $name {
+            fn next_start_after(
+                &self,
+                period: &dyn Period,
+                after: DateTime<Utc>,
+            ) -> Option<DateTime<Utc>> {
+                next_start_within(
+                    period.get_date_time_start(),
+                    self.step(),
+                    self.window,
+                    after,
+                )
+            }
+
+            fn previous_start_before(
+                &self,
+                period: &dyn Period,
+                before: DateTime<Utc>,
+            ) -> Option<DateTime<Utc>> {
+                previous_start_within(
+                    period.get_date_time_start(),
+                    self.step(),
+                    self.window,
+                    before,
+                )
+            }
+
+            fn describe_in(&self, period: &dyn Period, locale: &dyn Localization) -> String {
+                describe_sub_daily(
+                    self.interval.get() as i64 * $minutes,
+                    self.window.as_ref(),
+                    period,
+                    locale,
+                )
+            }
+
+            impl_cloned!(Cyclicity);
+        }
+    };
+}
+
+sub_daily_cycle!(HourlyCycle, "hours", Duration::hours, 60);
+sub_daily_cycle!(MinutelyCycle, "minutes", Duration::minutes, 1);
@@ -0,0 +1,80 @@
+use chrono::{DateTime, Datelike, Duration, Utc};
+
+#[cfg(feature = "serde_support")]
+use serde::{Deserialize, Serialize};
+
+use super::Cyclicity;
+use crate::{
+    event::{period::Period, Localization},
+    week::WeekStart,
+};
+
+/// How many years ahead a year holding the week is looked for, since only some have a 53rd.
+const YEARS_AHEAD: i32 = 8;
+
+/**
+   Repeats the period every year in the given week, on the weekday and at the time it starts,
+   like iCalendar's `BYWEEKNO`. Weeks are numbered as `WeekStart` does, counting from the end
+   of the year when negative; years without the week, e.g. without a 53rd one, are skipped.
+   A period starting in another week is skipped too, the first instance being in the next
+   matching one.
+*/
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
+pub struct WeekOfYearCycle {
+    pub week: i32,
+    pub week_start: WeekStart,
+}
+
+impl WeekOfYearCycle {
+    /// The given ISO week.
+    pub fn new(week: i32) -> Self {
+        Self {
+            week,
+            week_start: WeekStart::ISO,
+        }
+    }
+
+    pub fn with_week_start(self, week_start: WeekStart) -> Self {
+        Self { week_start, ..self }
+    }
+
+    fn start_in_year(&self, origin: DateTime<Utc>, year: i32) -> Option<DateTime<Utc>> {
+        let first_day = self.week_start.first_day_of_week(year, self.week)?;
+        let days = self.week_start.number(origin.weekday()) as i64 - 1;
+
+        (first_day + Duration::days(days)).and_time(origin.time())
+    }
+}
+
+#[cfg_attr(feature = "serde_support", typetag::serde)]
+impl Cyclicity for WeekOfYearCycle {
+    fn next_start_after(&self, period: &dyn Period, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let origin = period.get_date_time_start();
+        let year = after.year().max(origin.year());
+
+        (year - 1..=year + YEARS_AHEAD)
+            .filter_map(|year| self.start_in_year(origin, year))
+            .find(|start| *start >= origin && *start > after)
+    }
+
+    fn previous_start_before(
+        &self,
+        period: &dyn Period,
+        before: DateTime<Utc>,
+    ) -> Option<DateTime<Utc>> {
+        let origin = period.get_date_time_start();
+
+        (origin.year() - 1..=before.year() + 1)
+            .rev()
+            .filter_map(|year| self.start_in_year(origin, year))
+            .find(|start| *start >= origin && *start < before)
+    }
+
+    fn describe_in(&self, period: &dyn Period, locale: &dyn Localization) -> String {
+        locale.week_of_year(self.week, period.get_date_time_start().weekday())
+    }
+
+    impl_cloned!(Cyclicity);
+}
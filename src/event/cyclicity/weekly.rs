@@ -1,33 +1,41 @@
-use chrono::{Date, Datelike, Duration, Utc};
+use chrono::{DateTime, Datelike, Duration, Utc};
 
 #[cfg(feature = "serde_support")]
 use serde::{Deserialize, Serialize};
 
-use super::Cyclicity;
-use crate::event::period::Period;
+use super::{step_after, step_before, Cyclicity};
+use crate::event::{period::Period, Localization};
 
-#[derive(Debug)]
+/**
+   Repeats the period every 7 days from its start, so it doesn't depend on the `WeekStart` of
+   a calendar.
+*/
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct WeeklyCycle;
 
 #[cfg_attr(feature = "serde_support", typetag::serde)]
 impl Cyclicity for WeeklyCycle {
-    fn same_period_at(
-        &self,
-        same_period: Box<dyn Period>,
-        at_date: Date<Utc>,
-    ) -> Option<Box<dyn Period>> {
-        let (starting_weekday, ending_weekday) = same_period.as_weekdays();
-        use Datelike;
+    fn next_start_after(&self, period: &dyn Period, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        Some(step_after(
+            period.get_date_time_start(),
+            Duration::weeks(1),
+            after,
+        ))
+    }
 
-        match at_date.weekday().number_from_monday() {
-            value if value >= starting_weekday && value <= ending_weekday => {
-                let day_difference = (value - starting_weekday) as i64;
-                let at_date = at_date - Duration::days(day_difference);
+    fn previous_start_before(
+        &self,
+        period: &dyn Period,
+        before: DateTime<Utc>,
+    ) -> Option<DateTime<Utc>> {
+        step_before(period.get_date_time_start(), Duration::weeks(1), before)
+    }
 
-                Some(same_period.with_new_start(at_date))
-            }
-            _ => None,
-        }
+    fn describe_in(&self, period: &dyn Period, locale: &dyn Localization) -> String {
+        locale.weekly(1, &[period.get_date_time_start().weekday()])
     }
+
+    impl_cloned!(Cyclicity);
 }
@@ -0,0 +1,641 @@
+use super::{
+    cyclicity::{
+        AnnualCycle, DailyCycle, HourlyCycle, MinutelyCycle, MonthlyCycle, Shifted, TimeWindow,
+        WeeklyCycle,
+    },
+    Cyclicity, EasterReckoning, Event, Period, PeriodKind,
+};
+use crate::working_days::BusinessDayAdjustment;
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Weekday};
+
+/**
+   Wording of event and cycle descriptions, see `Event::describe_in` and
+   `Cyclicity::describe_in`. Every method returns a fragment, composed by the ones taking
+   other fragments, so a language is free to order them as its grammar wants.
+
+   [`English`](English) is the default.
+*/
+pub trait Localization {
+    fn date(&self, date: NaiveDate) -> String;
+    fn time(&self, time: NaiveTime) -> String;
+    fn weekday(&self, weekday: Weekday) -> String;
+
+    fn daily(&self, interval: u32) -> String;
+    /// Weekdays are ordered from Monday, whatever the `WeekStart` of the calendar.
+    fn weekly(&self, interval: u32, weekdays: &[Weekday]) -> String;
+    /// Days of the month are ordered.
+    fn monthly(&self, days: &[u32]) -> String;
+    fn annual(&self, month: u32, day: u32) -> String;
+    fn hourly(&self, interval: u32, window: Option<&TimeWindow>) -> String;
+    fn minutely(&self, interval: u32, window: Option<&TimeWindow>) -> String;
+    fn working_days(&self) -> String;
+    fn last_working_day_of_month(&self) -> String;
+    fn easter(&self, offset: i64, reckoning: EasterReckoning) -> String;
+    /// Weeks are counted from the end of the year when negative, `-1` being the last one.
+    fn week_of_year(&self, week: i32, weekday: Weekday) -> String;
+    fn on_dates(&self, dates: &[NaiveDate]) -> String;
+    fn any_of(&self, cycles: &[String]) -> String;
+    fn all_of(&self, cycles: &[String]) -> String;
+    fn except(&self, cycle: &str, excluded: &str) -> String;
+    fn shifted(&self, cycle: &str, by: Duration) -> String;
+    fn adjusted(&self, cycle: &str, adjustment: BusinessDayAdjustment) -> String;
+    /// A cycle which doesn't describe itself.
+    fn custom_cycle(&self) -> String;
+    /// A cycle which never starts, e.g. an empty `AnyOf`.
+    fn never(&self) -> String;
+
+    /// A single occurrence of whole days.
+    fn whole_days(&self, from: NaiveDate, to: NaiveDate) -> String;
+    /// A single occurrence, `from` and `to` being equal for moments.
+    fn between(&self, from: NaiveDateTime, to: NaiveDateTime) -> String;
+    fn open_ended(&self, from: NaiveDateTime) -> String;
+    /// A description of floating times, which are local to the reader.
+    fn local_time(&self, described: &str) -> String;
+
+    /// Length of repeated whole days.
+    fn all_day(&self, days: i64) -> String;
+    /// Times of repeated occurrences, ending `days` later than they start.
+    fn times(&self, from: NaiveTime, to: NaiveTime, days: i64) -> String;
+    fn recurring(&self, cycle: &str, times: &str, first: NaiveDate) -> String;
+    fn except_on(&self, described: &str, dates: &[NaiveDate]) -> String;
+    /// Exceptions of cycles starting several times a day, which dates alone don't tell apart.
+    fn except_at(&self, described: &str, instants: &[NaiveDateTime]) -> String;
+    /// Turns the description of an event into a sentence.
+    fn sentence(&self, described: &str) -> String;
+}
+
+/**
+   English descriptions, e.g. "Every week on Monday from 09:00 to 09:15, starting May 3,
+   2021".
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct English;
+
+const MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+impl English {
+    fn join(items: &[String], conjunction: &str) -> String {
+        match items {
+            [] => String::new(),
+            [item] => item.clone(),
+            [init @ .., last] => format!("{} {} {}", init.join(", "), conjunction, last),
+        }
+    }
+
+    fn ordinal(number: u32) -> String {
+        let suffix = match (number % 10, number % 100) {
+            (_, 11..=13) => "th",
+            (1, _) => "st",
+            (2, _) => "nd",
+            (3, _) => "rd",
+            _ => "th",
+        };
+
+        format!("{}{}", number, suffix)
+    }
+
+    fn count(count: i64, unit: &str) -> String {
+        match count {
+            1 => format!("1 {}", unit),
+            _ => format!("{} {}s", count, unit),
+        }
+    }
+
+    fn every(interval: u32, unit: &str, window: Option<&TimeWindow>) -> String {
+        let every = match interval {
+            1 => format!("every {}", unit),
+            _ => format!("every {} {}s", interval, unit),
+        };
+
+        match window {
+            Some(TimeWindow(from, to)) => format!(
+                "{} between {} and {}",
+                every,
+                English.time(*from),
+                English.time(*to)
+            ),
+            None => every,
+        }
+    }
+
+    /// The length of a non-zero duration, in the largest unit it is a whole number of.
+    fn duration(duration: Duration) -> String {
+        let millis = duration.num_milliseconds().abs();
+        let units = [
+            (86_400_000, "day"),
+            (3_600_000, "hour"),
+            (60_000, "minute"),
+            (1000, "second"),
+        ];
+
+        match units.iter().find(|(unit, _)| millis % unit == 0) {
+            Some((unit, name)) => Self::count(millis / unit, name),
+            None => Self::count(millis, "millisecond"),
+        }
+    }
+}
+
+impl Localization for English {
+    fn date(&self, date: NaiveDate) -> String {
+        format!(
+            "{} {}, {}",
+            MONTHS[date.month0() as usize],
+            date.day(),
+            date.year()
+        )
+    }
+
+    fn time(&self, time: NaiveTime) -> String {
+        time.format("%H:%M").to_string()
+    }
+
+    fn weekday(&self, weekday: Weekday) -> String {
+        String::from(match weekday {
+            Weekday::Mon => "Monday",
+            Weekday::Tue => "Tuesday",
+            Weekday::Wed => "Wednesday",
+            Weekday::Thu => "Thursday",
+            Weekday::Fri => "Friday",
+            Weekday::Sat => "Saturday",
+            Weekday::Sun => "Sunday",
+        })
+    }
+
+    fn daily(&self, interval: u32) -> String {
+        Self::every(interval, "day", None)
+    }
+
+    fn weekly(&self, interval: u32, weekdays: &[Weekday]) -> String {
+        let weekdays: Vec<_> = weekdays.iter().map(|day| self.weekday(*day)).collect();
+
+        format!(
+            "{} on {}",
+            Self::every(interval, "week", None),
+            Self::join(&weekdays, "and")
+        )
+    }
+
+    fn monthly(&self, days: &[u32]) -> String {
+        let days: Vec<_> = days
+            .iter()
+            .map(|day| format!("the {}", Self::ordinal(*day)))
+            .collect();
+
+        format!("every month on {}", Self::join(&days, "and"))
+    }
+
+    fn annual(&self, month: u32, day: u32) -> String {
+        format!("every year on {} {}", MONTHS[month as usize - 1], day)
+    }
+
+    fn hourly(&self, interval: u32, window: Option<&TimeWindow>) -> String {
+        Self::every(interval, "hour", window)
+    }
+
+    fn minutely(&self, interval: u32, window: Option<&TimeWindow>) -> String {
+        Self::every(interval, "minute", window)
+    }
+
+    fn working_days(&self) -> String {
+        String::from("every working day")
+    }
+
+    fn last_working_day_of_month(&self) -> String {
+        String::from("on the last working day of every month")
+    }
+
+    fn easter(&self, offset: i64, reckoning: EasterReckoning) -> String {
+        let easter = match reckoning {
+            EasterReckoning::Western => "Easter Sunday",
+            EasterReckoning::Orthodox => "Orthodox Easter Sunday",
+        };
+
+        match offset {
+            0 => format!("every year on {}", easter),
+            _ => format!(
+                "every year {} {} {}",
+                Self::count(offset.abs(), "day"),
+                if offset < 0 { "before" } else { "after" },
+                easter
+            ),
+        }
+    }
+
+    fn week_of_year(&self, week: i32, weekday: Weekday) -> String {
+        let week = match week {
+            -1 => String::from("the last week"),
+            _ if week < 0 => format!("the {} last week", Self::ordinal(week.unsigned_abs())),
+            _ => format!("week {}", week),
+        };
+
+        format!("every year on {} of {}", self.weekday(weekday), week)
+    }
+
+    fn on_dates(&self, dates: &[NaiveDate]) -> String {
+        let dates: Vec<_> = dates.iter().map(|date| self.date(*date)).collect();
+
+        format!("on {}", Self::join(&dates, "and"))
+    }
+
+    fn any_of(&self, cycles: &[String]) -> String {
+        Self::join(cycles, "or")
+    }
+
+    fn all_of(&self, cycles: &[String]) -> String {
+        match cycles {
+            [] => String::new(),
+            [only] => only.clone(),
+            [first, rest @ ..] => format!("{}, when also {}", first, Self::join(rest, "and")),
+        }
+    }
+
+    fn except(&self, cycle: &str, excluded: &str) -> String {
+        format!("{}, except {}", cycle, excluded)
+    }
+
+    fn shifted(&self, cycle: &str, by: Duration) -> String {
+        if by.num_milliseconds() == 0 {
+            return cycle.to_string();
+        }
+
+        let direction = if by < Duration::zero() {
+            "earlier"
+        } else {
+            "later"
+        };
+
+        format!("{}, {} {}", cycle, Self::duration(by), direction)
+    }
+
+    fn adjusted(&self, cycle: &str, adjustment: BusinessDayAdjustment) -> String {
+        let onto = match adjustment {
+            BusinessDayAdjustment::Following => "the following working day",
+            BusinessDayAdjustment::Preceding => "the preceding working day",
+            BusinessDayAdjustment::ModifiedFollowing => {
+                "the following working day within the month"
+            }
+        };
+
+        format!("{}, moved to {} if needed", cycle, onto)
+    }
+
+    fn custom_cycle(&self) -> String {
+        String::from("on a custom schedule")
+    }
+
+    fn never(&self) -> String {
+        String::from("never")
+    }
+
+    fn whole_days(&self, from: NaiveDate, to: NaiveDate) -> String {
+        if from == to {
+            format!("on {}", self.date(from))
+        } else {
+            format!("from {} to {}", self.date(from), self.date(to))
+        }
+    }
+
+    fn between(&self, from: NaiveDateTime, to: NaiveDateTime) -> String {
+        let date = self.date(from.date());
+
+        if from == to {
+            format!("on {} at {}", date, self.time(from.time()))
+        } else if from.date() == to.date() {
+            format!(
+                "on {} from {} to {}",
+                date,
+                self.time(from.time()),
+                self.time(to.time())
+            )
+        } else {
+            format!(
+                "from {} at {} to {} at {}",
+                date,
+                self.time(from.time()),
+                self.date(to.date()),
+                self.time(to.time())
+            )
+        }
+    }
+
+    fn open_ended(&self, from: NaiveDateTime) -> String {
+        format!(
+            "from {} at {} until further notice",
+            self.date(from.date()),
+            self.time(from.time())
+        )
+    }
+
+    fn local_time(&self, described: &str) -> String {
+        format!("{}, local time", described)
+    }
+
+    fn all_day(&self, days: i64) -> String {
+        match days {
+            1 => String::new(),
+            _ => format!("for {}", Self::count(days, "day")),
+        }
+    }
+
+    fn times(&self, from: NaiveTime, to: NaiveTime, days: i64) -> String {
+        match days {
+            0 if from == to => format!("at {}", self.time(from)),
+            0 => format!("from {} to {}", self.time(from), self.time(to)),
+            1 => format!("from {} to {} the next day", self.time(from), self.time(to)),
+            _ => format!(
+                "from {} to {} {} later",
+                self.time(from),
+                self.time(to),
+                Self::count(days, "day")
+            ),
+        }
+    }
+
+    fn recurring(&self, cycle: &str, times: &str, first: NaiveDate) -> String {
+        match times {
+            "" => format!("{}, starting {}", cycle, self.date(first)),
+            _ => format!("{} {}, starting {}", cycle, times, self.date(first)),
+        }
+    }
+
+    fn except_on(&self, described: &str, dates: &[NaiveDate]) -> String {
+        let dates: Vec<_> = dates.iter().map(|date| self.date(*date)).collect();
+
+        format!("{}, except on {}", described, Self::join(&dates, "and"))
+    }
+
+    fn except_at(&self, described: &str, instants: &[NaiveDateTime]) -> String {
+        let instants: Vec<_> = instants
+            .iter()
+            .map(|at| format!("{} at {}", self.date(at.date()), self.time(at.time())))
+            .collect();
+
+        format!("{}, except on {}", described, Self::join(&instants, "and"))
+    }
+
+    fn sentence(&self, described: &str) -> String {
+        let mut chars = described.chars();
+
+        match chars.next() {
+            Some(first) => first.to_uppercase().chain(chars).collect(),
+            None => String::new(),
+        }
+    }
+}
+
+/**
+   Merges cycles starting on weekdays or days of the month, possibly shifted by whole
+   days, into a single description, e.g. "every month on the 1st and the 15th".
+*/
+pub(crate) fn describe_merged(
+    cycles: &[Box<dyn Cyclicity>],
+    period: &dyn Period,
+    locale: &dyn Localization,
+) -> Option<String> {
+    let starts = cycles
+        .iter()
+        .map(|cycle| anchored_start(cycle.as_ref(), period))
+        .collect::<Option<Vec<_>>>()?;
+
+    let (first_kind, _) = starts.first()?;
+
+    if starts.iter().any(|(kind, _)| kind != first_kind) {
+        return None;
+    }
+
+    match first_kind {
+        Anchor::Weekly(interval) => {
+            let first = period.get_date_time_start().date_naive();
+
+            // Beyond the first week, a weekday doesn't tell which week of the interval it is.
+            if *interval > 1
+                && starts
+                    .iter()
+                    .any(|(_, date)| !(0..7).contains(&(*date - first).num_days()))
+            {
+                return None;
+            }
+
+            let mut weekdays: Vec<_> = starts.iter().map(|(_, date)| date.weekday()).collect();
+
+            weekdays.sort_by_key(|day| day.num_days_from_monday());
+            weekdays.dedup();
+            Some(locale.weekly(*interval, &weekdays))
+        }
+        Anchor::Monthly => {
+            let mut days: Vec<_> = starts.iter().map(|(_, date)| date.day()).collect();
+
+            days.sort_unstable();
+            days.dedup();
+            Some(locale.monthly(&days))
+        }
+    }
+}
+
+#[derive(PartialEq)]
+enum Anchor {
+    Weekly(u32),
+    Monthly,
+}
+
+fn anchored_start(cycle: &dyn Cyclicity, period: &dyn Period) -> Option<(Anchor, NaiveDate)> {
+    let any = cycle.as_any();
+    let start = period.get_date_time_start().date_naive();
+
+    if any.is::<WeeklyCycle>() {
+        Some((Anchor::Weekly(1), start))
+    } else if let Some(minutes) = sub_daily_minutes(cycle).filter(|minutes| minutes % WEEK == 0) {
+        Some((Anchor::Weekly((minutes / WEEK) as u32), start))
+    } else if any.is::<MonthlyCycle>() {
+        Some((Anchor::Monthly, start))
+    } else if let Some(Shifted(by, cycle)) = any.downcast_ref::<Shifted>() {
+        let moved = period.with_new_date_time_start(period.get_date_time_start() + by.0);
+
+        match by.0.num_seconds() % 86400 {
+            0 => anchored_start(cycle.as_ref(), moved.as_ref()),
+            _ => None,
+        }
+    } else {
+        None
+    }
+}
+
+/// Whether the cycle repeats on calendar days relative to the start of the period, so that
+/// shifting it by whole days amounts to shifting the period.
+pub(crate) fn follows_period(cycle: &dyn Cyclicity) -> bool {
+    let any = cycle.as_any();
+
+    any.is::<DailyCycle>()
+        || any.is::<WeeklyCycle>()
+        || any.is::<MonthlyCycle>()
+        || any.is::<AnnualCycle>()
+        || matches!(sub_daily_minutes(cycle), Some(DAY) | Some(WEEK))
+}
+
+const DAY: i64 = 1440;
+const WEEK: i64 = 7 * DAY;
+
+/// Interval in minutes of an hourly or minutely cycle without a time window.
+fn sub_daily_minutes(cycle: &dyn Cyclicity) -> Option<i64> {
+    let any = cycle.as_any();
+
+    if let Some(HourlyCycle {
+        interval,
+        window: None,
+    }) = any.downcast_ref::<HourlyCycle>()
+    {
+        Some(interval.get() as i64 * 60)
+    } else if let Some(MinutelyCycle {
+        interval,
+        window: None,
+    }) = any.downcast_ref::<MinutelyCycle>()
+    {
+        Some(interval.get() as i64)
+    } else {
+        None
+    }
+}
+
+/**
+   Describes an hourly or minutely cycle in the largest unit its interval is a whole number
+   of, e.g. "every 2 weeks on Monday" rather than "every 336 hours". Days and weeks are only
+   used without a time window.
+*/
+pub(crate) fn describe_sub_daily(
+    minutes: i64,
+    window: Option<&TimeWindow>,
+    period: &dyn Period,
+    locale: &dyn Localization,
+) -> String {
+    let weekday = period.get_date_time_start().weekday();
+
+    match window {
+        None if minutes % WEEK == 0 => locale.weekly((minutes / WEEK) as u32, &[weekday]),
+        None if minutes % DAY == 0 => locale.daily((minutes / DAY) as u32),
+        _ if minutes % 60 == 0 => locale.hourly((minutes / 60) as u32, window),
+        _ => locale.minutely(minutes as u32, window),
+    }
+}
+
+impl Event {
+    /// Describes the event in English, see `describe_in`.
+    pub fn describe(&self) -> String {
+        self.describe_in(&English)
+    }
+
+    /**
+       Describes when the event occurs: its period, its cycle if any, and its exceptions, with
+       their times when the cycle starts several times a day.
+
+       End conditions, such as "until June 30" or "10 times", are out of scope: cycles have
+       no end, so descriptions never have one.
+
+       # Examples
+
+       ```rust
+       use dateless::prelude::*;
+       use chrono::{Duration, TimeZone, Utc};
+
+       let start = Utc.ymd(2021, 5, 3).and_hms(9, 0, 0);
+       let event = EventPartial::new(String::from("Standup"))
+           .from_to(start, start + Duration::minutes(15))
+           .with_cyclicity(AnyOf(vec![
+               Box::new(WeeklyCycle),
+               Box::new(Shifted::new(Duration::days(2), Box::new(WeeklyCycle))),
+           ]))
+           .with_exception(start + Duration::weeks(1))
+           .complete();
+
+       assert_eq!(
+           event.describe_in(&English),
+           "Every week on Monday and Wednesday from 09:00 to 09:15, starting May 3, 2021, \
+            except on May 10, 2021"
+       );
+       ```
+    */
+    pub fn describe_in(&self, locale: &dyn Localization) -> String {
+        let period = &*self.period;
+        let (start, end) = (period.get_date_time_start(), period.get_date_time_end());
+
+        let described = match &self.cyclicity {
+            None => match &self.period {
+                PeriodKind::WholeDays(days) => {
+                    locale.whole_days(days.0.naive_utc(), days.1.naive_utc())
+                }
+                PeriodKind::OpenEnded(_) => locale.open_ended(start.naive_utc()),
+                PeriodKind::FloatingTime(floating) => {
+                    locale.local_time(&locale.between(floating.0, floating.1))
+                }
+                _ => locale.between(start.naive_utc(), end.naive_utc()),
+            },
+            Some(cyclicity) => {
+                let times = match &self.period {
+                    PeriodKind::WholeDays(days) => locale.all_day((days.1 - days.0).num_days() + 1),
+                    PeriodKind::OpenEnded(_) => locale.times(start.time(), start.time(), 0),
+                    PeriodKind::FloatingTime(floating) => locale.local_time(&locale.times(
+                        floating.0.time(),
+                        floating.1.time(),
+                        (floating.1.date() - floating.0.date()).num_days(),
+                    )),
+                    _ => locale.times(
+                        start.time(),
+                        end.time(),
+                        (end.date_naive() - start.date_naive()).num_days(),
+                    ),
+                };
+
+                if cyclicity
+                    .next_start_after(period, start - Duration::nanoseconds(1))
+                    .is_none()
+                {
+                    locale.never()
+                } else {
+                    locale.recurring(
+                        &cyclicity.describe_in(period, locale),
+                        &times,
+                        start.date_naive(),
+                    )
+                }
+            }
+        };
+
+        let several_a_day = self
+            .cyclicity
+            .as_ref()
+            .and_then(|cyclicity| cyclicity.next_start_after(period, start))
+            .is_some_and(|next| next < start + Duration::days(1));
+
+        let described = if self.exceptions.is_empty() {
+            described
+        } else if several_a_day {
+            let instants: Vec<_> = self
+                .exceptions()
+                .map(|exception| exception.naive_utc())
+                .collect();
+
+            locale.except_at(&described, &instants)
+        } else {
+            let dates: Vec<_> = self
+                .exceptions()
+                .map(|exception| exception.date_naive())
+                .collect();
+
+            locale.except_on(&described, &dates)
+        };
+
+        locale.sentence(&described)
+    }
+}
@@ -1,79 +1,497 @@
+mod alarm;
+mod change;
 mod cyclicity;
+mod describe;
 pub mod occurrence;
+mod parse;
 mod period;
 
 use crate::chrono::DateTimeDef;
-use chrono::{Date, DateTime, Utc};
+use chrono::{Date, DateTime, Duration, NaiveDateTime, SubsecRound, Utc};
+use std::{
+    any::Any,
+    hash::{Hash, Hasher},
+};
 
 #[cfg(feature = "serde_support")]
 use serde::{Deserialize, Serialize};
 
+pub use self::alarm::{Alarm, AlarmAnchor, AlarmOccurrence};
+pub use self::change::{ChangeSet, EventChange, FieldChange};
+pub use self::describe::{English, Localization};
+pub use self::parse::ParseError;
 pub use self::period::PeriodDef;
 pub use self::{cyclicity::Cyclicity, occurrence::EventOccurrence, period::Period};
 
-use cyclicity::*;
-pub use period::{StartEnd, WholeDays};
+pub use cyclicity::*;
+pub use period::{
+    EndBoundary, FloatingTime, Moment, OpenEnded, PeriodKind, StartEnd, WholeDays, WithBoundaries,
+};
 
 use optfield::optfield;
 use uuid::Uuid;
 
+/**
+   Access to the concrete type behind a `Period` or `Cyclicity` trait object, implemented for
+   every `'static` type.
+
+   # Examples
+
+   ```rust
+   use dateless::prelude::*;
+   use chrono::Utc;
+
+   let period: Box<dyn Period> = Box::new(Moment(Utc::now()));
+
+   assert!(period.as_ref().as_any().is::<Moment>());
+   ```
+*/
+pub trait AsAny: Any {
+    fn as_any(&self) -> &dyn Any;
+}
+
+impl<T: Any> AsAny for T {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/**
+   Equality of trait objects, implemented for every `'static` type implementing `PartialEq`:
+   values of different types are never equal.
+*/
+pub trait DynEq: AsAny {
+    fn dyn_eq(&self, other: &dyn Any) -> bool;
+}
+
+impl<T: PartialEq + Any> DynEq for T {
+    fn dyn_eq(&self, other: &dyn Any) -> bool {
+        matches!(other.downcast_ref::<T>(), Some(other) if self == other)
+    }
+}
+
+/**
+   Hashing of trait objects, implemented for every `'static` type implementing `Hash`. The
+   concrete type takes part in the hash, consistently with `DynEq`.
+*/
+pub trait DynHash: AsAny {
+    fn dyn_hash(&self, state: &mut dyn Hasher);
+}
+
+impl<T: Hash + Any> DynHash for T {
+    fn dyn_hash(&self, mut state: &mut dyn Hasher) {
+        self.as_any().type_id().hash(&mut state);
+        self.hash(&mut state);
+    }
+}
+
 #[optfield(
     pub EventPartial,
     merge_fn = pub,
     attrs,
 )]
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
 #[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct Event {
     #[cfg_attr(feature = "serde_support", serde(default = "Uuid::new_v4"))]
     pub uuid: Uuid,
     name: String,
     description: Option<String>,
-    cyclicity: Option<Box<dyn Cyclicity>>,
+    cyclicity: Option<Recurrence>,
+    #[cfg_attr(feature = "serde_support", serde(default))]
     exceptions: Vec<DateTimeDef>,
-    period: PeriodDef,
+    period: PeriodKind,
+    #[cfg_attr(feature = "serde_support", serde(default))]
+    alarms: Vec<Alarm>,
+    #[cfg_attr(feature = "serde_support", serde(default))]
+    created: DateTimeDef,
+    #[cfg_attr(feature = "serde_support", serde(default))]
+    last_modified: DateTimeDef,
+    #[cfg_attr(feature = "serde_support", serde(default))]
+    sequence: u32,
+}
+
+/**
+   Shape of events in positional payloads, e.g. bincode or postcard ones, of the format versions
+   before 3, which lack the revision fields. Such payloads can't skip missing fields, so they
+   are read as this and upgraded with the defaults of the self-describing ones.
+*/
+#[cfg(feature = "serde_support")]
+#[derive(Deserialize)]
+pub(crate) struct LegacyEvent {
+    uuid: Uuid,
+    name: String,
+    description: Option<String>,
+    cyclicity: Option<Recurrence>,
+    exceptions: Vec<DateTimeDef>,
+    period: PeriodKind,
+    alarms: Vec<Alarm>,
+}
+
+#[cfg(feature = "serde_support")]
+impl From<LegacyEvent> for Event {
+    fn from(legacy: LegacyEvent) -> Self {
+        Self {
+            uuid: legacy.uuid,
+            name: legacy.name,
+            description: legacy.description,
+            cyclicity: legacy.cyclicity,
+            exceptions: legacy.exceptions,
+            period: legacy.period,
+            alarms: legacy.alarms,
+            ..Default::default()
+        }
+    }
 }
 
 impl Event {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    pub fn period(&self) -> &PeriodKind {
+        &self.period
+    }
+
+    pub fn cyclicity(&self) -> Option<&Recurrence> {
+        self.cyclicity.as_ref()
+    }
+
+    /// Starts of the occurrences excluded from the event.
+    pub fn exceptions(&self) -> impl Iterator<Item = DateTime<Utc>> + '_ {
+        self.exceptions.iter().map(|exception| exception.0)
+    }
+
+    pub fn alarms(&self) -> &[Alarm] {
+        &self.alarms
+    }
+
+    /// Creation time, at a precision of a second as all the revision times.
+    pub fn created(&self) -> DateTime<Utc> {
+        self.created.0
+    }
+
+    pub fn last_modified(&self) -> DateTime<Utc> {
+        self.last_modified.0
+    }
+
+    /**
+       Revision of the event, as iCalendar's `SEQUENCE`: 0 once completed, increased each
+       time the event is modified through its calendar.
+    */
+    pub fn sequence(&self) -> u32 {
+        self.sequence
+    }
+
+    /**
+       Whether this version of an event is more recent than the given one: it has a higher
+       `sequence`, or the same one and a later `last_modified`.
+
+       # Examples
+
+       ```rust
+       use dateless::prelude::*;
+       use chrono::Utc;
+
+       let mut calendar = Calendar::new();
+       let event = EventPartial::new("Standup").whole_day(Utc::today()).complete();
+       let uuid = event.uuid;
+
+       calendar.add_event(event.clone());
+       calendar.add_exception(uuid, Utc::today().and_hms(0, 0, 0));
+
+       assert_eq!(calendar.event(uuid).unwrap().sequence(), 1);
+       assert!(calendar.event(uuid).unwrap().is_newer_than(&event));
+       ```
+    */
+    pub fn is_newer_than(&self, other: &Event) -> bool {
+        (self.sequence, self.last_modified.0) > (other.sequence, other.last_modified.0)
+    }
+
+    /// Returns the first occurrence touching the given day, if any.
+    #[deprecated(
+        since = "0.4.0",
+        note = "use `get_occurrences_at`, which returns all of them"
+    )]
     pub fn get_occurrence_at(&self, date: Date<Utc>) -> Option<EventOccurrence> {
-        let period = if self.period.0.contains(date) {
-            self.period.0.cloned()
-        } else if self.may_any_next_period_contains(date) {
-            let new_start: Option<Box<dyn Period>> = match &self.cyclicity {
-                Some(value) => value.same_period_at(self.period.0.cloned(), date),
-                _ => return None,
-            };
+        self.get_occurrences_at(date).into_iter().next()
+    }
+
+    /**
+       Returns every occurrence touching the given day, ordered by their start. It includes
+       instances started on previous days which are still lasting, e.g. the continuation of
+       yesterday's overnight shift along with today's one.
+    */
+    pub fn get_occurrences_at(&self, date: Date<Utc>) -> Vec<EventOccurrence> {
+        let mut periods: Vec<PeriodKind> = Vec::new();
+
+        if let Some(cyclicity) = &self.cyclicity {
+            let midnight = date.and_hms_opt(0, 0, 0).unwrap();
+            let next_midnight = midnight + Duration::days(1);
+            let origin = &*self.period;
+            let mut start = cyclicity.previous_start_before(origin, next_midnight);
+
+            while let Some(value) = start {
+                let period = self.period.starting_at(value);
+
+                if period.contains(date) {
+                    periods.push(period);
+                } else if period.get_date_time_end() <= midnight {
+                    break;
+                }
+
+                start = cyclicity.previous_start_before(origin, value);
+            }
+
+            periods.reverse();
+        } else if self.period.contains(date) {
+            periods.push(self.period.clone());
+        }
+
+        periods
+            .into_iter()
+            .filter(|period| !self.is_exception(&**period))
+            .map(|period| self.create_occurrence(period))
+            .collect()
+    }
+
+    /**
+       Returns the first occurrence starting after the given instant, if any.
+
+       # Examples
+
+       ```rust
+       use dateless::prelude::*;
+       use chrono::{TimeZone, Utc};
+
+       let event = EventPartial::new(String::from("Anne's birthday"))
+           .whole_day(Utc.ymd(2021, 5, 8))
+           .annual()
+           .complete();
+
+       let next = event.next_occurrence_after(Utc.ymd(2022, 1, 1).and_hms(0, 0, 0));
+
+       assert_eq!(
+           next.unwrap().period.get_date_time_start(),
+           Utc.ymd(2022, 5, 8).and_hms(0, 0, 0)
+       );
+       ```
+    */
+    pub fn next_occurrence_after(&self, after: DateTime<Utc>) -> Option<EventOccurrence> {
+        let origin = &*self.period;
 
-            if new_start.is_none() {
-                return None;
+        let cyclicity = match &self.cyclicity {
+            Some(cyclicity) => cyclicity,
+            None if origin.get_date_time_start() > after && !self.is_exception(origin) => {
+                return Some(self.create_occurrence(self.period.clone()))
             }
+            None => return None,
+        };
+
+        let mut start = cyclicity.next_start_after(origin, after);
+
+        while let Some(value) = start {
+            let period = self.period.starting_at(value);
+
+            if !self.is_exception(&*period) {
+                return Some(self.create_occurrence(period));
+            }
+
+            start = cyclicity.next_start_after(origin, value);
+        }
+
+        None
+    }
+
+    /**
+       Returns whether an occurrence overlaps the `[from, to)` range. Moments occur in it
+       when they are within it.
+
+       # Examples
+
+       ```rust
+       use dateless::prelude::*;
+       use chrono::{Duration, TimeZone, Utc};
+
+       let start = Utc.ymd(2021, 5, 3).and_hms(9, 0, 0);
+       let event = EventPartial::new(String::from("Standup"))
+           .from_to(start, start + Duration::minutes(15))
+           .weekly()
+           .complete();
+
+       assert!(event.occurs_between(start + Duration::days(7), start + Duration::days(8)));
+       assert!(!event.occurs_between(start + Duration::days(8), start + Duration::days(9)));
+       ```
+    */
+    pub fn occurs_between(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> bool {
+        let overlaps = |period: &dyn Period| {
+            let start = period.get_date_time_start();
+
+            start < to && (period.get_date_time_end() > from || start >= from)
+        };
+
+        let cyclicity = match &self.cyclicity {
+            Some(cyclicity) => cyclicity,
+            None => return overlaps(&*self.period) && !self.is_exception(&*self.period),
+        };
+
+        let origin = &*self.period;
+        let mut start = cyclicity.previous_start_before(origin, to);
 
-            new_start.unwrap().cloned()
+        while let Some(value) = start {
+            let period = self.period.starting_at(value);
+
+            if overlaps(&*period) && !self.is_exception(&*period) {
+                return true;
+            } else if value < from && period.get_date_time_end() <= from {
+                break;
+            }
+
+            start = cyclicity.previous_start_before(origin, value);
+        }
+
+        false
+    }
+
+    pub fn get_alarms_between(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Vec<AlarmOccurrence> {
+        let mut alarms = Vec::new();
+
+        if self.alarms.is_empty() {
+            return alarms;
+        }
+
+        let origin = &*self.period;
+        let reach = self.alarms_reach();
+        // Alarms anchored at the end fire a whole period later than the start.
+        let length = if self
+            .alarms
+            .iter()
+            .any(|alarm| alarm.anchor == AlarmAnchor::End)
+        {
+            origin.duration()
         } else {
-            return None;
+            Duration::zero()
+        };
+        let earliest = reach
+            .checked_add(&length)
+            .and_then(|before| from.checked_sub_signed(before))
+            .unwrap_or_else(|| origin.get_date_time_start());
+        let latest = to
+            .checked_add_signed(reach)
+            .unwrap_or(DateTime::<Utc>::MAX_UTC);
+        let mut start = match &self.cyclicity {
+            Some(cyclicity) => {
+                cyclicity.next_start_after(origin, earliest - Duration::nanoseconds(1))
+            }
+            None => Some(origin.get_date_time_start()).filter(|start| *start >= earliest),
         };
 
-        let occurrence = self.create_occurrence(period);
+        while let Some(value) = start.filter(|value| *value < latest) {
+            let period = self.period.starting_at(value);
 
-        for exception in &self.exceptions {
-            if exception.0 == occurrence.period.0.get_date_time_start() {
-                return None;
+            if !self.is_exception(&*period) {
+                for alarm in &self.alarms {
+                    let fires_at = match alarm.fires_at(&*period) {
+                        Some(fires_at) => fires_at,
+                        None => continue,
+                    };
+
+                    if fires_at >= from && fires_at < to {
+                        alarms.push(AlarmOccurrence {
+                            fires_at,
+                            occurrence: self.create_occurrence(period.clone()),
+                        });
+                    }
+                }
             }
+
+            start = match &self.cyclicity {
+                Some(cyclicity) => cyclicity.next_start_after(origin, value),
+                None => None,
+            };
         }
 
-        return Some(occurrence);
+        alarms
     }
 
-    fn may_any_next_period_contains(&self, date: Date<Utc>) -> bool {
-        self.cyclicity.is_some() && self.period.0.starts_before(date)
+    fn alarms_reach(&self) -> Duration {
+        let longest_offset = self
+            .alarms
+            .iter()
+            .map(|alarm| alarm.offset.0.num_seconds().abs())
+            .max()
+            .unwrap_or(0);
+
+        Duration::seconds(longest_offset) + Duration::days(1)
     }
 
-    fn create_occurrence(&self, period: Box<dyn Period>) -> EventOccurrence {
+    /// Adds an exception at the given instant, unless already present.
+    pub(crate) fn add_exception(&mut self, at: DateTime<Utc>) -> bool {
+        if self.exceptions.contains(&DateTimeDef(at)) {
+            return false;
+        }
+
+        self.exceptions.push(DateTimeDef(at));
+        true
+    }
+
+    /// Marks the event as modified now.
+    pub(crate) fn touch(&mut self) {
+        self.sequence += 1;
+        self.last_modified = DateTimeDef(now());
+    }
+
+    pub(crate) fn revise(&mut self, sequence: u32, last_modified: DateTime<Utc>) {
+        self.sequence = sequence;
+        self.last_modified = DateTimeDef(last_modified);
+    }
+
+    #[cfg(any(feature = "caldav", feature = "sqlite"))]
+    pub(crate) fn restore_revision(
+        &mut self,
+        created: DateTime<Utc>,
+        last_modified: DateTime<Utc>,
+        sequence: u32,
+    ) {
+        self.created = DateTimeDef(created);
+        self.revise(sequence, last_modified);
+    }
+
+    /// Marks the event as a modification of its given previous version.
+    pub(crate) fn modified_after(&mut self, previous: &Event) {
+        self.created = previous.created;
+        self.sequence = self.sequence.max(previous.sequence);
+        self.touch();
+    }
+
+    pub(crate) fn remove_exception(&mut self, at: DateTime<Utc>) -> bool {
+        let count = self.exceptions.len();
+
+        self.exceptions.retain(|exception| exception.0 != at);
+        self.exceptions.len() != count
+    }
+
+    fn is_exception(&self, period: &dyn Period) -> bool {
+        let start = period.get_date_time_start();
+
+        self.exceptions.iter().any(|exception| exception.0 == start)
+    }
+
+    fn create_occurrence(&self, period: PeriodKind) -> EventOccurrence {
         return EventOccurrence {
             origin: self.uuid,
             name: self.name.clone(),
             description: self.description.clone(),
-            period: PeriodDef(period),
+            period,
         };
     }
 }
@@ -83,33 +501,86 @@ impl EventPartial {
 
     bind_partial_filler!(with_description, description);
 
-    bind_partial_filler_boxed!(with_cyclicity, cyclicity, Cyclicity);
+    /// Accepts built-in cycles, `Recurrence`s and boxed cyclicities alike.
+    pub fn with_cyclicity<C: Into<Recurrence>>(self, cyclicity: C) -> Self {
+        Self {
+            cyclicity: Some(cyclicity.into()),
+            ..self
+        }
+    }
 
     bind_partial_trait_filler!(daily, DailyCycle, with_cyclicity);
     bind_partial_trait_filler!(weekly, WeeklyCycle, with_cyclicity);
     bind_partial_trait_filler!(monthly, MonthlyCycle, with_cyclicity);
     bind_partial_trait_filler!(annual, AnnualCycle, with_cyclicity);
 
-    bind_partial_filler!(with_period, period, PeriodDef);
+    /// Panics if `interval` is 0, like `HourlyCycle::new`.
+    pub fn hourly(self, interval: u32) -> Self {
+        self.with_cyclicity(HourlyCycle::new(interval))
+    }
+
+    /// Panics if `interval` is 0, like `MinutelyCycle::new`.
+    pub fn minutely(self, interval: u32) -> Self {
+        self.with_cyclicity(MinutelyCycle::new(interval))
+    }
+
+    /// Accepts built-in periods, `PeriodKind`s and `PeriodDef`s alike.
+    pub fn with_period<P: Into<PeriodKind>>(self, period: P) -> Self {
+        Self {
+            period: Some(period.into()),
+            ..self
+        }
+    }
+
+    pub fn with_exception(mut self, at: DateTime<Utc>) -> Self {
+        self.exceptions
+            .get_or_insert_with(Vec::new)
+            .push(DateTimeDef(at));
+        self
+    }
+
+    pub fn with_alarm(mut self, alarm: Alarm) -> Self {
+        self.alarms.get_or_insert_with(Vec::new).push(alarm);
+        self
+    }
 
     pub fn from_to(self, from: DateTime<Utc>, to: DateTime<Utc>) -> Self {
-        self.with_period(PeriodDef(Box::new(StartEnd(from, to))))
+        self.with_period(StartEnd(from, to))
+    }
+
+    pub fn moment(self, at: DateTime<Utc>) -> Self {
+        self.with_period(Moment(at))
+    }
+
+    pub fn open_ended(self, from: DateTime<Utc>) -> Self {
+        self.with_period(OpenEnded(from))
+    }
+
+    pub fn floating(self, from: NaiveDateTime, to: NaiveDateTime) -> Self {
+        self.with_period(FloatingTime(from, to))
     }
 
     pub fn whole_days(self, from: Date<Utc>, to: Date<Utc>) -> Self {
-        self.with_period(PeriodDef(Box::new(WholeDays(from, to))))
+        self.with_period(WholeDays(from, to))
     }
 
     pub fn whole_day(self, from_to: Date<Utc>) -> Self {
-        self.with_period(PeriodDef(Box::new(WholeDays(from_to, from_to))))
+        self.with_period(WholeDays(from_to, from_to))
     }
 
     pub fn complete(self) -> Event {
         let mut event: Event = Default::default();
 
         event.uuid = Uuid::new_v4();
+        event.created = DateTimeDef(now());
+        event.last_modified = event.created;
 
         event.merge_opt(self);
         event
     }
 }
+
+/// The current time in whole seconds, which every timestamp format keeps.
+fn now() -> DateTime<Utc> {
+    Utc::now().trunc_subsecs(0)
+}
@@ -1,13 +1,13 @@
-use super::PeriodDef;
+use super::PeriodKind;
 #[cfg(feature = "serde_support")]
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
 pub struct EventOccurrence {
     pub name: String,
     pub description: Option<String>,
-    pub period: PeriodDef,
+    pub period: PeriodKind,
     pub origin: Uuid,
 }
@@ -0,0 +1,1102 @@
+use super::{
+    cyclicity::{
+        AnnualCycle, AnyOf, DailyCycle, HourlyCycle, MinutelyCycle, MonthlyCycle, Shifted,
+        WeeklyCycle, WorkingDayCycle,
+    },
+    Cyclicity, EventPartial, StartEnd,
+};
+use crate::working_days::WorkingDays;
+use chrono::{
+    Date, DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Utc, Weekday,
+};
+use std::{convert::TryFrom, error::Error, fmt};
+
+/// Length of the events given a start time only, in minutes.
+const DEFAULT_LENGTH: i64 = 60;
+
+/// How many years ahead a date given without one is looked for, e.g. February 29.
+const YEARS_AHEAD: i32 = 8;
+
+const HOURS_PER_WEEK: u32 = 168;
+
+/**
+   Reason an entry could not be read by `EventPartial::parse`. Phrases are quoted as they
+   appear in the entry.
+*/
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ParseError {
+    /// Nothing is left of the entry to name the event after.
+    MissingName,
+    /// The entry gives neither a date, a time nor a recurrence, or needs a start time it
+    /// lacks, e.g. "every 2 hours" or "for 30 minutes" without one.
+    MissingStart,
+    /// The phrase can be read several ways, e.g. "3/4" as March 4 or April 3.
+    Ambiguous(String),
+    /// Both phrases set the same part of the event, e.g. "tomorrow" and "May 3", or a date
+    /// and weekdays it doesn't fall on, e.g. "tomorrow" and "every Friday" on a Wednesday.
+    Conflicting(String, String),
+    /// The phrase names something which doesn't exist, e.g. "25:00" or "February 30".
+    Invalid(String),
+    /// The phrase asks for a recurrence no cycle can represent, e.g. "every 2 months".
+    Unsupported(String),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::MissingName => write!(f, "the entry names no event"),
+            ParseError::MissingStart => write!(f, "the entry doesn't say when the event starts"),
+            ParseError::Ambiguous(phrase) => write!(f, "\"{}\" is ambiguous", phrase),
+            ParseError::Conflicting(first, second) => {
+                write!(f, "\"{}\" conflicts with \"{}\"", second, first)
+            }
+            ParseError::Invalid(phrase) => write!(f, "\"{}\" is not a valid date or time", phrase),
+            ParseError::Unsupported(phrase) => write!(f, "\"{}\" is not supported", phrase),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+impl EventPartial {
+    /**
+       Reads a quick-add entry, e.g. "Lunch with Anne 12:30-13:30 every Friday", relative to
+       the `reference` instant. Every phrase giving a date, a time, a length or
+       a recurrence is taken out of the entry, the words left naming the event.
+
+       - Dates: "today", "tomorrow", "Friday" (today or later), "next Friday" (after
+         today), "in 3 days", "May 3", "3rd of May, 2022", "the 15th", "2021-05-03" and
+         "15/5". Dates without a year are the next ones; numeric dates of which the day and
+         month could be swapped are ambiguous.
+       - Times: "12:30", "9am", "noon", "at 9", "in 2 hours", and ranges such as
+         "12:30-13:30", "9-10am" or "from 9 to 10"; an end at or before the start is on the
+         next day.
+       - Lengths: "for 30 minutes", "for 1h30", "for 2 days", or "until 17:00".
+       - Recurrences: "daily", "every week", "every 2 weeks", "every Monday and Wednesday",
+         "every other Friday", "every 2 weeks on Monday and Wednesday", "every weekday",
+         "every 2 hours". Months and years only recur one at a time. Recurring on given
+         weekdays or working days, the event starts on the first of them on or after today,
+         or after the date following "starting"; any other date must be one of them, e.g.
+         "tomorrow every Friday" conflicts unless tomorrow is a Friday. Weeks of recurrences
+         every few weeks start on Monday.
+
+       With a start time, the period is a `StartEnd`, lasting an hour unless a length is
+       given; without, it is a `WholeDays`. Dates and times are in UTC.
+
+       # Examples
+
+       ```rust
+       use dateless::prelude::*;
+       use chrono::{TimeZone, Utc};
+
+       // A Wednesday
+       let reference = Utc.ymd(2021, 5, 5).and_hms(10, 0, 0);
+       let lunch = EventPartial::parse("Lunch with Anne 12:30-13:30 every Friday", reference)
+           .unwrap()
+           .complete();
+
+       assert_eq!(lunch.name(), "Lunch with Anne");
+       assert_eq!(
+           lunch.describe(),
+           "Every week on Friday from 12:30 to 13:30, starting May 7, 2021"
+       );
+
+       assert_eq!(
+           EventPartial::parse("Lunch with Anne tomorrow 12:30-13:30 every Friday", reference)
+               .unwrap_err(),
+           ParseError::Conflicting(String::from("tomorrow"), String::from("every Friday"))
+       );
+       ```
+    */
+    pub fn parse(entry: &str, reference: DateTime<Utc>) -> Result<Self, ParseError> {
+        Parser::new(entry, reference).parse()
+    }
+}
+
+struct Token<'a> {
+    text: &'a str,
+    /// The text in lowercase, without surrounding punctuation.
+    word: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Meridiem {
+    Am,
+    Pm,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Clock {
+    hour: u32,
+    minute: u32,
+    meridiem: Option<Meridiem>,
+    /// Whether the word can only be a time, unlike e.g. "9".
+    explicit: bool,
+    /// "noon" or "midnight".
+    named: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Unit {
+    Minute,
+    Hour,
+    Day,
+    Week,
+}
+
+enum End {
+    At(NaiveTime),
+    After(Duration),
+}
+
+enum Cycle {
+    Rule(Box<dyn Cyclicity>),
+    /// A cycle repeating within days, which needs a start time.
+    SubDaily(Box<dyn Cyclicity>),
+    /// On the given weekdays, every given number of weeks.
+    Weekdays(Vec<Weekday>, u32),
+    WorkingDays,
+}
+
+enum Phrase {
+    Date(NaiveDate),
+    /// A date after "starting", which a recurrence may start after.
+    Starting(NaiveDate),
+    Time(NaiveTime),
+    Until(NaiveTime),
+    Range(NaiveTime, NaiveTime),
+    For(Duration),
+    Instant(NaiveDateTime),
+    Cycle(Cycle),
+}
+
+/// A phrase spanning the given count of tokens, if one matches.
+type Matched<T> = Result<Option<(usize, T)>, ParseError>;
+
+struct Parser<'a> {
+    tokens: Vec<Token<'a>>,
+    now: NaiveDateTime,
+    today: NaiveDate,
+}
+
+/// Parts of the entry read so far, along with the phrases which set them.
+#[derive(Default)]
+struct Entry<'a> {
+    name: Vec<&'a str>,
+    date: Option<(String, NaiveDate)>,
+    starting: bool,
+    start: Option<(String, NaiveTime)>,
+    end: Option<(String, End)>,
+    cycle: Option<(String, Cycle)>,
+}
+
+impl<'a> Parser<'a> {
+    fn new(entry: &'a str, reference: DateTime<Utc>) -> Self {
+        let tokens = entry
+            .split_whitespace()
+            .map(|text| Token {
+                text,
+                word: text
+                    .trim_start_matches(|c: char| "(\"".contains(c))
+                    .trim_end_matches(|c: char| ",;.!?)\"".contains(c))
+                    .to_lowercase(),
+            })
+            .collect();
+        let now = reference.naive_utc();
+
+        Self {
+            tokens,
+            now,
+            today: now.date(),
+        }
+    }
+
+    fn parse(self) -> Result<EventPartial, ParseError> {
+        let mut entry = Entry::default();
+        let mut at = 0;
+
+        while at < self.tokens.len() {
+            match self.phrase(at)? {
+                Some((len, phrase)) => {
+                    entry.set(self.text(at, len), phrase)?;
+                    at += len;
+                }
+                None => {
+                    entry.name.push(self.tokens[at].text);
+                    at += 1;
+                }
+            }
+        }
+
+        entry.complete(self.today)
+    }
+
+    fn word(&self, at: usize) -> Option<&str> {
+        self.tokens.get(at).map(|token| token.word.as_str())
+    }
+
+    fn text(&self, at: usize, len: usize) -> String {
+        let texts: Vec<_> = self.tokens[at..at + len]
+            .iter()
+            .map(|token| token.text)
+            .collect();
+
+        texts
+            .join(" ")
+            .trim_end_matches(|c: char| ",;".contains(c))
+            .to_string()
+    }
+
+    fn invalid(&self, at: usize, len: usize) -> ParseError {
+        ParseError::Invalid(self.text(at, len))
+    }
+
+    fn phrase(&self, at: usize) -> Matched<Phrase> {
+        let next = at + 1;
+        let connected = match self.word(at).unwrap_or_default() {
+            "every" | "each" => self
+                .cycle(next)?
+                .map(|(len, cycle)| (len, Phrase::Cycle(cycle))),
+            "in" => self.relative(next)?,
+            "for" => self
+                .duration(next)?
+                .map(|(len, length)| (len, Phrase::For(length))),
+            "until" | "till" => self
+                .time(next, true)?
+                .map(|(len, time)| (len, Phrase::Until(time))),
+            "on" if self.word(next) == Some("weekdays") => {
+                Some((1, Phrase::Cycle(Cycle::WorkingDays)))
+            }
+            "at" | "from" => self.when(next, true)?,
+            "on" => self.when(next, false)?,
+            "starting" => self.when(next, false)?.map(|(len, phrase)| match phrase {
+                Phrase::Date(date) => (len, Phrase::Starting(date)),
+                phrase => (len, phrase),
+            }),
+            _ => None,
+        };
+
+        if let Some((len, phrase)) = connected {
+            return Ok(Some((len + 1, phrase)));
+        }
+
+        let cycle = match self.word(at).unwrap_or_default() {
+            "daily" => Cycle::Rule(Box::new(DailyCycle)),
+            "weekly" => Cycle::Rule(Box::new(WeeklyCycle)),
+            "monthly" => Cycle::Rule(Box::new(MonthlyCycle)),
+            "yearly" | "annually" => Cycle::Rule(Box::new(AnnualCycle)),
+            "hourly" => Cycle::SubDaily(Box::new(HourlyCycle::new(1))),
+            _ => return self.when(at, false),
+        };
+
+        Ok(Some((1, Phrase::Cycle(cycle))))
+    }
+
+    /// A time range, a date or a time. Bare numbers are read as hours when `loose`.
+    fn when(&self, at: usize, loose: bool) -> Matched<Phrase> {
+        if let Some(range) = self.range(at, loose)? {
+            return Ok(Some(range));
+        }
+
+        if let Some((len, date)) = self.date(at)? {
+            return Ok(Some((len, Phrase::Date(date))));
+        }
+
+        Ok(self
+            .time(at, loose)?
+            .map(|(len, time)| (len, Phrase::Time(time))))
+    }
+
+    /// A clock, with its meridiem possibly in the next token, e.g. "9 am".
+    fn clock(&self, at: usize) -> Option<(usize, Clock)> {
+        let clock = clock(self.word(at)?)?;
+
+        match self.word(at + 1).and_then(meridiem) {
+            Some(meridiem) if clock.meridiem.is_none() && !clock.named => Some((
+                2,
+                Clock {
+                    meridiem: Some(meridiem),
+                    explicit: true,
+                    ..clock
+                },
+            )),
+            _ => Some((1, clock)),
+        }
+    }
+
+    fn time(&self, at: usize, loose: bool) -> Matched<NaiveTime> {
+        match self.clock(at) {
+            Some((len, clock)) if clock.explicit || loose => match clock.resolve() {
+                Some(time) => Ok(Some((len, time))),
+                None => Err(self.invalid(at, len)),
+            },
+            _ => Ok(None),
+        }
+    }
+
+    fn range(&self, at: usize, loose: bool) -> Matched<Phrase> {
+        let word = match self.word(at) {
+            Some(word) => word,
+            None => return Ok(None),
+        };
+
+        if let Some((from, to)) = word.split_once('-') {
+            if let (Some(first), Some(second)) = (clock(from), clock(to)) {
+                let (len, second) = match self.word(at + 1).and_then(meridiem) {
+                    Some(meridiem) if second.meridiem.is_none() && !second.named => (
+                        2,
+                        Clock {
+                            meridiem: Some(meridiem),
+                            explicit: true,
+                            ..second
+                        },
+                    ),
+                    _ => (1, second),
+                };
+
+                if first.explicit || second.explicit || loose {
+                    return self.resolve_range(at, len, first, second);
+                }
+            }
+
+            return Ok(None);
+        }
+
+        if let Some((first_len, first)) = self.clock(at) {
+            let separator = at + first_len;
+
+            if first.explicit || loose {
+                if let Some("-" | "to" | "until" | "till") = self.word(separator) {
+                    if let Some((second_len, second)) = self.clock(separator + 1) {
+                        return self.resolve_range(at, first_len + 1 + second_len, first, second);
+                    }
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// A start without meridiem takes the one of the end, e.g. in "9-10am", unless it would
+    /// then come after it, e.g. in "11-1pm".
+    fn resolve_range(&self, at: usize, len: usize, first: Clock, second: Clock) -> Matched<Phrase> {
+        let to = second.resolve().ok_or_else(|| self.invalid(at, len))?;
+        let inherited = match (first.meridiem, second.meridiem) {
+            (None, Some(meridiem)) if !first.named => Clock {
+                meridiem: Some(meridiem),
+                ..first
+            }
+            .resolve()
+            .filter(|from| *from <= to),
+            _ => None,
+        };
+        let from = match inherited {
+            Some(from) => from,
+            None => first.resolve().ok_or_else(|| self.invalid(at, len))?,
+        };
+
+        Ok(Some((len, Phrase::Range(from, to))))
+    }
+
+    fn date(&self, at: usize) -> Matched<NaiveDate> {
+        let word = match self.word(at) {
+            Some(word) => word,
+            None => return Ok(None),
+        };
+        let today = self.today;
+        let day_after = |date: NaiveDate, len| date.succ_opt().ok_or_else(|| self.invalid(at, len));
+
+        match word {
+            "today" => return Ok(Some((1, today))),
+            "tomorrow" => return day_after(today, 1).map(|date| Some((1, date))),
+            "yesterday" => {
+                return today
+                    .pred_opt()
+                    .map(|date| Some((1, date)))
+                    .ok_or_else(|| self.invalid(at, 1))
+            }
+            "next" | "this" => {
+                return match self.word(at + 1).and_then(weekday) {
+                    Some(weekday) => {
+                        let from = if word == "next" {
+                            day_after(today, 2)?
+                        } else {
+                            today
+                        };
+
+                        Ok(Some((2, on_or_after(from, weekday))))
+                    }
+                    None => Ok(None),
+                }
+            }
+            "the" => {
+                return match self.word(at + 1).and_then(day) {
+                    Some((day, true)) => self.day_of_month(day, at, 2).map(|date| Some((2, date))),
+                    _ => Ok(None),
+                }
+            }
+            _ => {}
+        }
+
+        if let Some(weekday) = weekday(word) {
+            return Ok(Some((1, on_or_after(today, weekday))));
+        }
+
+        if let Some(date) = self.numeric_date(word, at)? {
+            return Ok(Some((1, date)));
+        }
+
+        // "May 3", "May 3rd, 2022"
+        if let Some(month) = month(word) {
+            return match self.word(at + 1).and_then(day) {
+                Some((day, _)) => {
+                    let year = self.word(at + 2).and_then(year);
+                    let len = if year.is_some() { 3 } else { 2 };
+
+                    self.date_in(year, month, day, at, len)
+                        .map(|date| Some((len, date)))
+                }
+                None => Ok(None),
+            };
+        }
+
+        // "3 May", "3rd of May 2022"
+        if let Some((day, _)) = day(word) {
+            let of = usize::from(self.word(at + 1) == Some("of"));
+
+            if let Some(month) = self.word(at + 1 + of).and_then(month) {
+                let year = self.word(at + 2 + of).and_then(year);
+                let len = 2 + of + usize::from(year.is_some());
+
+                return self
+                    .date_in(year, month, day, at, len)
+                    .map(|date| Some((len, date)));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// ISO dates, e.g. "2021-05-03", and day and month separated by slashes, e.g. "15/5" or
+    /// "5/15/2021".
+    fn numeric_date(&self, word: &str, at: usize) -> Result<Option<NaiveDate>, ParseError> {
+        let numbers = |separator| -> Option<Vec<u32>> {
+            let parts: Vec<_> = word.split(separator).collect();
+
+            if parts.len() < 2 || parts.iter().any(|part| !is_number(part)) {
+                return None;
+            }
+
+            parts.iter().map(|part| part.parse().ok()).collect()
+        };
+
+        if let Some(parts) = numbers('-') {
+            return match parts[..] {
+                [year, month, day] if year > 999 => {
+                    NaiveDate::from_ymd_opt(year as i32, month, day)
+                        .map(Some)
+                        .ok_or_else(|| self.invalid(at, 1))
+                }
+                _ => Ok(None),
+            };
+        }
+
+        let parts = match numbers('/') {
+            Some(parts) if parts.len() <= 3 => parts,
+            _ => return Ok(None),
+        };
+        let (day, month) = match (parts[0], parts[1]) {
+            (first, second) if first <= 12 && second <= 12 && first != second => {
+                return Err(ParseError::Ambiguous(self.text(at, 1)))
+            }
+            (first, second) if first > 12 => (first, second),
+            (first, second) => (second, first),
+        };
+        let year = parts.get(2).map(|&year| match year {
+            0..=99 => 2000 + year as i32,
+            _ => year as i32,
+        });
+
+        self.date_in(year, month, day, at, 1).map(Some)
+    }
+
+    /// The date in the given year, or the next one on or after today.
+    fn date_in(
+        &self,
+        year: Option<i32>,
+        month: u32,
+        day: u32,
+        at: usize,
+        len: usize,
+    ) -> Result<NaiveDate, ParseError> {
+        let date = match year {
+            Some(year) => NaiveDate::from_ymd_opt(year, month, day),
+            None => (0..=YEARS_AHEAD)
+                .filter_map(|ahead| NaiveDate::from_ymd_opt(self.today.year() + ahead, month, day))
+                .find(|date| *date >= self.today),
+        };
+
+        date.ok_or_else(|| self.invalid(at, len))
+    }
+
+    /// The next date on or after today falling on the given day of the month.
+    fn day_of_month(&self, day: u32, at: usize, len: usize) -> Result<NaiveDate, ParseError> {
+        let (year, month) = (self.today.year(), self.today.month0());
+
+        (0..12)
+            .filter_map(|ahead| {
+                let month = month + ahead;
+
+                NaiveDate::from_ymd_opt(year + (month / 12) as i32, month % 12 + 1, day)
+            })
+            .find(|date| *date >= self.today)
+            .ok_or_else(|| self.invalid(at, len))
+    }
+
+    /// A count of units, e.g. "3 days", "an hour", "1h30" or "half an hour".
+    fn amount(&self, at: usize) -> Matched<(i64, Unit)> {
+        let word = match self.word(at) {
+            Some(word) => word,
+            None => return Ok(None),
+        };
+
+        if word == "half"
+            && matches!(self.word(at + 1), Some("a" | "an"))
+            && self.word(at + 2) == Some("hour")
+        {
+            return Ok(Some((3, (30, Unit::Minute))));
+        }
+
+        if let Some((count, unit)) = compact_amount(word) {
+            return match count {
+                Some(count) => Ok(Some((1, (count, unit)))),
+                None => Err(self.invalid(at, 1)),
+            };
+        }
+
+        let count = match word {
+            "a" | "an" | "one" => Some(1),
+            _ if is_number(word) => word.parse().ok(),
+            _ => return Ok(None),
+        };
+
+        match (count, self.word(at + 1).and_then(unit)) {
+            (Some(count), Some(unit)) => Ok(Some((2, (count, unit)))),
+            (None, Some(_)) => Err(self.invalid(at, 2)),
+            (_, None) => Ok(None),
+        }
+    }
+
+    fn duration(&self, at: usize) -> Matched<Duration> {
+        match self.amount(at)? {
+            Some((len, (count, unit))) => match unit.times(count).filter(|_| count > 0) {
+                Some(length) => Ok(Some((len, length))),
+                None => Err(self.invalid(at, len)),
+            },
+            None => Ok(None),
+        }
+    }
+
+    /// "3 days" or "2 hours" from the reference, after "in".
+    fn relative(&self, at: usize) -> Matched<Phrase> {
+        let (len, (count, unit)) = match self.amount(at)? {
+            Some(amount) => amount,
+            None => return Ok(None),
+        };
+        let phrase = unit.times(count).and_then(|length| match unit {
+            Unit::Minute | Unit::Hour => self.now.checked_add_signed(length).map(Phrase::Instant),
+            Unit::Day | Unit::Week => self.today.checked_add_signed(length).map(Phrase::Date),
+        });
+
+        match phrase {
+            Some(phrase) => Ok(Some((len, phrase))),
+            None => Err(self.invalid(at - 1, len + 1)),
+        }
+    }
+
+    /// A recurrence, after "every".
+    fn cycle(&self, at: usize) -> Matched<Cycle> {
+        let word = match self.word(at) {
+            Some(word) => word,
+            None => return Ok(None),
+        };
+        let cycle = match word {
+            "day" => Some(Cycle::Rule(Box::new(DailyCycle))),
+            "week" => Some(Cycle::Rule(Box::new(WeeklyCycle))),
+            "month" => Some(Cycle::Rule(Box::new(MonthlyCycle))),
+            "year" => Some(Cycle::Rule(Box::new(AnnualCycle))),
+            "hour" => Some(Cycle::SubDaily(Box::new(HourlyCycle::new(1)))),
+            "minute" => Some(Cycle::SubDaily(Box::new(MinutelyCycle::new(1)))),
+            "weekday" | "workday" => Some(Cycle::WorkingDays),
+            _ => None,
+        };
+
+        if let Some(cycle) = cycle {
+            return Ok(Some((1, cycle)));
+        }
+
+        if matches!(word, "working" | "business") && self.word(at + 1) == Some("day") {
+            return Ok(Some((2, Cycle::WorkingDays)));
+        }
+
+        if (word == "other" || is_number(word)) && self.word(at + 1).is_some_and(is_long_unit) {
+            return Err(ParseError::Unsupported(self.text(at - 1, 3)));
+        }
+
+        if let Some((days, weekdays)) = self.weekdays(at) {
+            return Ok(Some((days, Cycle::Weekdays(weekdays, 1))));
+        }
+
+        // "every other Friday"
+        if word == "other" {
+            return match self.weekdays(at + 1) {
+                Some((days, weekdays)) => Ok(Some((days + 1, Cycle::Weekdays(weekdays, 2)))),
+                None => match self.word(at + 1).and_then(unit) {
+                    Some(unit) => self.every(2, unit, at, 2).map(|cycle| Some((2, cycle))),
+                    None => Ok(None),
+                },
+            };
+        }
+
+        let (len, (count, unit)) = match self.amount(at)? {
+            Some(amount) => amount,
+            None => return Ok(None),
+        };
+
+        // "every 2 weeks on Monday and Wednesday"
+        if unit == Unit::Week && self.word(at + len) == Some("on") {
+            if let Some((days, weekdays)) = self.weekdays(at + len + 1) {
+                let len = len + 1 + days;
+
+                return match weeks(count) {
+                    Some(weeks) => Ok(Some((len, Cycle::Weekdays(weekdays, weeks)))),
+                    None => Err(self.invalid(at - 1, len + 1)),
+                };
+            }
+        }
+
+        self.every(count, unit, at, len)
+            .map(|cycle| Some((len, cycle)))
+    }
+
+    /// Weekdays joined by "and" or "or", e.g. "Monday and Wednesday".
+    fn weekdays(&self, at: usize) -> Option<(usize, Vec<Weekday>)> {
+        let mut weekdays = Vec::new();
+        let mut len = 0;
+
+        loop {
+            if let Some(weekday) = self.word(at + len).and_then(weekday) {
+                weekdays.push(weekday);
+                len += 1;
+            } else if !weekdays.is_empty()
+                && matches!(self.word(at + len), Some("and" | "&" | "or"))
+                && self.word(at + len + 1).and_then(weekday).is_some()
+            {
+                len += 1;
+            } else {
+                break;
+            }
+        }
+
+        if weekdays.is_empty() {
+            None
+        } else {
+            Some((len, weekdays))
+        }
+    }
+
+    /// A recurrence every `count` units, spanning `len` tokens after "every".
+    fn every(&self, count: i64, unit: Unit, at: usize, len: usize) -> Result<Cycle, ParseError> {
+        let hours = |per_unit: u32| u32::try_from(count).ok()?.checked_mul(per_unit);
+        let cycle = match (count, unit) {
+            (1, Unit::Day) => Some(Cycle::Rule(Box::new(DailyCycle))),
+            (1, Unit::Week) => Some(Cycle::Rule(Box::new(WeeklyCycle))),
+            (_, Unit::Minute) => u32::try_from(count)
+                .ok()
+                .and_then(MinutelyCycle::new_opt)
+                .map(|cycle| Cycle::SubDaily(Box::new(cycle))),
+            (_, Unit::Hour) => hours(1)
+                .and_then(HourlyCycle::new_opt)
+                .map(|cycle| Cycle::SubDaily(Box::new(cycle))),
+            (_, Unit::Day) => hours(24)
+                .and_then(HourlyCycle::new_opt)
+                .map(|cycle| Cycle::Rule(Box::new(cycle))),
+            (_, Unit::Week) => weeks(count)
+                .map(|weeks| Cycle::Rule(Box::new(HourlyCycle::new(weeks * HOURS_PER_WEEK)))),
+        };
+
+        cycle.ok_or_else(|| self.invalid(at - 1, len + 1))
+    }
+}
+
+impl<'a> Entry<'a> {
+    fn set(&mut self, phrase: String, value: Phrase) -> Result<(), ParseError> {
+        match value {
+            Phrase::Date(date) => fill(&mut self.date, phrase, date),
+            Phrase::Starting(date) => {
+                self.starting = true;
+                fill(&mut self.date, phrase, date)
+            }
+            Phrase::Time(time) => fill(&mut self.start, phrase, time),
+            Phrase::Until(time) => fill(&mut self.end, phrase, End::At(time)),
+            Phrase::Range(from, to) => {
+                fill(&mut self.start, phrase.clone(), from)?;
+                fill(&mut self.end, phrase, End::At(to))
+            }
+            Phrase::For(length) => fill(&mut self.end, phrase, End::After(length)),
+            Phrase::Instant(at) => {
+                fill(&mut self.date, phrase.clone(), at.date())?;
+                fill(&mut self.start, phrase, at.time())
+            }
+            Phrase::Cycle(cycle) => fill(&mut self.cycle, phrase, cycle),
+        }
+    }
+
+    fn complete(self, today: NaiveDate) -> Result<EventPartial, ParseError> {
+        let name = self.name.join(" ");
+        let name = name.trim_end_matches(|c: char| ",;:-".contains(c)).trim();
+
+        if name.is_empty() {
+            return Err(ParseError::MissingName);
+        }
+
+        if self.date.is_none() && self.start.is_none() && self.cycle.is_none() {
+            return Err(ParseError::MissingStart);
+        }
+
+        let mut date = self.date.as_ref().map_or(today, |(_, date)| *date);
+        // A date the recurrence must start on, rather than after
+        let exact = self.date.as_ref().filter(|_| !self.starting);
+        let cycle: Option<Box<dyn Cyclicity>> = match self.cycle {
+            None => None,
+            Some((_, Cycle::Rule(rule))) => Some(rule),
+            Some((_, Cycle::SubDaily(_))) if self.start.is_none() => {
+                return Err(ParseError::MissingStart)
+            }
+            Some((_, Cycle::SubDaily(rule))) => Some(rule),
+            Some((phrase, Cycle::WorkingDays)) => {
+                let working_days = WorkingDays::new();
+
+                if let Some((first, _)) =
+                    exact.filter(|_| !working_days.is_working_day(Date::from_utc(date, Utc)))
+                {
+                    return Err(ParseError::Conflicting(first.clone(), phrase));
+                }
+
+                date = working_days
+                    .following(Date::from_utc(date, Utc))
+                    .ok_or(ParseError::Unsupported(phrase))?
+                    .naive_utc();
+
+                Some(Box::new(WorkingDayCycle(working_days)))
+            }
+            Some((phrase, Cycle::Weekdays(weekdays, weeks))) => {
+                if let Some((first, _)) = exact.filter(|_| !weekdays.contains(&date.weekday())) {
+                    return Err(ParseError::Conflicting(first.clone(), phrase));
+                }
+
+                while !weekdays.contains(&date.weekday()) {
+                    date = date
+                        .succ_opt()
+                        .ok_or_else(|| ParseError::Invalid(phrase.clone()))?;
+                }
+
+                Some(weekly_on(date, &weekdays, weeks))
+            }
+        };
+
+        let partial = EventPartial::new(name.to_string());
+        let partial = match (self.start, self.end) {
+            (Some((start_phrase, time)), end) => {
+                let start = date.and_time(time);
+                let end = match end {
+                    Some((phrase, End::At(time))) if date.and_time(time) <= start => date
+                        .succ_opt()
+                        .ok_or(ParseError::Invalid(phrase))?
+                        .and_time(time),
+                    Some((_, End::At(time))) => date.and_time(time),
+                    Some((phrase, End::After(length))) => start
+                        .checked_add_signed(length)
+                        .ok_or(ParseError::Invalid(phrase))?,
+                    None => start
+                        .checked_add_signed(Duration::minutes(DEFAULT_LENGTH))
+                        .ok_or(ParseError::Invalid(start_phrase))?,
+                };
+
+                partial.with_period(StartEnd(
+                    DateTime::from_naive_utc_and_offset(start, Utc),
+                    DateTime::from_naive_utc_and_offset(end, Utc),
+                ))
+            }
+            (None, None) => partial.whole_day(Date::from_utc(date, Utc)),
+            (None, Some((phrase, End::After(length)))) if length.num_days() > 0 => {
+                let last = date
+                    .checked_add_signed(Duration::days(length.num_days() - 1))
+                    .ok_or(ParseError::Invalid(phrase))?;
+
+                partial.whole_days(Date::from_utc(date, Utc), Date::from_utc(last, Utc))
+            }
+            (None, Some(_)) => return Err(ParseError::MissingStart),
+        };
+
+        Ok(match cycle {
+            Some(cycle) => partial.with_cyclicity(cycle),
+            None => partial,
+        })
+    }
+}
+
+fn fill<T>(slot: &mut Option<(String, T)>, phrase: String, value: T) -> Result<(), ParseError> {
+    match slot {
+        Some((first, _)) => Err(ParseError::Conflicting(first.clone(), phrase)),
+        None => {
+            *slot = Some((phrase, value));
+            Ok(())
+        }
+    }
+}
+
+/// Repeats a period starting on `first` every given number of weeks on the given weekdays,
+/// `first` being one of them. Weeks start on Monday, so that every other Monday and
+/// Wednesday from a Wednesday goes on with the Monday 12 days later.
+fn weekly_on(first: NaiveDate, weekdays: &[Weekday], weeks: u32) -> Box<dyn Cyclicity> {
+    let origin = first.weekday().num_days_from_monday() as i64;
+    let mut offsets: Vec<i64> = weekdays
+        .iter()
+        .map(
+            |weekday| match weekday.num_days_from_monday() as i64 - origin {
+                offset if offset < 0 => offset + 7 * weeks as i64,
+                offset => offset,
+            },
+        )
+        .collect();
+    let every = || -> Box<dyn Cyclicity> {
+        match weeks {
+            1 => Box::new(WeeklyCycle),
+            _ => Box::new(HourlyCycle::new(weeks * HOURS_PER_WEEK)),
+        }
+    };
+
+    offsets.sort_unstable();
+    offsets.dedup();
+
+    if offsets.len() == 1 {
+        return every();
+    }
+
+    let rules = offsets
+        .into_iter()
+        .map(|offset| match offset {
+            0 => every(),
+            _ => Box::new(Shifted::new(Duration::days(offset), every())),
+        })
+        .collect();
+
+    Box::new(AnyOf(rules))
+}
+
+fn on_or_after(date: NaiveDate, weekday: Weekday) -> NaiveDate {
+    let days = weekday.num_days_from_monday() as i64 - date.weekday().num_days_from_monday() as i64;
+
+    date + Duration::days(days.rem_euclid(7))
+}
+
+impl Clock {
+    fn resolve(self) -> Option<NaiveTime> {
+        let hour = match self.meridiem {
+            None => self.hour,
+            Some(_) if self.hour == 0 || self.hour > 12 => return None,
+            Some(Meridiem::Am) => self.hour % 12,
+            Some(Meridiem::Pm) => self.hour % 12 + 12,
+        };
+
+        NaiveTime::from_hms_opt(hour, self.minute, 0)
+    }
+}
+
+impl Unit {
+    /// The length of `count` units, unless out of range.
+    fn times(self, count: i64) -> Option<Duration> {
+        match self {
+            Unit::Minute => Duration::try_minutes(count),
+            Unit::Hour => Duration::try_hours(count),
+            Unit::Day => Duration::try_days(count),
+            Unit::Week => Duration::try_weeks(count),
+        }
+    }
+}
+
+/// A positive number of weeks, few enough to be counted in hours.
+fn weeks(count: i64) -> Option<u32> {
+    u32::try_from(count)
+        .ok()
+        .filter(|weeks| *weeks > 0 && weeks.checked_mul(HOURS_PER_WEEK).is_some())
+}
+
+fn is_number(word: &str) -> bool {
+    !word.is_empty() && word.bytes().all(|byte| byte.is_ascii_digit())
+}
+
+/// Times such as "9", "9:30", "9pm" or "noon".
+fn clock(word: &str) -> Option<Clock> {
+    let named = |hour| Clock {
+        hour,
+        minute: 0,
+        meridiem: None,
+        explicit: true,
+        named: true,
+    };
+
+    match word {
+        "noon" | "midday" => return Some(named(12)),
+        "midnight" => return Some(named(0)),
+        _ => {}
+    }
+
+    let (digits, meridiem) = ["am", "a.m", "pm", "p.m"]
+        .iter()
+        .find_map(|suffix| {
+            let meridiem = if suffix.starts_with('a') {
+                Meridiem::Am
+            } else {
+                Meridiem::Pm
+            };
+
+            word.strip_suffix(suffix)
+                .map(|digits| (digits, Some(meridiem)))
+        })
+        .unwrap_or((word, None));
+    let (hour, minute) = match digits.split_once(':') {
+        Some((hour, minute)) if minute.len() == 2 && is_number(minute) => {
+            (hour, minute.parse().ok()?)
+        }
+        Some(_) => return None,
+        None => (digits, 0),
+    };
+
+    if !is_number(hour) || hour.len() > 2 {
+        return None;
+    }
+
+    Some(Clock {
+        hour: hour.parse().ok()?,
+        minute,
+        meridiem,
+        explicit: meridiem.is_some() || digits.contains(':'),
+        named: false,
+    })
+}
+
+fn meridiem(word: &str) -> Option<Meridiem> {
+    match word {
+        "am" | "a.m" => Some(Meridiem::Am),
+        "pm" | "p.m" => Some(Meridiem::Pm),
+        _ => None,
+    }
+}
+
+/// A number of `unit`s written as a single word, e.g. "90min", "2h" or "1h30", the number
+/// being `None` when out of range.
+fn compact_amount(word: &str) -> Option<(Option<i64>, Unit)> {
+    let split = word.find(|c: char| !c.is_ascii_digit())?;
+    let (count, rest) = word.split_at(split);
+
+    if count.is_empty() {
+        return None;
+    }
+
+    let count: Option<i64> = count.parse().ok();
+
+    if let Some(minutes) = rest.strip_prefix('h') {
+        let minutes = minutes
+            .strip_suffix("min")
+            .or_else(|| minutes.strip_suffix('m'))
+            .unwrap_or(minutes);
+
+        if is_number(minutes) {
+            let total = count.and_then(|hours| {
+                hours
+                    .checked_mul(60)?
+                    .checked_add(minutes.parse::<i64>().ok()?)
+            });
+
+            return Some((total, Unit::Minute));
+        }
+    }
+
+    Some((count, unit(rest)?))
+}
+
+/// Units recurrences can only repeat every one of, e.g. in "every 2 months".
+fn is_long_unit(word: &str) -> bool {
+    matches!(word, "month" | "months" | "year" | "years")
+}
+
+fn unit(word: &str) -> Option<Unit> {
+    match word {
+        "m" | "min" | "mins" | "minute" | "minutes" => Some(Unit::Minute),
+        "h" | "hr" | "hrs" | "hour" | "hours" => Some(Unit::Hour),
+        "d" | "day" | "days" => Some(Unit::Day),
+        "w" | "week" | "weeks" => Some(Unit::Week),
+        _ => None,
+    }
+}
+
+/// Full weekday names, in the singular or plural, e.g. "Friday" or "Fridays".
+fn weekday(word: &str) -> Option<Weekday> {
+    let word = word.strip_suffix('s').unwrap_or(word);
+
+    match word {
+        "monday" => Some(Weekday::Mon),
+        "tuesday" => Some(Weekday::Tue),
+        "wednesday" => Some(Weekday::Wed),
+        "thursday" => Some(Weekday::Thu),
+        "friday" => Some(Weekday::Fri),
+        "saturday" => Some(Weekday::Sat),
+        "sunday" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+/// Month names and their abbreviations.
+fn month(word: &str) -> Option<u32> {
+    let month = match word {
+        "january" | "jan" => 1,
+        "february" | "feb" => 2,
+        "march" | "mar" => 3,
+        "april" | "apr" => 4,
+        "may" => 5,
+        "june" | "jun" => 6,
+        "july" | "jul" => 7,
+        "august" | "aug" => 8,
+        "september" | "sep" | "sept" => 9,
+        "october" | "oct" => 10,
+        "november" | "nov" => 11,
+        "december" | "dec" => 12,
+        _ => return None,
+    };
+
+    Some(month)
+}
+
+/// A day of the month, along with whether it is written as an ordinal, e.g. "3rd".
+fn day(word: &str) -> Option<(u32, bool)> {
+    let (digits, ordinal) = ["st", "nd", "rd", "th"]
+        .iter()
+        .find_map(|suffix| word.strip_suffix(suffix))
+        .map_or((word, false), |digits| (digits, true));
+
+    if !is_number(digits) || digits.len() > 2 {
+        return None;
+    }
+
+    Some((digits.parse().ok()?, ordinal))
+}
+
+fn year(word: &str) -> Option<i32> {
+    if word.len() == 4 && is_number(word) {
+        word.parse().ok()
+    } else {
+        None
+    }
+}
@@ -0,0 +1,119 @@
+use chrono::{
+    Date, DateTime, Datelike, Duration, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, TimeZone,
+    Utc,
+};
+use std::ops::Range;
+
+#[cfg(feature = "serde_support")]
+use serde::{Deserialize, Serialize};
+
+use super::{Period, StartEnd};
+
+/**
+   Period defined with wall-clock times, independent of any time zone: 09:00 stays 09:00
+   wherever the calendar is looked at. Queries treat the wall-clock times as UTC; use
+   `at_offset` to pin them to a specific zone.
+*/
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
+pub struct FloatingTime(pub NaiveDateTime, pub NaiveDateTime);
+
+impl FloatingTime {
+    /**
+       Resolves the wall-clock times in a zone of the given offset.
+
+       # Examples
+
+       ```rust
+       use dateless::prelude::*;
+       use chrono::{FixedOffset, NaiveDate, TimeZone, Utc};
+
+       let day = NaiveDate::from_ymd(2021, 5, 8);
+       let period = FloatingTime(day.and_hms(9, 0, 0), day.and_hms(10, 0, 0));
+       let warsaw = period.at_offset(FixedOffset::east(2 * 3600));
+
+       assert_eq!(warsaw.0, Utc.ymd(2021, 5, 8).and_hms(7, 0, 0));
+       ```
+    */
+    pub fn at_offset(&self, offset: FixedOffset) -> StartEnd {
+        StartEnd(
+            offset
+                .from_local_datetime(&self.0)
+                .unwrap()
+                .with_timezone(&Utc),
+            offset
+                .from_local_datetime(&self.1)
+                .unwrap()
+                .with_timezone(&Utc),
+        )
+    }
+
+    /// The same period moved to start at the wall-clock time of the given instant in UTC.
+    pub fn starting_at(&self, start: DateTime<Utc>) -> Self {
+        let start = start.naive_utc();
+
+        Self(start, start + (self.1 - self.0))
+    }
+}
+
+#[cfg_attr(feature = "serde_support", typetag::serde)]
+impl Period for FloatingTime {
+    fn contains(&self, date: Date<Utc>) -> bool {
+        StartEnd(self.get_date_time_start(), self.get_date_time_end()).contains(date)
+    }
+
+    fn get_date_time_start(&self) -> DateTime<Utc> {
+        DateTime::from_naive_utc_and_offset(self.0, Utc)
+    }
+
+    fn get_date_time_end(&self) -> DateTime<Utc> {
+        DateTime::from_naive_utc_and_offset(self.1, Utc)
+    }
+
+    fn starts_before(&self, date: Date<Utc>) -> bool {
+        self.0 < date.naive_utc().and_time(NaiveTime::MIN)
+    }
+
+    fn with_new_start(&self, date: Date<Utc>) -> Box<dyn Period> {
+        let start = date.naive_utc().and_time(self.0.time());
+
+        Box::new(Self(start, start + (self.1 - self.0)))
+    }
+
+    fn with_new_date_time_start(&self, start: DateTime<Utc>) -> Box<dyn Period> {
+        Box::new(self.starting_at(start))
+    }
+
+    fn with_range(&self, range: Range<DateTime<Utc>>) -> Box<dyn Period> {
+        Box::new(Self(range.start.naive_utc(), range.end.naive_utc()))
+    }
+
+    fn as_weekdays(&self) -> (u32, u32) {
+        (
+            self.0.weekday().number_from_monday(),
+            self.1.weekday().number_from_monday(),
+        )
+    }
+
+    fn as_days_of_month(&self) -> (u32, u32) {
+        (self.0.day(), self.1.day())
+    }
+
+    fn as_months(&self) -> (u32, u32) {
+        (self.0.month(), self.1.month())
+    }
+
+    fn with_new_month(&self, month: u32) -> Date<Utc> {
+        Date::from_utc(
+            NaiveDate::from_ymd_opt(self.0.year(), month, self.0.day()).unwrap(),
+            Utc,
+        )
+    }
+
+    fn shift(&self, by: Duration) -> Box<dyn Period> {
+        Box::new(Self(self.0 + by, self.1 + by))
+    }
+
+    impl_cloned!(Period);
+}
@@ -0,0 +1,134 @@
+use chrono::{DateTime, Utc};
+use std::ops::Deref;
+
+#[cfg(feature = "serde_support")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use super::{FloatingTime, Moment, OpenEnded, Period, PeriodDef, StartEnd, WholeDays};
+
+/**
+   A period held by value: one of the built-in kinds, or any other `Period` boxed as `Custom`.
+
+   Built-in kinds are cloned, compared and moved without allocating. It dereferences to
+   `dyn Period`, so every method of the trait can be called on it. Serialized, it is
+   indistinguishable from the boxed period.
+
+   # Examples
+
+   ```rust
+   use dateless::prelude::*;
+   use chrono::{Duration, TimeZone, Utc};
+
+   let start = Utc.ymd(2021, 5, 8).and_hms(9, 0, 0);
+   let period = PeriodKind::from(StartEnd(start, start + Duration::hours(1)));
+   let next_day = period.starting_at(start + Duration::days(1));
+
+   assert_eq!(next_day.duration(), Duration::hours(1));
+   assert_ne!(next_day, period);
+   ```
+*/
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PeriodKind {
+    StartEnd(StartEnd),
+    WholeDays(WholeDays),
+    Moment(Moment),
+    OpenEnded(OpenEnded),
+    FloatingTime(FloatingTime),
+    Custom(Box<dyn Period>),
+}
+
+impl PeriodKind {
+    /// The same period moved to start at the given instant. Only `Custom` periods allocate.
+    pub fn starting_at(&self, start: DateTime<Utc>) -> Self {
+        match self {
+            Self::StartEnd(period) => Self::StartEnd(period.starting_at(start)),
+            Self::WholeDays(period) => Self::WholeDays(period.starting_at(start)),
+            Self::Moment(period) => Self::Moment(period.starting_at(start)),
+            Self::OpenEnded(period) => Self::OpenEnded(period.starting_at(start)),
+            Self::FloatingTime(period) => Self::FloatingTime(period.starting_at(start)),
+            Self::Custom(period) => Self::Custom(period.with_new_date_time_start(start)),
+        }
+    }
+}
+
+impl Deref for PeriodKind {
+    type Target = dyn Period;
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            Self::StartEnd(period) => period,
+            Self::WholeDays(period) => period,
+            Self::Moment(period) => period,
+            Self::OpenEnded(period) => period,
+            Self::FloatingTime(period) => period,
+            Self::Custom(period) => period.as_ref(),
+        }
+    }
+}
+
+impl Default for PeriodKind {
+    fn default() -> Self {
+        PeriodDef::default().into()
+    }
+}
+
+macro_rules! impl_from_built_in {
+    ($($kind:ident),*) => {
+        $(
+            impl From<$kind> for PeriodKind {
+                fn from(period: $kind) -> Self {
+                    Self::$kind(period)
+                }
+            }
+        )*
+
+        /// Unboxes built-in kinds, keeping the other periods as `Custom`.
+        impl From<Box<dyn Period>> for PeriodKind {
+            fn from(period: Box<dyn Period>) -> Self {
+                let any = period.as_ref().as_any();
+
+                $(
+                    if let Some(period) = any.downcast_ref::<$kind>() {
+                        return Self::$kind(period.clone());
+                    }
+                )*
+
+                Self::Custom(period)
+            }
+        }
+
+        impl<T: Period> From<Box<T>> for PeriodKind {
+            fn from(period: Box<T>) -> Self {
+                Self::from(period as Box<dyn Period>)
+            }
+        }
+    };
+}
+
+impl_from_built_in!(StartEnd, WholeDays, Moment, OpenEnded, FloatingTime);
+
+impl From<PeriodDef> for PeriodKind {
+    fn from(period: PeriodDef) -> Self {
+        period.0.into()
+    }
+}
+
+#[cfg(feature = "serde_support")]
+impl Serialize for PeriodKind {
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        crate::serde::period::serialize_dyn(&**self, s)
+    }
+}
+
+#[cfg(feature = "serde_support")]
+impl<'de> Deserialize<'de> for PeriodKind {
+    fn deserialize<D>(d: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        crate::serde::period::deserialize(d).map(Self::from)
+    }
+}
@@ -1,42 +1,179 @@
-use chrono::{Date, DateTime, Utc};
-use std::fmt::Debug;
+use chrono::{Date, DateTime, Duration, Utc};
+use std::{
+    fmt::Debug,
+    hash::{Hash, Hasher},
+    ops::Range,
+};
+
+use super::{DynEq, DynHash};
 
 #[cfg(feature = "serde_support")]
 use serde::{Deserialize, Serialize};
 
+/**
+   A span of time an event lasts.
+
+   Whatever the boundaries a period is defined with, the trait exposes it as a half-open
+   `[start, end)` range of instants: `get_date_time_end` is the first instant *after* the
+   period, so e.g. `WholeDays(may_8, may_8)` ends at midnight opening May 9th.
+*/
 #[cfg_attr(feature = "serde_support", typetag::serde(tag = "type"))]
-pub trait Period: Debug + Send {
+pub trait Period: DynEq + DynHash + Debug + Send {
+    /// Whether any instant of the period falls on the given day. Zero-length periods are
+    /// contained by the day of their single instant.
     fn contains(&self, date: Date<Utc>) -> bool;
     fn get_date_time_start(&self) -> DateTime<Utc>;
+    /// First instant after the period, i.e. the exclusive end of it.
+    fn get_date_time_end(&self) -> DateTime<Utc>;
+    /// Whether the period starts before the midnight opening the given day.
     fn starts_before(&self, date: Date<Utc>) -> bool;
     fn with_new_start(&self, date: Date<Utc>) -> Box<dyn Period>;
+    fn with_new_date_time_start(&self, start: DateTime<Utc>) -> Box<dyn Period>;
     fn cloned(&self) -> Box<dyn Period>;
+    /// Weekdays the period starts and ends on, numbered from Monday. The end comes first when
+    /// the period spans the end of the week, e.g. `(7, 2)` from Sunday to Tuesday; see
+    /// `WeekStart::number` for weeks starting on another day.
     fn as_weekdays(&self) -> (u32, u32);
     fn as_days_of_month(&self) -> (u32, u32);
     fn as_months(&self) -> (u32, u32);
     fn with_new_month(&self, month: u32) -> Date<Utc>;
+
+    fn range(&self) -> Range<DateTime<Utc>> {
+        self.get_date_time_start()..self.get_date_time_end()
+    }
+
+    fn duration(&self) -> Duration {
+        self.get_date_time_end() - self.get_date_time_start()
+    }
+
+    /// Whether the instant falls into the period. A zero-length period contains only the
+    /// instant it is placed at.
+    fn contains_instant(&self, instant: DateTime<Utc>) -> bool {
+        let Range { start, end } = self.range();
+
+        instant == start || (instant > start && instant < end)
+    }
+
+    fn overlaps(&self, other: &dyn Period) -> bool {
+        let zero = Duration::zero();
+
+        if self.duration() == zero {
+            other.contains_instant(self.get_date_time_start())
+        } else if other.duration() == zero {
+            self.contains_instant(other.get_date_time_start())
+        } else {
+            self.get_date_time_start() < other.get_date_time_end()
+                && other.get_date_time_start() < self.get_date_time_end()
+        }
+    }
+
+    /// Builds a period of the same kind spanning the given range, if the kind can represent
+    /// it, or a `StartEnd` otherwise.
+    fn with_range(&self, range: Range<DateTime<Utc>>) -> Box<dyn Period> {
+        Box::new(StartEnd(range.start, range.end))
+    }
+
+    fn intersection(&self, other: &dyn Period) -> Option<Box<dyn Period>> {
+        if !self.overlaps(other) {
+            return None;
+        }
+
+        let start = self.get_date_time_start().max(other.get_date_time_start());
+        let end = self.get_date_time_end().min(other.get_date_time_end());
+
+        Some(self.with_range(start..end.max(start)))
+    }
+
+    /// Merges two overlapping or adjacent periods into a single one.
+    fn union(&self, other: &dyn Period) -> Option<Box<dyn Period>> {
+        let adjacent = self.get_date_time_end() == other.get_date_time_start()
+            || other.get_date_time_end() == self.get_date_time_start();
+
+        if !adjacent && !self.overlaps(other) {
+            return None;
+        }
+
+        let start = self.get_date_time_start().min(other.get_date_time_start());
+        let end = self.get_date_time_end().max(other.get_date_time_end());
+
+        Some(self.with_range(start..end))
+    }
+
+    /// Moves the period by the given duration. `WholeDays` moves by the days the shifted
+    /// start falls on.
+    fn shift(&self, by: Duration) -> Box<dyn Period> {
+        self.with_new_date_time_start(self.get_date_time_start() + by)
+    }
+}
+
+impl PartialEq for dyn Period {
+    fn eq(&self, other: &Self) -> bool {
+        self.dyn_eq(other.as_any())
+    }
+}
+
+impl Eq for dyn Period {}
+
+impl Hash for dyn Period {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.dyn_hash(state);
+    }
+}
+
+// Derived comparisons of boxed fields coerce the right-hand side to a reference
+// (rust-lang/rust#31740).
+impl PartialEq<&Self> for Box<dyn Period> {
+    fn eq(&self, other: &&Self) -> bool {
+        self.as_ref() == other.as_ref()
+    }
+}
+
+impl Clone for Box<dyn Period> {
+    fn clone(&self) -> Self {
+        self.cloned()
+    }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
-pub struct PeriodDef(pub Box<dyn Period>);
+pub struct PeriodDef(
+    #[cfg_attr(feature = "serde_support", serde(with = "crate::serde::period"))] pub Box<dyn Period>,
+);
 
 impl Default for PeriodDef {
     fn default() -> Self {
-        Self(Box::new(WholeDays(
-            Utc::today(),
-            Utc::today() + chrono::Duration::days(1),
-        )))
+        Self(Box::new(WholeDays(Utc::today(), Utc::today())))
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EndBoundary {
+    Inclusive,
+    Exclusive,
+}
+
+/**
+   Boundaries a period is defined with, as stored. Unlike `Period::range`, `end` follows
+   the `END` convention of the implementor: `StartEnd` ends exclusively, while `WholeDays`
+   includes its last day.
+*/
 pub trait WithBoundaries<T> {
+    const END: EndBoundary;
+
     fn start(&self) -> T;
     fn end(&self) -> T;
 }
 
+mod floating_time;
+mod kind;
+mod moment;
+mod open_ended;
 mod start_end;
 mod whole_days;
 
+pub use floating_time::*;
+pub use kind::*;
+pub use moment::*;
+pub use open_ended::*;
 pub use start_end::*;
 pub use whole_days::*;
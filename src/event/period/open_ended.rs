@@ -0,0 +1,89 @@
+use chrono::{Date, DateTime, Datelike, Duration, NaiveDate, Utc};
+use std::ops::Range;
+
+#[cfg(feature = "serde_support")]
+use serde::{Deserialize, Serialize};
+
+use super::{Period, StartEnd};
+
+/**
+   Period with a start but no end, e.g. "on leave from March 3rd until further notice".
+   Its exclusive end is reported as the latest representable instant.
+*/
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
+pub struct OpenEnded(
+    #[cfg_attr(feature = "serde_support", serde(with = "crate::serde::timestamp"))]
+    #[cfg_attr(feature = "json_schema", schemars(with = "crate::schema::Timestamp"))]
+    pub DateTime<Utc>,
+);
+
+impl OpenEnded {
+    /// The same period moved to start at the given instant.
+    pub fn starting_at(&self, start: DateTime<Utc>) -> Self {
+        Self(start)
+    }
+}
+
+#[cfg_attr(feature = "serde_support", typetag::serde)]
+impl Period for OpenEnded {
+    fn contains(&self, date: Date<Utc>) -> bool {
+        self.0 < (date + Duration::days(1)).and_hms_opt(0, 0, 0).unwrap()
+    }
+
+    fn get_date_time_start(&self) -> DateTime<Utc> {
+        self.0
+    }
+
+    fn get_date_time_end(&self) -> DateTime<Utc> {
+        DateTime::<Utc>::MAX_UTC
+    }
+
+    fn starts_before(&self, date: Date<Utc>) -> bool {
+        self.0 < date.and_hms_opt(0, 0, 0).unwrap()
+    }
+
+    fn with_new_start(&self, date: Date<Utc>) -> Box<dyn Period> {
+        Box::new(Self(date.and_time(self.0.time()).unwrap()))
+    }
+
+    fn with_new_date_time_start(&self, start: DateTime<Utc>) -> Box<dyn Period> {
+        Box::new(self.starting_at(start))
+    }
+
+    fn with_range(&self, range: Range<DateTime<Utc>>) -> Box<dyn Period> {
+        if range.end == DateTime::<Utc>::MAX_UTC {
+            return Box::new(Self(range.start));
+        }
+
+        Box::new(StartEnd(range.start, range.end))
+    }
+
+    fn as_weekdays(&self) -> (u32, u32) {
+        let weekday = self.0.weekday().number_from_monday();
+
+        (weekday, weekday)
+    }
+
+    fn as_days_of_month(&self) -> (u32, u32) {
+        (self.0.day(), self.0.day())
+    }
+
+    fn as_months(&self) -> (u32, u32) {
+        (self.0.month(), self.0.month())
+    }
+
+    fn with_new_month(&self, month: u32) -> Date<Utc> {
+        Date::from_utc(
+            NaiveDate::from_ymd_opt(self.0.year(), month, self.0.day()).unwrap(),
+            Utc,
+        )
+    }
+
+    fn shift(&self, by: Duration) -> Box<dyn Period> {
+        Box::new(Self(self.0 + by))
+    }
+
+    impl_cloned!(Period);
+}
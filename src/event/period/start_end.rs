@@ -1,46 +1,54 @@
-use chrono::{Date, DateTime, Datelike, Duration, NaiveDate, NaiveTime, Utc};
-
-#[cfg(feature = "serde_support")]
-use chrono::serde::ts_seconds;
+use chrono::{Date, DateTime, Datelike, Duration, NaiveDate, Utc};
 
 #[cfg(feature = "serde_support")]
 use serde::{Deserialize, Serialize};
 
 use super::Period;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct StartEnd(
-    #[cfg_attr(feature = "serde_support", serde(with = "ts_seconds"))] pub DateTime<Utc>,
-    #[cfg_attr(feature = "serde_support", serde(with = "ts_seconds"))] pub DateTime<Utc>,
+    #[cfg_attr(feature = "serde_support", serde(with = "crate::serde::timestamp"))]
+    #[cfg_attr(feature = "json_schema", schemars(with = "crate::schema::Timestamp"))]
+    pub DateTime<Utc>,
+    #[cfg_attr(feature = "serde_support", serde(with = "crate::serde::timestamp"))]
+    #[cfg_attr(feature = "json_schema", schemars(with = "crate::schema::Timestamp"))]
+    pub DateTime<Utc>,
 );
 
-impl_period_boundaries!(StartEnd, DateTime<Utc>);
+impl_period_boundaries!(StartEnd, DateTime<Utc>, Exclusive);
+
+impl StartEnd {
+    /// The same period moved to start at the given instant.
+    pub fn starting_at(&self, start: DateTime<Utc>) -> Self {
+        Self(start, start + (self.end() - self.start()))
+    }
+}
 
 #[cfg_attr(feature = "serde_support", typetag::serde)]
 impl Period for StartEnd {
     fn contains(&self, date: Date<Utc>) -> bool {
-        {
-            let date = date.and_time(NaiveTime::from_hms(0, 0, 0)).unwrap();
-            if !((self.start() - date).num_milliseconds() >= 0
-                || (self.end() - date).num_milliseconds() > 0)
-            {
-                return false;
-            }
+        let midnight = date.and_hms_opt(0, 0, 0).unwrap();
+        let next_midnight = midnight + Duration::days(1);
+
+        if self.start() == self.end() {
+            return self.start() >= midnight && self.start() < next_midnight;
         }
-        let date = (date + Duration::days(1))
-            .and_time(Utc::now().time())
-            .unwrap();
-        (self.start() - date).num_milliseconds() < 0
+
+        self.start() < next_midnight && self.end() > midnight
     }
 
     fn get_date_time_start(&self) -> DateTime<Utc> {
         self.start().clone()
     }
 
+    fn get_date_time_end(&self) -> DateTime<Utc> {
+        self.end()
+    }
+
     fn starts_before(&self, date: Date<Utc>) -> bool {
-        let date = date.and_time(Utc::now().time()).unwrap();
-        (self.start() - date).num_milliseconds() < 0
+        self.start() < date.and_hms_opt(0, 0, 0).unwrap()
     }
 
     fn with_new_start(&self, date: Date<Utc>) -> Box<dyn Period> {
@@ -52,6 +60,10 @@ impl Period for StartEnd {
         Box::new(Self(date, date + total_duration))
     }
 
+    fn with_new_date_time_start(&self, start: DateTime<Utc>) -> Box<dyn Period> {
+        Box::new(self.starting_at(start))
+    }
+
     fn as_weekdays(&self) -> (u32, u32) {
         (
             self.start().date().weekday().number_from_monday(),
@@ -69,7 +81,7 @@ impl Period for StartEnd {
 
     fn with_new_month(&self, month: u32) -> Date<Utc> {
         Date::from_utc(
-            NaiveDate::from_ymd(self.start().year(), month, self.start().day()),
+            NaiveDate::from_ymd_opt(self.start().year(), month, self.start().day()).unwrap(),
             Utc,
         )
     }
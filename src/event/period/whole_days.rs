@@ -1,4 +1,4 @@
-use chrono::{Date, DateTime, Datelike, NaiveDate, NaiveTime, Utc};
+use chrono::{Date, DateTime, Datelike, Duration, NaiveDate, NaiveTime, Utc};
 
 #[cfg(feature = "serde_support")]
 use serde::{Deserialize, Serialize};
@@ -6,10 +6,12 @@ use serde::{Deserialize, Serialize};
 #[cfg(feature = "serde_support")]
 use crate::serde::{from_date_into_string, from_string_into_date};
 
-use super::Period;
+use super::{Period, StartEnd};
+use std::ops::Range;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
 pub struct WholeDays(
     #[cfg_attr(
         feature = "serde_support",
@@ -18,6 +20,7 @@ pub struct WholeDays(
             deserialize_with = "from_string_into_date"
         )
     )]
+    #[cfg_attr(feature = "json_schema", schemars(with = "chrono::NaiveDate"))]
     pub Date<Utc>,
     #[cfg_attr(
         feature = "serde_support",
@@ -26,10 +29,44 @@ pub struct WholeDays(
             deserialize_with = "from_string_into_date"
         )
     )]
+    #[cfg_attr(feature = "json_schema", schemars(with = "chrono::NaiveDate"))]
     pub Date<Utc>,
 );
 
-impl_period_boundaries!(WholeDays, Date<Utc>);
+impl_period_boundaries!(WholeDays, Date<Utc>, Inclusive);
+
+impl WholeDays {
+    /**
+       Creates `WholeDays` from an exclusive end date, as iCalendar's `DTEND` of all-day
+       events is. An end not later than the start yields a single day.
+
+       # Examples
+
+       ```rust
+       use dateless::prelude::*;
+       use chrono::{TimeZone, Utc};
+
+       let period = WholeDays::from_exclusive_end(Utc.ymd(2021, 5, 8), Utc.ymd(2021, 5, 9));
+
+       assert_eq!(period.1, Utc.ymd(2021, 5, 8));
+       assert_eq!(period.exclusive_end(), Utc.ymd(2021, 5, 9));
+       ```
+    */
+    pub fn from_exclusive_end(start: Date<Utc>, end: Date<Utc>) -> Self {
+        Self(start, (end - Duration::days(1)).max(start))
+    }
+
+    pub fn exclusive_end(&self) -> Date<Utc> {
+        self.end() + Duration::days(1)
+    }
+
+    /// The same number of days starting on the day of the given instant.
+    pub fn starting_at(&self, start: DateTime<Utc>) -> Self {
+        let date = start.date();
+
+        Self(date, date + (self.end() - self.start()))
+    }
+}
 
 #[cfg_attr(feature = "serde_support", typetag::serde)]
 impl Period for WholeDays {
@@ -39,7 +76,11 @@ impl Period for WholeDays {
     }
 
     fn get_date_time_start(&self) -> DateTime<Utc> {
-        self.start().and_time(NaiveTime::from_hms(0, 0, 0)).unwrap()
+        self.start().and_time(NaiveTime::MIN).unwrap()
+    }
+
+    fn get_date_time_end(&self) -> DateTime<Utc> {
+        self.exclusive_end().and_time(NaiveTime::MIN).unwrap()
     }
 
     fn starts_before(&self, date: Date<Utc>) -> bool {
@@ -51,6 +92,23 @@ impl Period for WholeDays {
         Box::new(Self(date, date + total_duration))
     }
 
+    fn with_new_date_time_start(&self, start: DateTime<Utc>) -> Box<dyn Period> {
+        Box::new(self.starting_at(start))
+    }
+
+    fn with_range(&self, range: Range<DateTime<Utc>>) -> Box<dyn Period> {
+        let midnight = NaiveTime::MIN;
+
+        if range.start.time() != midnight || range.end.time() != midnight || range.is_empty() {
+            return Box::new(StartEnd(range.start, range.end));
+        }
+
+        Box::new(Self::from_exclusive_end(
+            range.start.date(),
+            range.end.date(),
+        ))
+    }
+
     fn as_weekdays(&self) -> (u32, u32) {
         (
             self.start().weekday().number_from_monday(),
@@ -68,7 +126,7 @@ impl Period for WholeDays {
 
     fn with_new_month(&self, month: u32) -> Date<Utc> {
         Date::from_utc(
-            NaiveDate::from_ymd(self.start().year(), month, self.start().day()),
+            NaiveDate::from_ymd_opt(self.start().year(), month, self.start().day()).unwrap(),
             Utc,
         )
     }
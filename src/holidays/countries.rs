@@ -0,0 +1,169 @@
+use super::{
+    Holiday,
+    HolidayRule::{EasterOffset, Fixed, NthWeekday},
+    Observance::{NearestWeekday, NextFreeWeekday},
+};
+use chrono::Weekday::{Mon, Thu};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Country {
+    /// Federal holidays.
+    UnitedStates,
+    /// Bank holidays of England and Wales.
+    UnitedKingdom,
+    /// Nationwide holidays.
+    Germany,
+    France,
+    Poland,
+}
+
+impl Country {
+    pub fn holidays(&self) -> Vec<Holiday> {
+        match self {
+            Self::UnitedStates => vec![
+                Holiday::new("New Year's Day", Fixed { month: 1, day: 1 }).observed(NearestWeekday),
+                Holiday::new(
+                    "Birthday of Martin Luther King, Jr.",
+                    NthWeekday {
+                        month: 1,
+                        weekday: Mon,
+                        nth: 3,
+                    },
+                ),
+                Holiday::new(
+                    "Washington's Birthday",
+                    NthWeekday {
+                        month: 2,
+                        weekday: Mon,
+                        nth: 3,
+                    },
+                ),
+                Holiday::new(
+                    "Memorial Day",
+                    NthWeekday {
+                        month: 5,
+                        weekday: Mon,
+                        nth: -1,
+                    },
+                ),
+                Holiday::new(
+                    "Juneteenth National Independence Day",
+                    Fixed { month: 6, day: 19 },
+                )
+                .observed(NearestWeekday)
+                .since(2021),
+                Holiday::new("Independence Day", Fixed { month: 7, day: 4 })
+                    .observed(NearestWeekday),
+                Holiday::new(
+                    "Labor Day",
+                    NthWeekday {
+                        month: 9,
+                        weekday: Mon,
+                        nth: 1,
+                    },
+                ),
+                Holiday::new(
+                    "Columbus Day",
+                    NthWeekday {
+                        month: 10,
+                        weekday: Mon,
+                        nth: 2,
+                    },
+                ),
+                Holiday::new("Veterans Day", Fixed { month: 11, day: 11 }).observed(NearestWeekday),
+                Holiday::new(
+                    "Thanksgiving Day",
+                    NthWeekday {
+                        month: 11,
+                        weekday: Thu,
+                        nth: 4,
+                    },
+                ),
+                Holiday::new("Christmas Day", Fixed { month: 12, day: 25 })
+                    .observed(NearestWeekday),
+            ],
+            Self::UnitedKingdom => vec![
+                Holiday::new("New Year's Day", Fixed { month: 1, day: 1 })
+                    .observed(NextFreeWeekday),
+                Holiday::new("Good Friday", EasterOffset(-2)),
+                Holiday::new("Easter Monday", EasterOffset(1)),
+                Holiday::new(
+                    "Early May bank holiday",
+                    NthWeekday {
+                        month: 5,
+                        weekday: Mon,
+                        nth: 1,
+                    },
+                ),
+                Holiday::new(
+                    "Spring bank holiday",
+                    NthWeekday {
+                        month: 5,
+                        weekday: Mon,
+                        nth: -1,
+                    },
+                ),
+                Holiday::new(
+                    "Summer bank holiday",
+                    NthWeekday {
+                        month: 8,
+                        weekday: Mon,
+                        nth: -1,
+                    },
+                ),
+                Holiday::new("Christmas Day", Fixed { month: 12, day: 25 })
+                    .observed(NextFreeWeekday),
+                Holiday::new("Boxing Day", Fixed { month: 12, day: 26 }).observed(NextFreeWeekday),
+            ],
+            Self::Germany => vec![
+                Holiday::new("Neujahr", Fixed { month: 1, day: 1 }),
+                Holiday::new("Karfreitag", EasterOffset(-2)),
+                Holiday::new("Ostermontag", EasterOffset(1)),
+                Holiday::new("Tag der Arbeit", Fixed { month: 5, day: 1 }),
+                Holiday::new("Christi Himmelfahrt", EasterOffset(39)),
+                Holiday::new("Pfingstmontag", EasterOffset(50)),
+                Holiday::new("Tag der Deutschen Einheit", Fixed { month: 10, day: 3 }),
+                Holiday::new("1. Weihnachtstag", Fixed { month: 12, day: 25 }),
+                Holiday::new("2. Weihnachtstag", Fixed { month: 12, day: 26 }),
+            ],
+            Self::France => vec![
+                Holiday::new("Jour de l'an", Fixed { month: 1, day: 1 }),
+                Holiday::new("Lundi de Pâques", EasterOffset(1)),
+                Holiday::new("Fête du Travail", Fixed { month: 5, day: 1 }),
+                Holiday::new("Victoire 1945", Fixed { month: 5, day: 8 }),
+                Holiday::new("Ascension", EasterOffset(39)),
+                Holiday::new("Lundi de Pentecôte", EasterOffset(50)),
+                Holiday::new("Fête nationale", Fixed { month: 7, day: 14 }),
+                Holiday::new("Assomption", Fixed { month: 8, day: 15 }),
+                Holiday::new("Toussaint", Fixed { month: 11, day: 1 }),
+                Holiday::new("Armistice 1918", Fixed { month: 11, day: 11 }),
+                Holiday::new("Noël", Fixed { month: 12, day: 25 }),
+            ],
+            Self::Poland => vec![
+                Holiday::new("Nowy Rok", Fixed { month: 1, day: 1 }),
+                Holiday::new("Święto Trzech Króli", Fixed { month: 1, day: 6 }).since(2011),
+                Holiday::new("Wielkanoc", EasterOffset(0)),
+                Holiday::new("Poniedziałek Wielkanocny", EasterOffset(1)),
+                Holiday::new("Święto Pracy", Fixed { month: 5, day: 1 }),
+                Holiday::new("Święto Konstytucji 3 Maja", Fixed { month: 5, day: 3 }),
+                Holiday::new("Zielone Świątki", EasterOffset(49)),
+                Holiday::new("Boże Ciało", EasterOffset(60)),
+                Holiday::new(
+                    "Wniebowzięcie Najświętszej Maryi Panny",
+                    Fixed { month: 8, day: 15 },
+                ),
+                Holiday::new("Wszystkich Świętych", Fixed { month: 11, day: 1 }),
+                Holiday::new(
+                    "Narodowe Święto Niepodległości",
+                    Fixed { month: 11, day: 11 },
+                ),
+                Holiday::new("Wigilia Bożego Narodzenia", Fixed { month: 12, day: 24 }).since(2025),
+                Holiday::new("Boże Narodzenie", Fixed { month: 12, day: 25 }),
+                Holiday::new(
+                    "Drugi dzień Bożego Narodzenia",
+                    Fixed { month: 12, day: 26 },
+                ),
+            ],
+        }
+    }
+}
@@ -0,0 +1,207 @@
+/*!
+   Rule-based public holidays.
+
+   Holidays are generated offline and deterministically: the same country and years always
+   yield events of the same names, dates and UUIDs.
+
+   ```rust
+   use dateless::{holidays::{self, Country}, prelude::*};
+   use chrono::{TimeZone, Utc};
+
+   let calendar = holidays::calendar(Country::UnitedStates, 2021..=2022);
+
+   assert_eq!(calendar.day(Utc.ymd(2021, 11, 25))[0].name, "Thanksgiving Day");
+   ```
+*/
+
+mod countries;
+
+use super::{calendar::Calendar, computus::western_easter, event::EventPartial};
+use chrono::{Date, Datelike, Duration, NaiveDate, Utc, Weekday};
+use std::ops::RangeInclusive;
+use uuid::Uuid;
+
+pub use countries::Country;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HolidayRule {
+    /// The same day every year.
+    Fixed { month: u32, day: u32 },
+    /// The `nth` weekday of the month; negative values count from the end of the month,
+    /// e.g. `-1` is the last one.
+    NthWeekday {
+        month: u32,
+        weekday: Weekday,
+        nth: i32,
+    },
+    /// Number of days after Western Easter Sunday.
+    EasterOffset(i64),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Observance {
+    /// Observed on the day it falls on.
+    Actual,
+    /// Observed on Friday when falling on Saturday and on Monday when falling on Sunday.
+    NearestWeekday,
+    /// Observed on the first following weekday not taken by another holiday when falling on
+    /// a weekend.
+    NextFreeWeekday,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Holiday {
+    pub name: &'static str,
+    pub rule: HolidayRule,
+    pub observance: Observance,
+    /// First year the holiday is observed in, if it has not always been.
+    pub since: Option<i32>,
+}
+
+impl Holiday {
+    pub const fn new(name: &'static str, rule: HolidayRule) -> Self {
+        Self {
+            name,
+            rule,
+            observance: Observance::Actual,
+            since: None,
+        }
+    }
+
+    pub const fn observed(self, observance: Observance) -> Self {
+        Self { observance, ..self }
+    }
+
+    pub const fn since(self, year: i32) -> Self {
+        Self {
+            since: Some(year),
+            ..self
+        }
+    }
+
+    /**
+       Returns the day the holiday falls on in the given year, disregarding its observance.
+    */
+    pub fn date_in(&self, year: i32) -> Option<Date<Utc>> {
+        if matches!(self.since, Some(since) if year < since) {
+            return None;
+        }
+
+        let date = match self.rule {
+            HolidayRule::Fixed { month, day } => NaiveDate::from_ymd_opt(year, month, day)?,
+            HolidayRule::NthWeekday {
+                month,
+                weekday,
+                nth,
+            } => nth_weekday(year, month, weekday, nth)?,
+            HolidayRule::EasterOffset(days) => western_easter(year) + Duration::days(days),
+        };
+
+        Some(Date::from_utc(date, Utc))
+    }
+}
+
+fn nth_weekday(year: i32, month: u32, weekday: Weekday, nth: i32) -> Option<NaiveDate> {
+    if nth > 0 {
+        let first = NaiveDate::from_ymd_opt(year, month, 1)?;
+        let offset = (7 + weekday.num_days_from_monday() as i64
+            - first.weekday().num_days_from_monday() as i64)
+            % 7;
+        let date = first + Duration::days(offset + 7 * (nth as i64 - 1));
+
+        Some(date).filter(|date| date.month() == month)
+    } else if nth < 0 {
+        let (next_year, next_month) = if month == 12 {
+            (year + 1, 1)
+        } else {
+            (year, month + 1)
+        };
+        let last = NaiveDate::from_ymd_opt(next_year, next_month, 1)?.pred_opt()?;
+        let offset = (7 + last.weekday().num_days_from_monday() as i64
+            - weekday.num_days_from_monday() as i64)
+            % 7;
+        let date = last - Duration::days(offset + 7 * (-nth as i64 - 1));
+
+        Some(date).filter(|date| date.month() == month)
+    } else {
+        None
+    }
+}
+
+fn is_weekend(date: Date<Utc>) -> bool {
+    matches!(date.weekday(), Weekday::Sat | Weekday::Sun)
+}
+
+/**
+   Returns the observed days of the holidays in the given year, along with the holidays.
+*/
+pub fn observed_in(holidays: &[Holiday], year: i32) -> Vec<(Date<Utc>, &Holiday)> {
+    let actual_days: Vec<_> = holidays
+        .iter()
+        .filter_map(|holiday| Some((holiday.date_in(year)?, holiday)))
+        .collect();
+
+    let mut taken_days: Vec<_> = actual_days
+        .iter()
+        .map(|(date, _)| *date)
+        .filter(|date| !is_weekend(*date))
+        .collect();
+
+    actual_days
+        .into_iter()
+        .map(|(date, holiday)| {
+            let observed = match holiday.observance {
+                Observance::Actual => date,
+                Observance::NearestWeekday => match date.weekday() {
+                    Weekday::Sat => date - Duration::days(1),
+                    Weekday::Sun => date + Duration::days(1),
+                    _ => date,
+                },
+                Observance::NextFreeWeekday if is_weekend(date) => {
+                    let mut observed = date;
+
+                    while is_weekend(observed) || taken_days.contains(&observed) {
+                        observed += Duration::days(1);
+                    }
+
+                    taken_days.push(observed);
+                    observed
+                }
+                Observance::NextFreeWeekday => date,
+            };
+
+            (observed, holiday)
+        })
+        .collect()
+}
+
+/**
+   Generates a `Calendar` of whole day events observing the country's public holidays in the
+   given years. Holidays observed on another day than they fall on are suffixed with
+   "(observed)".
+*/
+pub fn calendar(country: Country, years: RangeInclusive<i32>) -> Calendar {
+    let holidays = country.holidays();
+    let mut calendar = Calendar::new();
+
+    for year in years {
+        for (date, holiday) in observed_in(&holidays, year) {
+            let name = if Some(date) == holiday.date_in(year) {
+                holiday.name.to_string()
+            } else {
+                format!("{} (observed)", holiday.name)
+            };
+
+            let mut event = EventPartial::new(&name).whole_day(date).complete();
+
+            event.uuid = Uuid::new_v5(
+                &Uuid::NAMESPACE_URL,
+                format!("dateless:holidays/{:?}/{}/{}", country, year, name).as_bytes(),
+            );
+
+            calendar.add_event(event);
+        }
+    }
+
+    calendar
+}
@@ -0,0 +1,107 @@
+use super::event::Event;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+#[cfg(feature = "serde_support")]
+use serde::{Deserialize, Serialize};
+
+/**
+   Reversible mutation of a `Calendar`, holding whatever is needed to undo it. Events are
+   addressed by their index in the calendar, which stays valid as long as operations are
+   undone and redone in the order of the journal.
+
+   Undoing or redoing an update or an exception edit modifies the event anew, so its
+   `sequence` keeps increasing.
+*/
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+pub enum Operation {
+    Insert {
+        index: usize,
+        event: Event,
+    },
+    Remove {
+        index: usize,
+        event: Event,
+    },
+    Update {
+        from: Box<Event>,
+        to: Box<Event>,
+    },
+    AddException {
+        uuid: Uuid,
+        #[cfg_attr(feature = "serde_support", serde(with = "crate::serde::timestamp"))]
+        at: DateTime<Utc>,
+    },
+    RemoveException {
+        uuid: Uuid,
+        #[cfg_attr(feature = "serde_support", serde(with = "crate::serde::timestamp"))]
+        at: DateTime<Utc>,
+    },
+    /// Operations done and undone at once, e.g. by `Calendar::apply`.
+    Batch(Vec<Operation>),
+}
+
+impl Operation {
+    /// The operation reverting this one.
+    pub fn inverse(&self) -> Operation {
+        match self.clone() {
+            Operation::Insert { index, event } => Operation::Remove { index, event },
+            Operation::Remove { index, event } => Operation::Insert { index, event },
+            Operation::Update { from, to } => Operation::Update { from: to, to: from },
+            Operation::AddException { uuid, at } => Operation::RemoveException { uuid, at },
+            Operation::RemoveException { uuid, at } => Operation::AddException { uuid, at },
+            Operation::Batch(operations) => {
+                Operation::Batch(operations.iter().rev().map(Operation::inverse).collect())
+            }
+        }
+    }
+}
+
+/**
+   Log of the operations done on a calendar, to undo and redo them. Doing a new operation
+   discards the undone ones.
+
+   Serialized, its instants are RFC 3339 strings, down to the nanosecond.
+*/
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+pub struct Journal {
+    done: Vec<Operation>,
+    undone: Vec<Operation>,
+}
+
+impl Journal {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Operations which can be undone, the most recent last.
+    pub fn done(&self) -> &[Operation] {
+        &self.done
+    }
+
+    /// Operations which can be redone, the most recently undone last.
+    pub fn undone(&self) -> &[Operation] {
+        &self.undone
+    }
+
+    pub(crate) fn record(&mut self, operation: Operation) {
+        self.done.push(operation);
+        self.undone.clear();
+    }
+
+    pub(crate) fn undo(&mut self) -> Option<Operation> {
+        let operation = self.done.pop()?;
+
+        self.undone.push(operation.clone());
+        Some(operation.inverse())
+    }
+
+    pub(crate) fn redo(&mut self) -> Option<Operation> {
+        let operation = self.undone.pop()?;
+
+        self.done.push(operation.clone());
+        Some(operation)
+    }
+}
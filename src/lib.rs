@@ -12,7 +12,7 @@ First, add `dateless` as a dependency in your project's Cargo.toml:
 
 ```toml
 [dependencies]
-dateless = "0.3.1"
+dateless = "0.4.0"
 ```
 
 And then, you can start with creating a calendar:
@@ -88,10 +88,21 @@ It prints to `stdout`:
 #[macro_use]
 mod codegen;
 
+#[cfg(feature = "caldav")]
+pub mod caldav;
 mod calendar;
 mod chrono;
+mod computus;
 mod event;
+pub mod holidays;
+mod journal;
 pub mod prelude;
+pub mod store;
+mod week;
+mod working_days;
+
+#[cfg(feature = "serde_support")]
+pub mod schema;
 
 #[cfg(test)]
 mod test;
@@ -99,5 +110,12 @@ mod test;
 #[cfg(feature = "serde_support")]
 mod serde;
 
-pub use calendar::Calendar;
-pub use event::{Cyclicity, Event, EventOccurrence, EventPartial, Period};
+pub use calendar::{Calendar, TimestampFormat};
+pub use event::{
+    Alarm, AlarmAnchor, AlarmOccurrence, ChangeSet, Cyclicity, English, Event, EventChange,
+    EventOccurrence, EventPartial, FieldChange, Localization, ParseError, Period, PeriodKind,
+    Recurrence,
+};
+pub use journal::{Journal, Operation};
+pub use week::WeekStart;
+pub use working_days::{BusinessDayAdjustment, WorkingDays};
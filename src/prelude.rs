@@ -1,2 +1,5 @@
 pub use super::calendar::*;
 pub use super::event::*;
+pub use super::journal::*;
+pub use super::week::*;
+pub use super::working_days::*;
@@ -0,0 +1,462 @@
+/*!
+   Versioned wire format of calendars.
+
+   A serialized `Calendar` is an object holding the `version` of the format it was written
+   with, the representation of its instants (`timestamps`), the first day of its weeks
+   (`week_start`) and its `events`:
+
+   ```json
+   {
+       "version": 4,
+       "timestamps": "Seconds",
+       "week_start": "Mon",
+       "events": [
+           {
+               "uuid": "5f3c1c5e-8d0e-4b8e-9c4e-8c0b1e7e6a10",
+               "name": "Standup",
+               "description": null,
+               "cyclicity": { "type": "DailyCycle" },
+               "exceptions": [1620205200],
+               "period": { "type": "StartEnd", "value": [1620032400, 1620033300] },
+               "alarms": [{ "anchor": "Start", "offset": -900, "at_time": null }],
+               "created": 1619964000,
+               "last_modified": 1620205300,
+               "sequence": 2
+           }
+       ]
+   }
+   ```
+
+   Periods and cyclicities are tagged with their `type`. Fields of struct-like ones sit next
+   to the tag, the others are wrapped in `value`. Durations are signed numbers of seconds and
+   dates are `%Y-%m-%d` strings.
+
+   Instants follow the calendar's [`TimestampFormat`]: Unix seconds (`Seconds`), Unix
+   milliseconds (`Milliseconds`) or RFC 3339 strings (`Rfc3339`). Since events are read in the
+   format declared before them, payloads listing `timestamps` after `events` are rejected.
+   Likewise, tagged periods and cyclicities are expected to start with their `type`, as they
+   are written, and ones which don't can only hold RFC 3339 strings.
+
+   ```rust
+   use dateless::prelude::*;
+   use chrono::{Duration, TimeZone, Utc};
+
+   let start = Utc.ymd(2021, 5, 3).and_hms_milli(9, 0, 0, 250);
+   let mut calendar = Calendar::new().with_timestamp_format(TimestampFormat::Milliseconds);
+
+   calendar.add_event(
+       EventPartial::new(String::from("Standup"))
+           .from_to(start, start + Duration::minutes(15))
+           .complete(),
+   );
+
+   let json = serde_json::to_string(&calendar).unwrap();
+
+   assert!(json.contains("1620032400250"));
+   ```
+
+   The JSON Schema of the format is kept in `schema/calendar.schema.json` and generated from
+   the types with [`json_schema`] (behind the `json_schema` feature).
+
+   # Binary formats
+
+   Formats which are not human-readable, such as bincode or postcard, cannot read typetag's
+   tagged maps. There, periods and cyclicities are written as closed enums of the built-in
+   types, in the order listed by the JSON Schema, and custom ones fail to serialize. Fields are
+   positional, so fields added by a version are only read from payloads of that version on.
+
+   # Versions
+
+   - `4`: adds the `week_start` field. Payloads without it start weeks on Monday.
+   - `3`: adds the `created`, `last_modified` and `sequence` fields of events. Events
+     without them were created and last modified at the Unix epoch, with a `sequence` of 0.
+   - `2`: adds the `timestamps` field. Payloads without it use `Seconds`.
+   - `1`: adds the `version` field. `exceptions` and `alarms` of events may be omitted.
+   - `0`: payloads without a `version` field, written before it was introduced.
+
+   No version changed the meaning of a field, so older payloads are read as they are: missing
+   fields take the defaults above and positional payloads are read in the shape of their
+   version. Payloads of versions newer than [`CURRENT_VERSION`] are rejected. The shape of
+   version `0` is kept in `schema/fixtures/calendar-v0.json`.
+*/
+
+use super::{
+    calendar::{Calendar, TimestampFormat},
+    event::{Event, LegacyEvent},
+    serde::timestamp::Formatted,
+    week::WeekStart,
+};
+use serde::{
+    de::{DeserializeSeed, Deserializer, Error, IgnoredAny, MapAccess, SeqAccess, Visitor},
+    ser::{SerializeStruct, Serializer},
+    Deserialize, Serialize,
+};
+use std::{fmt, marker::PhantomData};
+
+pub const CURRENT_VERSION: u32 = 4;
+
+const FIELDS: &[&str] = &["version", "timestamps", "week_start", "events"];
+
+impl Serialize for Calendar {
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut calendar = s.serialize_struct("Calendar", FIELDS.len())?;
+
+        calendar.serialize_field("version", &CURRENT_VERSION)?;
+        calendar.serialize_field("timestamps", &self.timestamp_format)?;
+        calendar.serialize_field("week_start", &self.week_start)?;
+        calendar.serialize_field(
+            "events",
+            &Formatted::new(
+                &self.events as &dyn erased_serde::Serialize,
+                self.timestamp_format,
+            ),
+        )?;
+        calendar.end()
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "lowercase")]
+enum Field {
+    Version,
+    Timestamps,
+    #[serde(rename = "week_start")]
+    WeekStart,
+    Events,
+    #[serde(other)]
+    Unknown,
+}
+
+/**
+   Events of a calendar, deserialized through a trait object, as they are serialized. Working
+   days hold a calendar of holidays, so each calendar nested in an event would otherwise wrap
+   the (de)serializer in `Formatted` once more, and their code would never stop being
+   generated.
+*/
+struct Events<T>(PhantomData<T>);
+
+impl<'de, T> DeserializeSeed<'de> for Events<T>
+where
+    T: Deserialize<'de>,
+{
+    type Value = T;
+
+    fn deserialize<D>(self, d: D) -> Result<T, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        erased_serde::deserialize(&mut <dyn erased_serde::Deserializer>::erase(d))
+            .map_err(D::Error::custom)
+    }
+}
+
+struct CalendarVisitor;
+
+impl CalendarVisitor {
+    fn finish<E: Error>(
+        version: u32,
+        timestamp_format: TimestampFormat,
+        week_start: WeekStart,
+        events: Option<Vec<Event>>,
+    ) -> Result<Calendar, E> {
+        if version > CURRENT_VERSION {
+            return Err(E::custom(format!(
+                "unsupported calendar version {}, the latest known is {}",
+                version, CURRENT_VERSION
+            )));
+        }
+
+        Ok(Calendar {
+            events: events.ok_or_else(|| E::missing_field("events"))?,
+            timestamp_format,
+            week_start,
+            journal: None,
+        })
+    }
+}
+
+impl<'de> Visitor<'de> for CalendarVisitor {
+    type Value = Calendar;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a calendar")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let version = seq
+            .next_element()?
+            .ok_or_else(|| A::Error::invalid_length(0, &self))?;
+        let timestamp_format = match version {
+            0..=1 => TimestampFormat::default(),
+            _ => seq
+                .next_element()?
+                .ok_or_else(|| A::Error::invalid_length(1, &self))?,
+        };
+        let week_start = match version {
+            0..=3 => WeekStart::default(),
+            _ => seq
+                .next_element()?
+                .ok_or_else(|| A::Error::invalid_length(2, &self))?,
+        };
+        let events = match version {
+            0..=2 => seq
+                .next_element_seed(Formatted::new(
+                    Events(PhantomData::<Vec<LegacyEvent>>),
+                    timestamp_format,
+                ))?
+                .map(|events| events.into_iter().map(Event::from).collect()),
+            _ => seq.next_element_seed(Formatted::new(Events(PhantomData), timestamp_format))?,
+        };
+
+        Self::finish(version, timestamp_format, week_start, events)
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut version = 0;
+        let mut timestamp_format = TimestampFormat::default();
+        let mut week_start = WeekStart::default();
+        let mut events = None;
+
+        while let Some(field) = map.next_key()? {
+            match field {
+                Field::Version => version = map.next_value()?,
+                // Events already read were read in the default format.
+                Field::Timestamps if events.is_some() => {
+                    return Err(A::Error::custom("`timestamps` must come before `events`"));
+                }
+                Field::Timestamps => timestamp_format = map.next_value()?,
+                Field::WeekStart => week_start = map.next_value()?,
+                Field::Events => {
+                    events = Some(
+                        map.next_value_seed(Formatted::new(Events(PhantomData), timestamp_format))?,
+                    )
+                }
+                Field::Unknown => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+
+        Self::finish(version, timestamp_format, week_start, events)
+    }
+}
+
+impl<'de> Deserialize<'de> for Calendar {
+    fn deserialize<D>(d: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        d.deserialize_struct("Calendar", FIELDS, CalendarVisitor)
+    }
+}
+
+#[cfg(feature = "json_schema")]
+mod json {
+    use super::{Calendar, TimestampFormat, WeekStart, CURRENT_VERSION};
+    use crate::event::*;
+    use schemars::{
+        gen::SchemaGenerator,
+        schema::{InstanceType, ObjectValidation, RootSchema, Schema, SchemaObject},
+        JsonSchema,
+    };
+
+    /**
+       Generates the JSON Schema of serialized calendars.
+    */
+    pub fn json_schema() -> RootSchema {
+        let mut schema = schemars::schema_for!(Calendar);
+
+        // The default UUID of events is random, so it is documented rather than embedded.
+        if let Some(Schema::Object(event)) = schema.definitions.get_mut("Event") {
+            if let Some(Schema::Object(uuid)) = event.object().properties.get_mut("uuid") {
+                let metadata = uuid.metadata();
+
+                metadata.default = None;
+                metadata.description = Some("Random (version 4) when omitted.".into());
+            }
+        }
+
+        schema
+    }
+
+    fn object(properties: Vec<(&str, Schema)>, required: &[&str]) -> SchemaObject {
+        SchemaObject {
+            instance_type: Some(InstanceType::Object.into()),
+            object: Some(Box::new(ObjectValidation {
+                properties: properties
+                    .into_iter()
+                    .map(|(name, schema)| (name.to_string(), schema))
+                    .collect(),
+                required: required.iter().map(|name| name.to_string()).collect(),
+                ..Default::default()
+            })),
+            ..Default::default()
+        }
+    }
+
+    fn constant(value: &str) -> Schema {
+        SchemaObject {
+            const_value: Some(value.into()),
+            ..Default::default()
+        }
+        .into()
+    }
+
+    /// Schema of a value serialized through typetag's internally tagged registry.
+    fn tagged<T: JsonSchema>(gen: &mut SchemaGenerator, tag: &str) -> Schema {
+        let mut schema = T::json_schema(gen).into_object();
+
+        // Newtypes around structs refer to their definition, whose fields typetag flattens.
+        if schema.is_ref() {
+            if let Some(Schema::Object(definition)) = gen.dereference(&schema.clone().into()) {
+                schema = definition.clone();
+            }
+        }
+
+        if schema.instance_type == Some(InstanceType::Null.into()) {
+            return object(vec![("type", constant(tag))], &["type"]).into();
+        }
+
+        if let Some(validation) = schema.object.as_mut() {
+            validation.properties.insert("type".into(), constant(tag));
+            validation.required.insert("type".into());
+
+            return schema.into();
+        }
+
+        object(
+            vec![("type", constant(tag)), ("value", schema.into())],
+            &["type", "value"],
+        )
+        .into()
+    }
+
+    fn one_of(variants: Vec<Schema>) -> Schema {
+        let mut schema = SchemaObject::default();
+
+        schema.subschemas().one_of = Some(variants);
+        schema.into()
+    }
+
+    impl JsonSchema for Calendar {
+        fn schema_name() -> String {
+            "Calendar".into()
+        }
+
+        fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+            let version = SchemaObject {
+                instance_type: Some(InstanceType::Integer.into()),
+                const_value: Some(CURRENT_VERSION.into()),
+                ..Default::default()
+            };
+
+            object(
+                vec![
+                    ("version", version.into()),
+                    ("timestamps", gen.subschema_for::<TimestampFormat>()),
+                    ("week_start", gen.subschema_for::<WeekStart>()),
+                    ("events", gen.subschema_for::<Vec<Event>>()),
+                ],
+                &["events"],
+            )
+            .into()
+        }
+    }
+
+    /// Instant in any of the `TimestampFormat`s.
+    pub struct Timestamp;
+
+    impl JsonSchema for Timestamp {
+        fn schema_name() -> String {
+            "Timestamp".into()
+        }
+
+        fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+            let mut schema = SchemaObject::default();
+
+            schema.subschemas().any_of = Some(vec![
+                gen.subschema_for::<i64>(),
+                gen.subschema_for::<chrono::DateTime<chrono::Utc>>(),
+            ]);
+            schema.into()
+        }
+    }
+
+    impl JsonSchema for PeriodDef {
+        fn schema_name() -> String {
+            "Period".into()
+        }
+
+        fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+            one_of(vec![
+                tagged::<StartEnd>(gen, "StartEnd"),
+                tagged::<WholeDays>(gen, "WholeDays"),
+                tagged::<Moment>(gen, "Moment"),
+                tagged::<OpenEnded>(gen, "OpenEnded"),
+                tagged::<FloatingTime>(gen, "FloatingTime"),
+            ])
+        }
+    }
+
+    impl JsonSchema for PeriodKind {
+        fn schema_name() -> String {
+            PeriodDef::schema_name()
+        }
+
+        fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+            PeriodDef::json_schema(gen)
+        }
+    }
+
+    impl JsonSchema for Recurrence {
+        fn schema_name() -> String {
+            <Box<dyn Cyclicity>>::schema_name()
+        }
+
+        fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+            <Box<dyn Cyclicity>>::json_schema(gen)
+        }
+    }
+
+    impl JsonSchema for Box<dyn Cyclicity> {
+        fn schema_name() -> String {
+            "Cyclicity".into()
+        }
+
+        fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+            one_of(vec![
+                tagged::<DailyCycle>(gen, "DailyCycle"),
+                tagged::<WeeklyCycle>(gen, "WeeklyCycle"),
+                tagged::<MonthlyCycle>(gen, "MonthlyCycle"),
+                tagged::<AnnualCycle>(gen, "AnnualCycle"),
+                tagged::<HourlyCycle>(gen, "HourlyCycle"),
+                tagged::<MinutelyCycle>(gen, "MinutelyCycle"),
+                tagged::<AnyOf>(gen, "AnyOf"),
+                tagged::<AllOf>(gen, "AllOf"),
+                tagged::<Except>(gen, "Except"),
+                tagged::<Shifted>(gen, "Shifted"),
+                tagged::<OnDates>(gen, "OnDates"),
+                tagged::<WorkingDayCycle>(gen, "WorkingDayCycle"),
+                tagged::<LastWorkingDayOfMonth>(gen, "LastWorkingDayOfMonth"),
+                tagged::<Adjusted>(gen, "Adjusted"),
+                tagged::<EasterOffsetCycle>(gen, "EasterOffsetCycle"),
+                tagged::<WeekOfYearCycle>(gen, "WeekOfYearCycle"),
+            ])
+        }
+    }
+}
+
+#[cfg(feature = "json_schema")]
+pub use json::json_schema;
+
+#[cfg(feature = "json_schema")]
+pub(crate) use json::Timestamp;
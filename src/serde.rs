@@ -1,4 +1,4 @@
-use chrono::{Date, NaiveDate, Utc};
+use chrono::{Date, Duration, NaiveDate, Utc};
 use serde::{de::Deserializer, ser::Serializer, Deserialize};
 use std::str::FromStr;
 
@@ -21,3 +21,173 @@ where
 {
     s.serialize_str(&date.naive_utc().format("%Y-%m-%d").to_string())
 }
+
+pub fn from_seconds_into_duration<'de, D>(d: D) -> Result<Duration, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    i64::deserialize(d).map(Duration::seconds)
+}
+
+pub fn from_duration_into_seconds<S>(duration: &Duration, s: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    s.serialize_i64(duration.num_seconds())
+}
+
+pub mod timestamp;
+
+/**
+   Generates (de)serialization of boxed trait objects: through typetag in human-readable
+   formats, as a closed enum of the built-in types in the other ones, whose deserializers
+   cannot tell the type of values beforehand. `with_collections` adds the `vec` module for
+   fields holding several of them.
+*/
+macro_rules! closed_representation {
+    ($trait:ident, [$($kind:ident),* $(,)?]) => {
+        use crate::event::*;
+        use serde::{
+            de::Deserializer,
+            ser::{Error, Serializer},
+            Deserialize, Serialize,
+        };
+
+        #[derive(Serialize)]
+        enum Borrowed<'a> {
+            $($kind(&'a $kind),)*
+        }
+
+        #[derive(Deserialize)]
+        enum Owned {
+            $($kind($kind),)*
+        }
+
+        pub fn serialize_dyn<S>(value: &dyn $trait, s: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            if s.is_human_readable() {
+                return value.serialize(s);
+            }
+
+            let any = value.as_any();
+
+            $(
+                if let Some(kind) = any.downcast_ref::<$kind>() {
+                    return Borrowed::$kind(kind).serialize(s);
+                }
+            )*
+
+            Err(S::Error::custom(format!(
+                "`{}` is not built-in, so it can only be serialized in human-readable formats",
+                value.typetag_name()
+            )))
+        }
+
+        fn deserialize_boxed<'de, D>(d: D) -> Result<Box<dyn $trait>, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            if d.is_human_readable() {
+                return Box::<dyn $trait>::deserialize(d);
+            }
+
+            Ok(match Owned::deserialize(d)? {
+                $(Owned::$kind(kind) => Box::new(kind),)*
+            })
+        }
+
+        #[allow(clippy::borrowed_box)]
+        pub fn serialize<S>(value: &Box<dyn $trait>, s: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            serialize_dyn(value.as_ref(), s)
+        }
+
+        pub fn deserialize<'de, D>(d: D) -> Result<Box<dyn $trait>, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserialize_boxed(d)
+        }
+    };
+    ($trait:ident, [$($kind:ident),* $(,)?], with_collections) => {
+        closed_representation!($trait, [$($kind),*]);
+
+        struct Wrapper<'a>(&'a dyn $trait);
+
+        impl Serialize for Wrapper<'_> {
+            fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                serialize_dyn(self.0, s)
+            }
+        }
+
+        struct BoxWrapper(Box<dyn $trait>);
+
+        impl<'de> Deserialize<'de> for BoxWrapper {
+            fn deserialize<D>(d: D) -> Result<Self, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                deserialize_boxed(d).map(BoxWrapper)
+            }
+        }
+
+        pub mod vec {
+            use super::*;
+
+            pub fn serialize<S>(values: &[Box<dyn $trait>], s: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                s.collect_seq(values.iter().map(|value| Wrapper(value.as_ref())))
+            }
+
+            pub fn deserialize<'de, D>(d: D) -> Result<Vec<Box<dyn $trait>>, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                let wrappers = Vec::<BoxWrapper>::deserialize(d)?;
+
+                Ok(wrappers.into_iter().map(|wrapper| wrapper.0).collect())
+            }
+        }
+    };
+}
+
+pub mod period {
+    closed_representation!(
+        Period,
+        [StartEnd, WholeDays, Moment, OpenEnded, FloatingTime]
+    );
+}
+
+pub mod cyclicity {
+    closed_representation!(
+        Cyclicity,
+        [
+            DailyCycle,
+            WeeklyCycle,
+            MonthlyCycle,
+            AnnualCycle,
+            HourlyCycle,
+            MinutelyCycle,
+            AnyOf,
+            AllOf,
+            Except,
+            Shifted,
+            OnDates,
+            WorkingDayCycle,
+            LastWorkingDayOfMonth,
+            Adjusted,
+            EasterOffsetCycle,
+            WeekOfYearCycle,
+        ],
+        with_collections
+    );
+}
@@ -0,0 +1,868 @@
+/*!
+   Instants, serialized in the `TimestampFormat` of the calendar holding them and as RFC 3339
+   strings, down to the nanosecond, outside of one.
+
+   The format is carried by [`Formatted`], which wraps the (de)serializer of a calendar's
+   events and every one it hands down. Instants name themselves after [`NAME`] so that it can
+   write and read them in its format, while other (de)serializers see through the name.
+*/
+
+use crate::calendar::TimestampFormat;
+use chrono::{DateTime, SecondsFormat, TimeZone, Utc};
+use serde::{
+    de::{
+        self, DeserializeSeed, Deserializer, EnumAccess, IntoDeserializer, MapAccess, SeqAccess,
+        VariantAccess, Visitor,
+    },
+    ser::{self, Impossible, Serialize, Serializer},
+};
+use std::{convert::TryFrom, fmt};
+
+/// Name of the newtype instants are serialized as.
+const NAME: &str = "$dateless::Timestamp";
+
+fn to_rfc3339(instant: DateTime<Utc>) -> String {
+    instant.to_rfc3339_opts(SecondsFormat::AutoSi, true)
+}
+
+pub fn serialize<S>(instant: &DateTime<Utc>, s: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    s.serialize_newtype_struct(NAME, &Payload(*instant))
+}
+
+pub fn deserialize<'de, D>(d: D) -> Result<DateTime<Utc>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    d.deserialize_newtype_struct(NAME, TimestampVisitor { renamed: false })
+}
+
+/**
+   Content of the `NAME` newtype. Typetag drops the names of the newtypes it tags, e.g.
+   `Moment`, along with the ones inside of them, but hands optional values over as they are,
+   so in human-readable formats instants name themselves once more inside of one.
+*/
+struct Payload(DateTime<Utc>);
+
+impl Serialize for Payload {
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if s.is_human_readable() {
+            s.serialize_some(&Renamed(self.0))
+        } else {
+            s.serialize_str(&to_rfc3339(self.0))
+        }
+    }
+}
+
+struct Renamed(DateTime<Utc>);
+
+impl Serialize for Renamed {
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        s.serialize_newtype_struct(NAME, &to_rfc3339(self.0))
+    }
+}
+
+/// Reads what `serialize` writes, `renamed` once past the optional value.
+struct TimestampVisitor {
+    renamed: bool,
+}
+
+impl<'de> Visitor<'de> for TimestampVisitor {
+    type Value = DateTime<Utc>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an RFC 3339 date and time")
+    }
+
+    fn visit_newtype_struct<D>(self, d: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        if d.is_human_readable() && !self.renamed {
+            d.deserialize_option(self)
+        } else {
+            d.deserialize_str(self)
+        }
+    }
+
+    fn visit_some<D>(self, d: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        d.deserialize_newtype_struct(NAME, TimestampVisitor { renamed: true })
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        DateTime::parse_from_rfc3339(value)
+            .map(|instant| instant.with_timezone(&Utc))
+            .map_err(E::custom)
+    }
+}
+
+/// Reads Unix timestamps, in milliseconds if `millis` and in seconds otherwise.
+struct UnixVisitor {
+    millis: bool,
+}
+
+impl<'de> Visitor<'de> for UnixVisitor {
+    type Value = DateTime<Utc>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a Unix timestamp")
+    }
+
+    fn visit_i64<E>(self, value: i64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        let instant = match self.millis {
+            true => Utc.timestamp_millis_opt(value),
+            false => Utc.timestamp_opt(value, 0),
+        };
+
+        instant
+            .single()
+            .ok_or_else(|| E::custom(format!("timestamp {} is out of range", value)))
+    }
+
+    fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        match i64::try_from(value) {
+            Ok(value) => self.visit_i64(value),
+            Err(_) => Err(E::custom(format!("timestamp {} is out of range", value))),
+        }
+    }
+}
+
+/// Lists of instants, each (de)serialized as above.
+pub mod vec {
+    use crate::chrono::DateTimeDef;
+    use chrono::{DateTime, Utc};
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S>(instants: &[DateTime<Utc>], s: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        s.collect_seq(instants.iter().copied().map(DateTimeDef))
+    }
+
+    pub fn deserialize<'de, D>(d: D) -> Result<Vec<DateTime<Utc>>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let instants = Vec::<DateTimeDef>::deserialize(d)?;
+
+        Ok(instants.into_iter().map(|instant| instant.0).collect())
+    }
+}
+
+/**
+   Serde value, (de)serializer or one of their parts, whose instants are (de)serialized in the
+   given format. Wrapping a value serializes it with a wrapped serializer, wrapping a
+   `DeserializeSeed`, e.g. `PhantomData<T>`, deserializes it with a wrapped deserializer.
+*/
+pub struct Formatted<T> {
+    inner: T,
+    format: TimestampFormat,
+}
+
+impl<T> Formatted<T> {
+    pub fn new(inner: T, format: TimestampFormat) -> Self {
+        Self { inner, format }
+    }
+
+    fn wrap<U>(&self, inner: U) -> Formatted<U> {
+        Formatted::new(inner, self.format)
+    }
+}
+
+impl<T> Serialize for Formatted<&T>
+where
+    T: ?Sized + Serialize,
+{
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.inner.serialize(self.wrap(s))
+    }
+}
+
+macro_rules! forward_serialize {
+    ($($method:ident($ty:ty)),* $(,)?) => {
+        $(
+            fn $method(self, value: $ty) -> Result<S::Ok, S::Error> {
+                self.inner.$method(value)
+            }
+        )*
+    };
+}
+
+impl<S> Serializer for Formatted<S>
+where
+    S: Serializer,
+{
+    type Ok = S::Ok;
+    type Error = S::Error;
+    type SerializeSeq = Formatted<S::SerializeSeq>;
+    type SerializeTuple = Formatted<S::SerializeTuple>;
+    type SerializeTupleStruct = Formatted<S::SerializeTupleStruct>;
+    type SerializeTupleVariant = Formatted<S::SerializeTupleVariant>;
+    type SerializeMap = Formatted<S::SerializeMap>;
+    type SerializeStruct = Formatted<S::SerializeStruct>;
+    type SerializeStructVariant = Formatted<S::SerializeStructVariant>;
+
+    forward_serialize!(
+        serialize_bool(bool),
+        serialize_i8(i8),
+        serialize_i16(i16),
+        serialize_i32(i32),
+        serialize_i64(i64),
+        serialize_i128(i128),
+        serialize_u8(u8),
+        serialize_u16(u16),
+        serialize_u32(u32),
+        serialize_u64(u64),
+        serialize_u128(u128),
+        serialize_f32(f32),
+        serialize_f64(f64),
+        serialize_char(char),
+        serialize_str(&str),
+        serialize_bytes(&[u8]),
+        serialize_unit_struct(&'static str),
+    );
+
+    fn serialize_none(self) -> Result<S::Ok, S::Error> {
+        self.inner.serialize_none()
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<S::Ok, S::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.inner
+            .serialize_some(&Formatted::new(value, self.format))
+    }
+
+    fn serialize_unit(self) -> Result<S::Ok, S::Error> {
+        self.inner.serialize_unit()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        name: &'static str,
+        index: u32,
+        variant: &'static str,
+    ) -> Result<S::Ok, S::Error> {
+        self.inner.serialize_unit_variant(name, index, variant)
+    }
+
+    fn serialize_newtype_struct<T>(self, name: &'static str, value: &T) -> Result<S::Ok, S::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        if name == NAME {
+            return value.serialize(Instant(self));
+        }
+
+        self.inner
+            .serialize_newtype_struct(name, &Formatted::new(value, self.format))
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        name: &'static str,
+        index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<S::Ok, S::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.inner.serialize_newtype_variant(
+            name,
+            index,
+            variant,
+            &Formatted::new(value, self.format),
+        )
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, S::Error> {
+        Ok(Formatted::new(self.inner.serialize_seq(len)?, self.format))
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, S::Error> {
+        Ok(Formatted::new(
+            self.inner.serialize_tuple(len)?,
+            self.format,
+        ))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleStruct, S::Error> {
+        Ok(Formatted::new(
+            self.inner.serialize_tuple_struct(name, len)?,
+            self.format,
+        ))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        name: &'static str,
+        index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleVariant, S::Error> {
+        Ok(Formatted::new(
+            self.inner
+                .serialize_tuple_variant(name, index, variant, len)?,
+            self.format,
+        ))
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap, S::Error> {
+        Ok(Formatted::new(self.inner.serialize_map(len)?, self.format))
+    }
+
+    fn serialize_struct(
+        self,
+        name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStruct, S::Error> {
+        Ok(Formatted::new(
+            self.inner.serialize_struct(name, len)?,
+            self.format,
+        ))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        name: &'static str,
+        index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStructVariant, S::Error> {
+        Ok(Formatted::new(
+            self.inner
+                .serialize_struct_variant(name, index, variant, len)?,
+            self.format,
+        ))
+    }
+
+    fn is_human_readable(&self) -> bool {
+        self.inner.is_human_readable()
+    }
+}
+
+macro_rules! forward_compound {
+    ($trait:ident, $($method:ident($($key:ident: $key_ty:ty),*)),*) => {
+        impl<S> ser::$trait for Formatted<S>
+        where
+            S: ser::$trait,
+        {
+            type Ok = S::Ok;
+            type Error = S::Error;
+
+            $(
+                fn $method<T>(&mut self, $($key: $key_ty,)* value: &T) -> Result<(), S::Error>
+                where
+                    T: ?Sized + Serialize,
+                {
+                    let value = self.wrap(value);
+
+                    self.inner.$method($($key,)* &value)
+                }
+            )*
+
+            fn end(self) -> Result<S::Ok, S::Error> {
+                self.inner.end()
+            }
+        }
+    };
+}
+
+forward_compound!(SerializeSeq, serialize_element());
+forward_compound!(SerializeTuple, serialize_element());
+forward_compound!(SerializeTupleStruct, serialize_field());
+forward_compound!(SerializeTupleVariant, serialize_field());
+impl<S> ser::SerializeMap for Formatted<S>
+where
+    S: ser::SerializeMap,
+{
+    type Ok = S::Ok;
+    type Error = S::Error;
+
+    fn serialize_key<T>(&mut self, key: &T) -> Result<(), S::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.inner.serialize_key(key)
+    }
+
+    fn serialize_value<T>(&mut self, value: &T) -> Result<(), S::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        let value = self.wrap(value);
+
+        self.inner.serialize_value(&value)
+    }
+
+    fn end(self) -> Result<S::Ok, S::Error> {
+        self.inner.end()
+    }
+}
+forward_compound!(SerializeStruct, serialize_field(key: &'static str));
+forward_compound!(SerializeStructVariant, serialize_field(key: &'static str));
+
+/// Serializer of the payload of an instant, which writes it in the format of the wrapped one.
+struct Instant<S>(Formatted<S>);
+
+macro_rules! reject_serialize {
+    ($($method:ident($($ty:ty),*)),* $(,)?) => {
+        $(
+            fn $method(self, $(_: $ty),*) -> Result<S::Ok, S::Error> {
+                Err(ser::Error::custom("expected an RFC 3339 date and time"))
+            }
+        )*
+    };
+}
+
+impl<S> Serializer for Instant<S>
+where
+    S: Serializer,
+{
+    type Ok = S::Ok;
+    type Error = S::Error;
+    type SerializeSeq = Impossible<S::Ok, S::Error>;
+    type SerializeTuple = Impossible<S::Ok, S::Error>;
+    type SerializeTupleStruct = Impossible<S::Ok, S::Error>;
+    type SerializeTupleVariant = Impossible<S::Ok, S::Error>;
+    type SerializeMap = Impossible<S::Ok, S::Error>;
+    type SerializeStruct = Impossible<S::Ok, S::Error>;
+    type SerializeStructVariant = Impossible<S::Ok, S::Error>;
+
+    reject_serialize!(
+        serialize_bool(bool),
+        serialize_i8(i8),
+        serialize_i16(i16),
+        serialize_i32(i32),
+        serialize_i64(i64),
+        serialize_u8(u8),
+        serialize_u16(u16),
+        serialize_u32(u32),
+        serialize_u64(u64),
+        serialize_f32(f32),
+        serialize_f64(f64),
+        serialize_char(char),
+        serialize_bytes(&[u8]),
+        serialize_none(),
+        serialize_unit(),
+        serialize_unit_struct(&'static str),
+        serialize_unit_variant(&'static str, u32, &'static str),
+    );
+
+    fn serialize_str(self, value: &str) -> Result<S::Ok, S::Error> {
+        let instant = DateTime::parse_from_rfc3339(value).map_err(ser::Error::custom)?;
+        let Formatted { inner, format } = self.0;
+
+        match format {
+            TimestampFormat::Seconds => inner.serialize_i64(instant.timestamp()),
+            TimestampFormat::Milliseconds => inner.serialize_i64(instant.timestamp_millis()),
+            TimestampFormat::Rfc3339 => {
+                inner.serialize_str(&to_rfc3339(instant.with_timezone(&Utc)))
+            }
+        }
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<S::Ok, S::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_struct<T>(self, _: &'static str, value: &T) -> Result<S::Ok, S::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: &T,
+    ) -> Result<S::Ok, S::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        Err(ser::Error::custom("expected an RFC 3339 date and time"))
+    }
+
+    fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq, S::Error> {
+        Err(ser::Error::custom("expected an RFC 3339 date and time"))
+    }
+
+    fn serialize_tuple(self, _: usize) -> Result<Self::SerializeTuple, S::Error> {
+        Err(ser::Error::custom("expected an RFC 3339 date and time"))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleStruct, S::Error> {
+        Err(ser::Error::custom("expected an RFC 3339 date and time"))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleVariant, S::Error> {
+        Err(ser::Error::custom("expected an RFC 3339 date and time"))
+    }
+
+    fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, S::Error> {
+        Err(ser::Error::custom("expected an RFC 3339 date and time"))
+    }
+
+    fn serialize_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStruct, S::Error> {
+        Err(ser::Error::custom("expected an RFC 3339 date and time"))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStructVariant, S::Error> {
+        Err(ser::Error::custom("expected an RFC 3339 date and time"))
+    }
+
+    fn is_human_readable(&self) -> bool {
+        self.0.inner.is_human_readable()
+    }
+}
+
+impl<'de, T> DeserializeSeed<'de> for Formatted<T>
+where
+    T: DeserializeSeed<'de>,
+{
+    type Value = T::Value;
+
+    fn deserialize<D>(self, d: D) -> Result<T::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let d = self.wrap(d);
+
+        self.inner.deserialize(d)
+    }
+}
+
+macro_rules! forward_deserialize {
+    ($($method:ident($($arg:ident: $ty:ty),*)),* $(,)?) => {
+        $(
+            fn $method<V>(self, $($arg: $ty,)* visitor: V) -> Result<V::Value, D::Error>
+            where
+                V: Visitor<'de>,
+            {
+                let visitor = self.wrap(visitor);
+
+                self.inner.$method($($arg,)* visitor)
+            }
+        )*
+    };
+}
+
+impl<'de, D> Deserializer<'de> for Formatted<D>
+where
+    D: Deserializer<'de>,
+{
+    type Error = D::Error;
+
+    forward_deserialize!(
+        deserialize_any(),
+        deserialize_bool(),
+        deserialize_i8(),
+        deserialize_i16(),
+        deserialize_i32(),
+        deserialize_i64(),
+        deserialize_i128(),
+        deserialize_u8(),
+        deserialize_u16(),
+        deserialize_u32(),
+        deserialize_u64(),
+        deserialize_u128(),
+        deserialize_f32(),
+        deserialize_f64(),
+        deserialize_char(),
+        deserialize_str(),
+        deserialize_string(),
+        deserialize_bytes(),
+        deserialize_byte_buf(),
+        deserialize_option(),
+        deserialize_unit(),
+        deserialize_unit_struct(name: &'static str),
+        deserialize_seq(),
+        deserialize_tuple(len: usize),
+        deserialize_tuple_struct(name: &'static str, len: usize),
+        deserialize_map(),
+        deserialize_struct(name: &'static str, fields: &'static [&'static str]),
+        deserialize_enum(name: &'static str, variants: &'static [&'static str]),
+        deserialize_identifier(),
+        deserialize_ignored_any(),
+    );
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, D::Error>
+    where
+        V: Visitor<'de>,
+    {
+        if name != NAME {
+            let visitor = self.wrap(visitor);
+
+            return self.inner.deserialize_newtype_struct(name, visitor);
+        }
+
+        let instant = match self.format {
+            TimestampFormat::Seconds => self.inner.deserialize_i64(UnixVisitor { millis: false }),
+            TimestampFormat::Milliseconds => {
+                self.inner.deserialize_i64(UnixVisitor { millis: true })
+            }
+            TimestampFormat::Rfc3339 => self
+                .inner
+                .deserialize_str(TimestampVisitor { renamed: true }),
+        }?;
+
+        visitor.visit_newtype_struct(to_rfc3339(instant).into_deserializer())
+    }
+
+    fn is_human_readable(&self) -> bool {
+        self.inner.is_human_readable()
+    }
+}
+
+macro_rules! forward_visit {
+    ($($method:ident($ty:ty)),* $(,)?) => {
+        $(
+            fn $method<E>(self, value: $ty) -> Result<V::Value, E>
+            where
+                E: de::Error,
+            {
+                self.inner.$method(value)
+            }
+        )*
+    };
+}
+
+macro_rules! wrap_visit {
+    ($($method:ident($trait:ident)),* $(,)?) => {
+        $(
+            fn $method<A>(self, value: A) -> Result<V::Value, A::Error>
+            where
+                A: $trait<'de>,
+            {
+                let value = self.wrap(value);
+
+                self.inner.$method(value)
+            }
+        )*
+    };
+}
+
+impl<'de, V> Visitor<'de> for Formatted<V>
+where
+    V: Visitor<'de>,
+{
+    type Value = V::Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        self.inner.expecting(formatter)
+    }
+
+    forward_visit!(
+        visit_bool(bool),
+        visit_i8(i8),
+        visit_i16(i16),
+        visit_i32(i32),
+        visit_i64(i64),
+        visit_i128(i128),
+        visit_u8(u8),
+        visit_u16(u16),
+        visit_u32(u32),
+        visit_u64(u64),
+        visit_u128(u128),
+        visit_f32(f32),
+        visit_f64(f64),
+        visit_char(char),
+        visit_str(&str),
+        visit_borrowed_str(&'de str),
+        visit_string(String),
+        visit_bytes(&[u8]),
+        visit_borrowed_bytes(&'de [u8]),
+        visit_byte_buf(Vec<u8>),
+    );
+
+    wrap_visit!(
+        visit_some(Deserializer),
+        visit_newtype_struct(Deserializer),
+        visit_seq(SeqAccess),
+        visit_map(MapAccess),
+        visit_enum(EnumAccess),
+    );
+
+    fn visit_none<E>(self) -> Result<V::Value, E>
+    where
+        E: de::Error,
+    {
+        self.inner.visit_none()
+    }
+
+    fn visit_unit<E>(self) -> Result<V::Value, E>
+    where
+        E: de::Error,
+    {
+        self.inner.visit_unit()
+    }
+}
+
+impl<'de, A> SeqAccess<'de> for Formatted<A>
+where
+    A: SeqAccess<'de>,
+{
+    type Error = A::Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, A::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        let seed = self.wrap(seed);
+
+        self.inner.next_element_seed(seed)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.inner.size_hint()
+    }
+}
+
+impl<'de, A> MapAccess<'de> for Formatted<A>
+where
+    A: MapAccess<'de>,
+{
+    type Error = A::Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, A::Error>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        let seed = self.wrap(seed);
+
+        self.inner.next_key_seed(seed)
+    }
+
+    fn next_value_seed<T>(&mut self, seed: T) -> Result<T::Value, A::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        let seed = self.wrap(seed);
+
+        self.inner.next_value_seed(seed)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.inner.size_hint()
+    }
+}
+
+impl<'de, A> EnumAccess<'de> for Formatted<A>
+where
+    A: EnumAccess<'de>,
+{
+    type Error = A::Error;
+    type Variant = Formatted<A::Variant>;
+
+    fn variant_seed<T>(self, seed: T) -> Result<(T::Value, Self::Variant), A::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        let seed = self.wrap(seed);
+        let (value, variant) = self.inner.variant_seed(seed)?;
+
+        Ok((value, Formatted::new(variant, self.format)))
+    }
+}
+
+impl<'de, A> VariantAccess<'de> for Formatted<A>
+where
+    A: VariantAccess<'de>,
+{
+    type Error = A::Error;
+
+    fn unit_variant(self) -> Result<(), A::Error> {
+        self.inner.unit_variant()
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, A::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        let seed = self.wrap(seed);
+
+        self.inner.newtype_variant_seed(seed)
+    }
+
+    fn tuple_variant<V>(self, len: usize, visitor: V) -> Result<V::Value, A::Error>
+    where
+        V: Visitor<'de>,
+    {
+        let visitor = self.wrap(visitor);
+
+        self.inner.tuple_variant(len, visitor)
+    }
+
+    fn struct_variant<V>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, A::Error>
+    where
+        V: Visitor<'de>,
+    {
+        let visitor = self.wrap(visitor);
+
+        self.inner.struct_variant(fields, visitor)
+    }
+}
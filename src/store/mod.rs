@@ -0,0 +1,133 @@
+/*!
+   Persistence of calendars event by event, rather than by serializing them whole.
+
+   A [`CalendarStore`](CalendarStore) loads and saves calendars, writes single events and
+   looks up the events occurring within a range. [`MemoryStore`](MemoryStore) keeps them in
+   memory; with the `sqlite` feature, [`SqliteStore`](SqliteStore) keeps them in an SQLite
+   database, and answers range queries from indexed columns.
+
+   # Examples
+
+   ```rust
+   use dateless::{prelude::*, store::{CalendarStore, MemoryStore}};
+   use chrono::{Duration, TimeZone, Utc};
+
+   let start = Utc.ymd(2021, 5, 3).and_hms(9, 0, 0);
+   let standup = EventPartial::new(String::from("Standup"))
+       .from_to(start, start + Duration::minutes(15))
+       .weekly()
+       .complete();
+
+   let mut store = MemoryStore::new();
+   store.upsert_event(&standup).unwrap();
+
+   let next_week = store
+       .events_between(start + Duration::days(7), start + Duration::days(8))
+       .unwrap();
+
+   assert_eq!(next_week, vec![standup]);
+   ```
+*/
+
+#[cfg(feature = "sqlite")]
+mod sqlite;
+
+use crate::{calendar::Calendar, event::Event};
+use chrono::{DateTime, Utc};
+use std::{convert::Infallible, error::Error};
+use uuid::Uuid;
+
+#[cfg(feature = "sqlite")]
+pub use sqlite::{SqliteStore, StoreError};
+
+pub trait CalendarStore {
+    type Error: Error;
+
+    /// Reads every event, in the order they were first stored.
+    fn load(&self) -> Result<Calendar, Self::Error>;
+
+    /// Replaces the content of the store with the calendar. Its journal isn't stored.
+    fn save(&mut self, calendar: &Calendar) -> Result<(), Self::Error>;
+
+    fn event(&self, uuid: Uuid) -> Result<Option<Event>, Self::Error>;
+
+    /// Stores the event, replacing the one of the same `uuid` in place, if any. The event
+    /// is stored as is, its revision included.
+    fn upsert_event(&mut self, event: &Event) -> Result<(), Self::Error>;
+
+    /// Removes the event of the given `uuid`, returning whether it was stored.
+    fn delete_event(&mut self, uuid: Uuid) -> Result<bool, Self::Error>;
+
+    /// Events of which an occurrence overlaps the `[from, to)` range (see
+    /// `Event::occurs_between`), in the order of the store.
+    fn events_between(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<Event>, Self::Error>;
+}
+
+/// Store keeping a calendar in memory, e.g. for tests or as a cache.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct MemoryStore {
+    calendar: Calendar,
+}
+
+impl MemoryStore {
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
+impl CalendarStore for MemoryStore {
+    type Error = Infallible;
+
+    fn load(&self) -> Result<Calendar, Self::Error> {
+        Ok(self.calendar.clone())
+    }
+
+    fn save(&mut self, calendar: &Calendar) -> Result<(), Self::Error> {
+        self.calendar = Calendar {
+            journal: None,
+            ..calendar.clone()
+        };
+        Ok(())
+    }
+
+    fn event(&self, uuid: Uuid) -> Result<Option<Event>, Self::Error> {
+        Ok(self.calendar.event(uuid).cloned())
+    }
+
+    fn upsert_event(&mut self, event: &Event) -> Result<(), Self::Error> {
+        let events = &mut self.calendar.events;
+
+        match events.iter_mut().find(|stored| stored.uuid == event.uuid) {
+            Some(stored) => *stored = event.clone(),
+            None => events.push(event.clone()),
+        }
+
+        Ok(())
+    }
+
+    fn delete_event(&mut self, uuid: Uuid) -> Result<bool, Self::Error> {
+        let events = &mut self.calendar.events;
+        let count = events.len();
+
+        events.retain(|event| event.uuid != uuid);
+        Ok(events.len() < count)
+    }
+
+    fn events_between(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<Event>, Self::Error> {
+        Ok(self
+            .calendar
+            .events
+            .iter()
+            .filter(|event| event.occurs_between(from, to))
+            .cloned()
+            .collect())
+    }
+}
@@ -0,0 +1,393 @@
+use super::CalendarStore;
+use crate::{
+    calendar::Calendar,
+    event::{Event, EventPartial, PeriodKind, Recurrence},
+};
+use chrono::{DateTime, TimeZone, Utc};
+use rusqlite::{named_params, types::Type, Connection, OptionalExtension, Row, ToSql};
+use serde::de::DeserializeOwned;
+use std::{error::Error, fmt, path::Path};
+use uuid::Uuid;
+
+/// Version of the database layout, kept in `PRAGMA user_version`.
+const SCHEMA_VERSION: i64 = 1;
+
+/// Events and their occurrences are looked up by the range of their first period.
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS events (
+        uuid TEXT PRIMARY KEY NOT NULL,
+        position INTEGER NOT NULL,
+        name TEXT NOT NULL,
+        description TEXT,
+        period_kind TEXT NOT NULL,
+        period_start INTEGER NOT NULL,
+        period_end INTEGER NOT NULL,
+        period TEXT NOT NULL,
+        cyclicity_kind TEXT,
+        cyclicity TEXT,
+        exceptions TEXT NOT NULL,
+        alarms TEXT NOT NULL,
+        created INTEGER NOT NULL,
+        last_modified INTEGER NOT NULL,
+        sequence INTEGER NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS events_by_position ON events (position);
+    CREATE INDEX IF NOT EXISTS events_by_period ON events (period_start, period_end);
+    CREATE TABLE IF NOT EXISTS settings (
+        key TEXT PRIMARY KEY NOT NULL,
+        value TEXT NOT NULL
+    );
+";
+
+const UPSERT: &str = "
+    INSERT INTO events (
+        uuid, position, name, description, period_kind, period_start, period_end, period,
+        cyclicity_kind, cyclicity, exceptions, alarms, created, last_modified, sequence
+    ) VALUES (
+        :uuid, (SELECT COALESCE(MAX(position) + 1, 0) FROM events), :name, :description,
+        :period_kind, :period_start, :period_end, :period, :cyclicity_kind, :cyclicity,
+        :exceptions, :alarms, :created, :last_modified, :sequence
+    ) ON CONFLICT (uuid) DO UPDATE SET
+        name = excluded.name,
+        description = excluded.description,
+        period_kind = excluded.period_kind,
+        period_start = excluded.period_start,
+        period_end = excluded.period_end,
+        period = excluded.period,
+        cyclicity_kind = excluded.cyclicity_kind,
+        cyclicity = excluded.cyclicity,
+        exceptions = excluded.exceptions,
+        alarms = excluded.alarms,
+        created = excluded.created,
+        last_modified = excluded.last_modified,
+        sequence = excluded.sequence
+";
+
+const SELECT: &str = "
+    SELECT uuid, name, description, period, cyclicity, exceptions, alarms, created,
+        last_modified, sequence
+    FROM events
+";
+
+#[derive(Debug)]
+pub enum StoreError {
+    Sqlite(rusqlite::Error),
+    /// A stored field could not be (de)serialized.
+    Serde(serde_json::Error),
+    /// The database was written by a newer version of the library.
+    UnsupportedVersion(i64),
+}
+
+impl fmt::Display for StoreError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StoreError::Sqlite(error) => write!(f, "SQLite error: {}", error),
+            StoreError::Serde(error) => write!(f, "invalid stored field: {}", error),
+            StoreError::UnsupportedVersion(version) => {
+                write!(f, "unsupported database version {}", version)
+            }
+        }
+    }
+}
+
+impl Error for StoreError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            StoreError::Sqlite(error) => Some(error),
+            StoreError::Serde(error) => Some(error),
+            StoreError::UnsupportedVersion(_) => None,
+        }
+    }
+}
+
+impl From<rusqlite::Error> for StoreError {
+    fn from(error: rusqlite::Error) -> Self {
+        StoreError::Sqlite(error)
+    }
+}
+
+impl From<serde_json::Error> for StoreError {
+    fn from(error: serde_json::Error) -> Self {
+        StoreError::Serde(error)
+    }
+}
+
+/**
+   Store keeping events in an SQLite database, one row each.
+
+   Besides the name and the description, every period is stored along with its kind and
+   the range of its first occurrence in Unix seconds, and every cyclicity along with its
+   kind, so the database is queryable by itself. Range queries read only the events whose
+   first occurrence starts before the end of the range, and, unless they repeat, ends
+   after its start. Periods, cyclicities, exceptions and alarms are stored as JSON, with
+   instants as RFC 3339 strings so that none of their precision is lost.
+
+   # Examples
+
+   ```rust
+   use dateless::{prelude::*, store::{CalendarStore, SqliteStore}};
+   use chrono::Utc;
+
+   let mut calendar = Calendar::new();
+   calendar.add_event(EventPartial::new("Standup").whole_day(Utc::today()).daily().complete());
+
+   let mut store = SqliteStore::open(":memory:").unwrap();
+   store.save(&calendar).unwrap();
+
+   assert_eq!(store.load().unwrap(), calendar);
+   ```
+*/
+#[derive(Debug)]
+pub struct SqliteStore {
+    connection: Connection,
+}
+
+impl SqliteStore {
+    /// Opens the database at the given path, `:memory:` being a new in-memory one, and
+    /// creates its tables if needed.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, StoreError> {
+        Self::from_connection(Connection::open(path)?)
+    }
+
+    pub fn open_in_memory() -> Result<Self, StoreError> {
+        Self::from_connection(Connection::open_in_memory()?)
+    }
+
+    /// The underlying connection, e.g. to query the `events` table directly.
+    pub fn connection(&self) -> &Connection {
+        &self.connection
+    }
+
+    fn from_connection(connection: Connection) -> Result<Self, StoreError> {
+        let version: i64 = connection.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+
+        if version > SCHEMA_VERSION {
+            return Err(StoreError::UnsupportedVersion(version));
+        }
+
+        connection.execute_batch(SCHEMA)?;
+        connection.pragma_update(None, "user_version", SCHEMA_VERSION)?;
+
+        Ok(Self { connection })
+    }
+
+    fn select(
+        &self,
+        filter: &str,
+        params: &[(&str, &dyn ToSql)],
+    ) -> Result<Vec<Event>, StoreError> {
+        let mut statement = self
+            .connection
+            .prepare(&format!("{} {} ORDER BY position", SELECT, filter))?;
+        let rows = statement
+            .query_map(params, StoredEvent::from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        rows.into_iter().map(StoredEvent::into_event).collect()
+    }
+
+    fn setting<T: DeserializeOwned>(&self, key: &str) -> Result<Option<T>, StoreError> {
+        let value = self
+            .connection
+            .query_row("SELECT value FROM settings WHERE key = ?1", [key], |row| {
+                row.get::<_, String>(0)
+            })
+            .optional()?;
+
+        Ok(value
+            .map(|value| serde_json::from_str(&value))
+            .transpose()?)
+    }
+}
+
+impl CalendarStore for SqliteStore {
+    type Error = StoreError;
+
+    fn load(&self) -> Result<Calendar, Self::Error> {
+        Ok(Calendar {
+            events: self.select("", &[])?,
+            timestamp_format: self.setting("timestamp_format")?.unwrap_or_default(),
+            week_start: self.setting("week_start")?.unwrap_or_default(),
+            journal: None,
+        })
+    }
+
+    fn save(&mut self, calendar: &Calendar) -> Result<(), Self::Error> {
+        let transaction = self.connection.transaction()?;
+
+        transaction.execute("DELETE FROM events", [])?;
+
+        for event in &calendar.events {
+            upsert(&transaction, event)?;
+        }
+
+        for (key, value) in &[
+            (
+                "timestamp_format",
+                serde_json::to_string(&calendar.timestamp_format)?,
+            ),
+            ("week_start", serde_json::to_string(&calendar.week_start)?),
+        ] {
+            transaction.execute(
+                "INSERT OR REPLACE INTO settings (key, value) VALUES (?1, ?2)",
+                [key, value.as_str()],
+            )?;
+        }
+        transaction.commit()?;
+
+        Ok(())
+    }
+
+    fn event(&self, uuid: Uuid) -> Result<Option<Event>, Self::Error> {
+        Ok(self
+            .select(
+                "WHERE uuid = :uuid",
+                named_params! { ":uuid": uuid.to_string() },
+            )?
+            .pop())
+    }
+
+    fn upsert_event(&mut self, event: &Event) -> Result<(), Self::Error> {
+        upsert(&self.connection, event)
+    }
+
+    fn delete_event(&mut self, uuid: Uuid) -> Result<bool, Self::Error> {
+        let deleted = self
+            .connection
+            .execute("DELETE FROM events WHERE uuid = ?1", [uuid.to_string()])?;
+
+        Ok(deleted > 0)
+    }
+
+    fn events_between(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<Event>, Self::Error> {
+        let candidates = self.select(
+            "WHERE period_start < :to
+                AND (cyclicity_kind IS NOT NULL OR period_end > :from OR period_start >= :from)",
+            named_params! { ":from": seconds_floor(from), ":to": seconds_ceil(to) },
+        )?;
+
+        Ok(candidates
+            .into_iter()
+            .filter(|event| event.occurs_between(from, to))
+            .collect())
+    }
+}
+
+fn upsert(connection: &Connection, event: &Event) -> Result<(), StoreError> {
+    let period = serde_json::to_string(event.period())?;
+    let cyclicity = event.cyclicity().map(serde_json::to_string).transpose()?;
+    let exceptions = serde_json::to_string(&event.exceptions().collect::<Vec<_>>())?;
+
+    connection.execute(
+        UPSERT,
+        named_params! {
+            ":uuid": event.uuid.to_string(),
+            ":name": event.name(),
+            ":description": event.description(),
+            ":period_kind": kind(&period)?,
+            ":period_start": seconds_floor(event.period().get_date_time_start()),
+            ":period_end": seconds_ceil(event.period().get_date_time_end()),
+            ":period": period,
+            ":cyclicity_kind": cyclicity.as_deref().map(kind).transpose()?,
+            ":cyclicity": cyclicity,
+            ":exceptions": exceptions,
+            ":alarms": serde_json::to_string(event.alarms())?,
+            ":created": event.created().timestamp(),
+            ":last_modified": event.last_modified().timestamp(),
+            ":sequence": event.sequence(),
+        },
+    )?;
+
+    Ok(())
+}
+
+/// Columns of a stored event, as read from the database.
+struct StoredEvent {
+    uuid: Uuid,
+    name: String,
+    description: Option<String>,
+    period: String,
+    cyclicity: Option<String>,
+    exceptions: String,
+    alarms: String,
+    created: DateTime<Utc>,
+    last_modified: DateTime<Utc>,
+    sequence: u32,
+}
+
+impl StoredEvent {
+    fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        let uuid: String = row.get(0)?;
+
+        Ok(Self {
+            uuid: Uuid::parse_str(&uuid).map_err(|error| {
+                rusqlite::Error::FromSqlConversionFailure(0, Type::Text, Box::new(error))
+            })?,
+            name: row.get(1)?,
+            description: row.get(2)?,
+            period: row.get(3)?,
+            cyclicity: row.get(4)?,
+            exceptions: row.get(5)?,
+            alarms: row.get(6)?,
+            created: instant(row, 7)?,
+            last_modified: instant(row, 8)?,
+            sequence: row.get(9)?,
+        })
+    }
+
+    fn into_event(self) -> Result<Event, StoreError> {
+        let mut partial = EventPartial::new(self.name)
+            .with_period(serde_json::from_str::<PeriodKind>(&self.period)?);
+
+        if let Some(description) = self.description {
+            partial = partial.with_description(description);
+        }
+
+        if let Some(cyclicity) = self.cyclicity {
+            partial = partial.with_cyclicity(serde_json::from_str::<Recurrence>(&cyclicity)?);
+        }
+
+        for exception in serde_json::from_str::<Vec<DateTime<Utc>>>(&self.exceptions)? {
+            partial = partial.with_exception(exception);
+        }
+
+        for alarm in serde_json::from_str::<Vec<_>>(&self.alarms)? {
+            partial = partial.with_alarm(alarm);
+        }
+
+        let mut event = partial.complete();
+
+        event.uuid = self.uuid;
+        event.restore_revision(self.created, self.last_modified, self.sequence);
+
+        Ok(event)
+    }
+}
+
+/// The instant stored in seconds in the given column.
+fn instant(row: &Row, column: usize) -> rusqlite::Result<DateTime<Utc>> {
+    let seconds = row.get(column)?;
+
+    Utc.timestamp_opt(seconds, 0)
+        .single()
+        .ok_or(rusqlite::Error::IntegralValueOutOfRange(column, seconds))
+}
+
+/// The type tag of a serialized period or cyclicity.
+fn kind(json: &str) -> Result<String, serde_json::Error> {
+    let value: serde_json::Value = serde_json::from_str(json)?;
+
+    Ok(value["type"].as_str().unwrap_or_default().to_string())
+}
+
+fn seconds_floor(at: DateTime<Utc>) -> i64 {
+    at.timestamp()
+}
+
+fn seconds_ceil(at: DateTime<Utc>) -> i64 {
+    at.timestamp() + i64::from(at.timestamp_subsec_nanos() > 0)
+}
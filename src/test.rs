@@ -17,3 +17,2706 @@ test!(
     Duration::hours(1),
     [Duration::days(2), Duration::days(-1)]
 );
+
+#[test]
+fn alarms_follow_cyclicity_and_exceptions() {
+    use crate::prelude::*;
+    use chrono::{Duration, TimeZone, Utc};
+
+    let start = Utc.ymd(2021, 5, 3).and_hms(10, 0, 0);
+
+    let event = EventPartial::new(String::from("Standup"))
+        .from_to(start, start + Duration::hours(1))
+        .daily()
+        .with_exception(start + Duration::days(2))
+        .with_alarm(Alarm::before_start(Duration::minutes(15)))
+        .complete();
+
+    let mut calendar = Calendar::new();
+    calendar.add_event(event);
+
+    let alarms = calendar.alarms_between(
+        Utc.ymd(2021, 5, 4).and_hms(0, 0, 0),
+        Utc.ymd(2021, 5, 7).and_hms(0, 0, 0),
+    );
+
+    assert_eq!(
+        alarms
+            .iter()
+            .map(|alarm| alarm.fires_at)
+            .collect::<Vec<_>>(),
+        vec![
+            Utc.ymd(2021, 5, 4).and_hms(9, 45, 0),
+            Utc.ymd(2021, 5, 6).and_hms(9, 45, 0),
+        ]
+    );
+}
+
+#[test]
+fn alarms_of_minutely_events_over_weeks() {
+    use crate::prelude::*;
+    use chrono::{Duration, TimeZone, Utc};
+
+    let start = Utc.ymd(2021, 5, 3).and_hms(0, 0, 0);
+    let event = EventPartial::new(String::from("Heartbeat"))
+        .from_to(start, start + Duration::minutes(1))
+        .minutely(1)
+        .with_alarm(Alarm::after_end(Duration::seconds(30)))
+        .complete();
+
+    let alarms = event.get_alarms_between(start, start + Duration::weeks(4));
+
+    assert_eq!(alarms.len(), 4 * 7 * 24 * 60 - 1);
+    assert_eq!(alarms[0].fires_at, start + Duration::seconds(90));
+    assert!(alarms
+        .windows(2)
+        .all(|pair| pair[1].fires_at - pair[0].fires_at == Duration::minutes(1)));
+}
+
+#[test]
+fn alarms_anchored_at_the_end_of_open_ended_events() {
+    use crate::prelude::*;
+    use chrono::{Duration, TimeZone, Utc};
+
+    let start = Utc.ymd(2021, 5, 3).and_hms(9, 0, 0);
+    let leave = EventPartial::new(String::from("Leave"))
+        .open_ended(start)
+        .daily()
+        .with_alarm(Alarm::before_start(Duration::minutes(15)))
+        .with_alarm(Alarm::after_end(Duration::hours(1)))
+        .complete();
+
+    assert_eq!(
+        Alarm::after_end(Duration::hours(1)).fires_at(&OpenEnded(start)),
+        None
+    );
+
+    let alarms = leave.get_alarms_between(start - Duration::days(1), start + Duration::days(1));
+
+    let fired: Vec<_> = alarms.iter().map(|alarm| alarm.fires_at).collect();
+
+    assert_eq!(
+        fired,
+        vec![
+            start - Duration::minutes(15),
+            start + Duration::days(1) - Duration::minutes(15)
+        ]
+    );
+}
+
+#[test]
+fn whole_day_alarm_at_fixed_time() {
+    use crate::prelude::*;
+    use chrono::{Duration, NaiveTime, TimeZone, Utc};
+
+    let mut calendar = Calendar::new();
+    calendar.add_event(
+        EventPartial::new(String::from("Anne's birthday"))
+            .whole_day(Utc.ymd(2021, 5, 8))
+            .monthly()
+            .with_alarm(Alarm::before_start(Duration::days(1)).at(NaiveTime::from_hms(9, 0, 0)))
+            .complete(),
+    );
+
+    let alarms = calendar.alarms_between(
+        Utc.ymd(2021, 6, 7).and_hms(0, 0, 0),
+        Utc.ymd(2021, 6, 8).and_hms(0, 0, 0),
+    );
+
+    assert_eq!(alarms.len(), 1);
+    assert_eq!(alarms[0].fires_at, Utc.ymd(2021, 6, 7).and_hms(9, 0, 0));
+    assert_eq!(
+        alarms[0].occurrence.period.get_date_time_start(),
+        Utc.ymd(2021, 6, 8).and_hms(0, 0, 0)
+    );
+}
+
+#[test]
+fn sub_daily_cycles_occur_many_times_a_day() {
+    use crate::prelude::*;
+    use chrono::{Duration, NaiveTime, TimeZone, Utc};
+
+    let start = Utc.ymd(2021, 5, 3).and_hms(2, 0, 0);
+    let window = TimeWindow(NaiveTime::from_hms(8, 0, 0), NaiveTime::from_hms(18, 0, 0));
+
+    let mut calendar = Calendar::new();
+    calendar.add_event(
+        EventPartial::new(String::from("Medication"))
+            .from_to(start, start + Duration::minutes(5))
+            .hourly(6)
+            .complete(),
+    );
+    calendar.add_event(
+        EventPartial::new(String::from("Polling"))
+            .from_to(
+                start + Duration::hours(6),
+                start + Duration::hours(6) + Duration::minutes(15),
+            )
+            .with_cyclicity(Box::new(MinutelyCycle::new(30).within(window)))
+            .complete(),
+    );
+
+    let occurrences = calendar.day(Utc.ymd(2021, 5, 4));
+    let starts_of = |name: &str| {
+        occurrences
+            .iter()
+            .filter(|occurrence| occurrence.name == name)
+            .map(|occurrence| occurrence.period.get_date_time_start().time())
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        starts_of("Medication"),
+        vec![
+            NaiveTime::from_hms(2, 0, 0),
+            NaiveTime::from_hms(8, 0, 0),
+            NaiveTime::from_hms(14, 0, 0),
+            NaiveTime::from_hms(20, 0, 0),
+        ]
+    );
+
+    let polling = starts_of("Polling");
+
+    assert_eq!(polling.len(), 20);
+    assert_eq!(polling.first(), Some(&NaiveTime::from_hms(8, 0, 0)));
+    assert_eq!(polling.last(), Some(&NaiveTime::from_hms(17, 30, 0)));
+
+    assert_eq!(HourlyCycle::new_opt(0), None);
+    assert_eq!(MinutelyCycle::new_opt(0), None);
+    assert!(serde_json::from_str::<Box<dyn Cyclicity>>(
+        r#"{ "type": "HourlyCycle", "interval": 0 }"#
+    )
+    .is_err());
+}
+
+#[test]
+fn overnight_occurrences_show_on_both_days() {
+    use crate::prelude::*;
+    use chrono::{Duration, TimeZone, Utc};
+
+    let start = Utc.ymd(2021, 5, 3).and_hms(22, 0, 0);
+
+    let shift = EventPartial::new(String::from("Night shift"))
+        .from_to(start, start + Duration::hours(8))
+        .daily()
+        .complete();
+
+    #[allow(deprecated)]
+    let first = shift.get_occurrence_at(Utc.ymd(2021, 5, 5)).unwrap();
+    assert_eq!(
+        first.period.get_date_time_start(),
+        start + Duration::days(1)
+    );
+
+    let mut calendar = Calendar::new();
+    calendar.add_event(shift);
+
+    let starts_at = |date| {
+        calendar
+            .day(date)
+            .iter()
+            .map(|occurrence| occurrence.period.get_date_time_start())
+            .collect::<Vec<_>>()
+    };
+
+    assert!(starts_at(Utc.ymd(2021, 5, 2)).is_empty());
+    assert_eq!(starts_at(Utc.ymd(2021, 5, 3)), vec![start]);
+    assert_eq!(
+        starts_at(Utc.ymd(2021, 5, 5)),
+        vec![start + Duration::days(1), start + Duration::days(2)]
+    );
+}
+
+mod period_boundaries {
+    use crate::prelude::*;
+    use chrono::{Date, DateTime, Duration, TimeZone, Utc};
+    use proptest::prelude::*;
+
+    const DAY: i64 = 86_400;
+
+    fn instant() -> impl Strategy<Value = DateTime<Utc>> {
+        let day = 10_000i64..30_000;
+
+        prop_oneof![
+            day.clone().prop_map(|day| day * DAY),
+            (day, 0..DAY).prop_map(|(day, second)| day * DAY + second),
+        ]
+        .prop_map(|timestamp| Utc.timestamp(timestamp, 0))
+    }
+
+    fn length() -> impl Strategy<Value = Duration> {
+        prop_oneof![
+            Just(0i64),
+            (1i64..5).prop_map(|days| days * DAY),
+            0..5 * DAY,
+        ]
+        .prop_map(Duration::seconds)
+    }
+
+    fn touched_days(period: &StartEnd) -> (Date<Utc>, Date<Utc>) {
+        let last_instant = if period.0 == period.1 {
+            period.1
+        } else {
+            period.1 - Duration::nanoseconds(1)
+        };
+
+        (period.0.date(), last_instant.date())
+    }
+
+    proptest! {
+        #[test]
+        fn start_end_contains_days_of_its_instants(
+            start in instant(),
+            length in length(),
+            offset in -3i64..8,
+        ) {
+            let period = StartEnd(start, start + length);
+            let date = start.date() + Duration::days(offset);
+            let (first_day, last_day) = touched_days(&period);
+
+            prop_assert_eq!(period.contains(date), date >= first_day && date <= last_day);
+        }
+
+        #[test]
+        fn start_end_starts_before_ignores_the_clock(start in instant(), offset in -3i64..3) {
+            let period = StartEnd(start, start + Duration::hours(1));
+            let date = start.date() + Duration::days(offset);
+
+            prop_assert_eq!(period.starts_before(date), offset > 0);
+        }
+
+        #[test]
+        fn whole_days_contains_its_days_inclusively(
+            start in instant(),
+            days in 0i64..5,
+            offset in -3i64..8,
+        ) {
+            let period = WholeDays(start.date(), start.date() + Duration::days(days));
+            let date = start.date() + Duration::days(offset);
+
+            prop_assert_eq!(period.contains(date), offset >= 0 && offset <= days);
+        }
+    }
+
+    #[test]
+    fn start_end_touching_midnight() {
+        let midnight = Utc.ymd(2021, 5, 4).and_hms(0, 0, 0);
+
+        let ending_at_midnight = StartEnd(midnight - Duration::hours(2), midnight);
+        assert!(ending_at_midnight.contains(Utc.ymd(2021, 5, 3)));
+        assert!(!ending_at_midnight.contains(Utc.ymd(2021, 5, 4)));
+
+        let starting_at_midnight = StartEnd(midnight, midnight + Duration::hours(2));
+        assert!(!starting_at_midnight.contains(Utc.ymd(2021, 5, 3)));
+        assert!(starting_at_midnight.contains(Utc.ymd(2021, 5, 4)));
+
+        let moment_at_midnight = StartEnd(midnight, midnight);
+        assert!(!moment_at_midnight.contains(Utc.ymd(2021, 5, 3)));
+        assert!(moment_at_midnight.contains(Utc.ymd(2021, 5, 4)));
+    }
+}
+
+#[test]
+fn periods_expose_exclusive_ranges() {
+    use crate::prelude::*;
+    use chrono::{Duration, TimeZone, Utc};
+
+    let may_8 = Utc.ymd(2021, 5, 8);
+    let whole_day = WholeDays(may_8, may_8);
+
+    assert_eq!(
+        whole_day.range(),
+        may_8.and_hms(0, 0, 0)..Utc.ymd(2021, 5, 9).and_hms(0, 0, 0)
+    );
+    assert_eq!(whole_day.duration(), Duration::days(1));
+    assert_eq!(
+        WholeDays::from_exclusive_end(may_8, Utc.ymd(2021, 5, 10)).duration(),
+        Duration::days(2)
+    );
+    assert_eq!(PeriodDef::default().0.duration(), Duration::days(1));
+
+    let meeting = StartEnd(may_8.and_hms(10, 0, 0), may_8.and_hms(11, 30, 0));
+    assert_eq!(meeting.duration(), Duration::minutes(90));
+    assert_eq!(StartEnd::END, EndBoundary::Exclusive);
+    assert_eq!(WholeDays::END, EndBoundary::Inclusive);
+}
+
+#[test]
+fn period_algebra() {
+    use crate::prelude::*;
+    use chrono::{Duration, TimeZone, Utc};
+
+    let day = Utc.ymd(2021, 5, 8);
+    let morning = StartEnd(day.and_hms(9, 0, 0), day.and_hms(12, 0, 0));
+    let lunch = StartEnd(day.and_hms(11, 30, 0), day.and_hms(13, 0, 0));
+    let afternoon = StartEnd(day.and_hms(13, 0, 0), day.and_hms(17, 0, 0));
+    let deadline = StartEnd(day.and_hms(13, 0, 0), day.and_hms(13, 0, 0));
+
+    assert!(morning.overlaps(&lunch));
+    assert!(!lunch.overlaps(&afternoon));
+    assert!(afternoon.overlaps(&deadline));
+    assert!(!lunch.overlaps(&deadline));
+
+    assert_eq!(
+        morning.intersection(&lunch).unwrap().range(),
+        day.and_hms(11, 30, 0)..day.and_hms(12, 0, 0)
+    );
+    assert!(morning.intersection(&afternoon).is_none());
+    assert_eq!(
+        lunch.union(&afternoon).unwrap().range(),
+        day.and_hms(11, 30, 0)..day.and_hms(17, 0, 0)
+    );
+    assert!(morning.union(&afternoon).is_none());
+
+    assert!(morning.contains_instant(day.and_hms(9, 0, 0)));
+    assert!(!morning.contains_instant(day.and_hms(12, 0, 0)));
+    assert!(deadline.contains_instant(day.and_hms(13, 0, 0)));
+
+    let weekend = WholeDays(day, day + Duration::days(1));
+    let next_weekend = weekend.shift(Duration::weeks(1));
+    assert_eq!(
+        next_weekend.range(),
+        Utc.ymd(2021, 5, 15).and_hms(0, 0, 0)..Utc.ymd(2021, 5, 17).and_hms(0, 0, 0)
+    );
+
+    let sunday = WholeDays(day + Duration::days(1), day + Duration::days(3));
+    let shared = weekend.intersection(&sunday).unwrap();
+    assert_eq!(
+        format!("{:?}", shared),
+        format!(
+            "{:?}",
+            WholeDays(day + Duration::days(1), day + Duration::days(1))
+        )
+    );
+    assert_eq!(
+        weekend.intersection(&afternoon).unwrap().range(),
+        afternoon.range()
+    );
+}
+
+#[test]
+fn moments_open_ended_and_floating_periods() {
+    use crate::prelude::*;
+    use chrono::{Duration, NaiveDate, TimeZone, Utc};
+
+    let deadline = Utc.ymd(2021, 5, 8).and_hms(23, 59, 0);
+    let leave = Utc.ymd(2021, 3, 3).and_hms(0, 0, 0);
+    let day = NaiveDate::from_ymd(2021, 5, 10);
+
+    let mut calendar = Calendar::new();
+    calendar.add_event(
+        EventPartial::new(String::from("Deadline"))
+            .moment(deadline)
+            .with_alarm(Alarm::before_start(Duration::hours(1)))
+            .complete(),
+    );
+    calendar.add_event(
+        EventPartial::new(String::from("Leave"))
+            .open_ended(leave)
+            .with_alarm(Alarm::after_end(Duration::days(1)))
+            .complete(),
+    );
+    calendar.add_event(
+        EventPartial::new(String::from("Standup"))
+            .floating(day.and_hms(9, 0, 0), day.and_hms(9, 15, 0))
+            .weekly()
+            .complete(),
+    );
+
+    let names_at = |date| {
+        calendar
+            .day(date)
+            .into_iter()
+            .map(|occurrence| occurrence.name)
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(names_at(Utc.ymd(2021, 3, 2)), Vec::<String>::new());
+    assert_eq!(names_at(Utc.ymd(2021, 5, 8)), vec!["Deadline", "Leave"]);
+    assert_eq!(names_at(Utc.ymd(2030, 5, 13)), vec!["Leave", "Standup"]);
+
+    let alarms = calendar.alarms_between(deadline - Duration::days(1), deadline);
+    assert_eq!(alarms.len(), 1);
+    assert_eq!(alarms[0].fires_at, deadline - Duration::hours(1));
+
+    for period in [
+        PeriodDef(Box::new(Moment(deadline))),
+        PeriodDef(Box::new(OpenEnded(leave))),
+        PeriodDef(Box::new(FloatingTime(
+            day.and_hms(9, 0, 0),
+            day.and_hms(9, 15, 0),
+        ))),
+    ] {
+        let json = serde_json::to_string(&period).unwrap();
+        let restored: PeriodDef = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(restored.0.range(), period.0.range());
+    }
+}
+
+#[test]
+fn cycles_step_to_neighbouring_starts() {
+    use crate::prelude::*;
+    use chrono::{TimeZone, Utc};
+
+    let origin = Utc.ymd(2020, 1, 31).and_hms(10, 0, 0);
+    let period = StartEnd(origin, origin + chrono::Duration::hours(1));
+    let at = |y, m, d| Utc.ymd(y, m, d).and_hms(10, 0, 0);
+
+    assert_eq!(
+        DailyCycle.next_start_after(&period, at(2020, 2, 3)),
+        Some(at(2020, 2, 4))
+    );
+    assert_eq!(
+        WeeklyCycle.previous_start_before(&period, at(2020, 2, 14)),
+        Some(at(2020, 2, 7))
+    );
+    assert_eq!(
+        MonthlyCycle.next_start_after(&period, at(2020, 1, 31)),
+        Some(at(2020, 3, 31))
+    );
+    assert_eq!(
+        MonthlyCycle.previous_start_before(&period, at(2020, 5, 1)),
+        Some(at(2020, 3, 31))
+    );
+    assert_eq!(
+        AnnualCycle.next_start_after(&period, at(2020, 2, 1)),
+        Some(at(2021, 1, 31))
+    );
+
+    assert_eq!(
+        DailyCycle.next_start_after(&period, at(2019, 1, 1)),
+        Some(origin)
+    );
+    assert_eq!(DailyCycle.previous_start_before(&period, origin), None);
+
+    assert!(MonthlyCycle
+        .same_period_at(Box::new(period.clone()), Utc.ymd(2020, 2, 29))
+        .is_none());
+    assert_eq!(
+        MonthlyCycle
+            .same_period_at(Box::new(period), Utc.ymd(2020, 3, 31))
+            .map(|period| period.get_date_time_start()),
+        Some(at(2020, 3, 31))
+    );
+}
+
+#[test]
+fn shifted_and_listed_starts_begin_with_the_period() {
+    use crate::prelude::*;
+    use chrono::{Duration, TimeZone, Utc};
+
+    let origin = Utc.ymd(2021, 5, 3).and_hms(9, 0, 0);
+    let period = Moment(origin);
+    let starts = |cycle: &dyn Cyclicity| {
+        let mut starts = Vec::new();
+        let mut start = cycle.next_start_after(&period, origin - Duration::days(30));
+
+        while let Some(value) = start.filter(|value| *value < origin + Duration::days(15)) {
+            starts.push(value);
+            start = cycle.next_start_after(&period, value);
+        }
+
+        starts
+    };
+    let ends = |cycle: &dyn Cyclicity| {
+        let mut starts = Vec::new();
+        let mut start = cycle.previous_start_before(&period, origin + Duration::days(15));
+
+        while let Some(value) = start {
+            starts.push(value);
+            start = cycle.previous_start_before(&period, value);
+        }
+
+        starts.reverse();
+        starts
+    };
+
+    let listed = OnDates::new(vec![origin - Duration::days(1), origin + Duration::days(3)]);
+    assert_eq!(starts(&listed), vec![origin, origin + Duration::days(3)]);
+    assert_eq!(ends(&listed), starts(&listed));
+
+    let later = Shifted::new(Duration::days(2), Box::new(WeeklyCycle));
+    assert_eq!(
+        starts(&later),
+        vec![
+            origin,
+            origin + Duration::days(2),
+            origin + Duration::days(9)
+        ]
+    );
+    assert_eq!(ends(&later), starts(&later));
+
+    let earlier = Shifted::new(Duration::days(-2), Box::new(WeeklyCycle));
+    assert_eq!(
+        starts(&earlier),
+        vec![
+            origin,
+            origin + Duration::days(5),
+            origin + Duration::days(12)
+        ]
+    );
+    assert_eq!(ends(&earlier), starts(&earlier));
+}
+
+#[test]
+fn composite_cyclicities() {
+    use crate::prelude::*;
+    use chrono::{Duration, TimeZone, Utc};
+
+    let monday = Utc.ymd(2021, 5, 3).and_hms(9, 0, 0);
+    let weekdays = (0..5)
+        .map(|days| Box::new(Shifted::new(Duration::days(days), Box::new(WeeklyCycle))) as _)
+        .collect();
+    let rule = AnyOf(vec![
+        Box::new(Except(
+            Box::new(AnyOf(weekdays)),
+            Box::new(OnDates::new(vec![monday + Duration::days(2)])),
+        )),
+        Box::new(OnDates::new(vec![monday + Duration::days(5)])),
+    ]);
+
+    let mut calendar = Calendar::new();
+    calendar.add_event(
+        EventPartial::new(String::from("Standup"))
+            .from_to(monday, monday + Duration::minutes(15))
+            .with_cyclicity(Box::new(rule))
+            .complete(),
+    );
+    calendar.add_event(
+        EventPartial::new(String::from("Payday"))
+            .whole_day(Utc.ymd(2021, 1, 1))
+            .with_cyclicity(Box::new(AnyOf(vec![
+                Box::new(MonthlyCycle),
+                Box::new(Shifted::new(Duration::days(14), Box::new(MonthlyCycle))),
+            ])))
+            .complete(),
+    );
+    calendar.add_event(
+        EventPartial::new(String::from("Review"))
+            .from_to(monday, monday + Duration::hours(1))
+            .with_cyclicity(Box::new(AllOf(vec![
+                Box::new(DailyCycle),
+                Box::new(Shifted::new(Duration::days(14), Box::new(WeeklyCycle))),
+            ])))
+            .complete(),
+    );
+
+    let json = serde_json::to_string(&calendar).unwrap();
+    let calendar: Calendar = serde_json::from_str(&json).unwrap();
+
+    let names_at = |y, m, d| {
+        calendar
+            .day(Utc.ymd(y, m, d))
+            .into_iter()
+            .map(|occurrence| occurrence.name)
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(names_at(2021, 5, 4), vec!["Standup"]);
+    assert!(names_at(2021, 5, 5).is_empty());
+    assert_eq!(names_at(2021, 5, 8), vec!["Standup"]);
+    assert!(names_at(2021, 5, 9).is_empty());
+    assert_eq!(names_at(2021, 5, 10), vec!["Standup"]);
+    assert!(names_at(2021, 5, 16).is_empty());
+    assert_eq!(names_at(2021, 5, 17), vec!["Standup", "Review"]);
+    assert_eq!(names_at(2021, 3, 15), vec!["Payday"]);
+    assert!(names_at(2021, 3, 16).is_empty());
+}
+
+#[test]
+fn business_day_cycles() {
+    use crate::prelude::*;
+    use chrono::{Duration, TimeZone, Utc, Weekday};
+
+    let holidays = || {
+        let mut holidays = Calendar::new();
+        holidays.add_event(
+            EventPartial::new(String::from("Company day"))
+                .whole_day(Utc.ymd(2021, 7, 30))
+                .complete(),
+        );
+        holidays
+    };
+    let working_days = || WorkingDays::new().with_holidays(holidays());
+
+    let mut calendar = Calendar::new();
+    calendar.add_event(
+        EventPartial::new(String::from("Payment"))
+            .whole_day(Utc.ymd(2021, 1, 15))
+            .with_cyclicity(Box::new(Adjusted {
+                rule: Box::new(MonthlyCycle),
+                working_days: working_days(),
+                adjustment: BusinessDayAdjustment::Following,
+            }))
+            .complete(),
+    );
+    calendar.add_event(
+        EventPartial::new(String::from("Payroll"))
+            .whole_day(Utc.ymd(2021, 1, 29))
+            .with_cyclicity(Box::new(LastWorkingDayOfMonth(working_days())))
+            .complete(),
+    );
+    calendar.add_event(
+        EventPartial::new(String::from("Standup"))
+            .whole_day(Utc.ymd(2021, 7, 26))
+            .with_cyclicity(Box::new(WorkingDayCycle(working_days())))
+            .complete(),
+    );
+
+    let names_at = |y, m, d| {
+        calendar
+            .day(Utc.ymd(y, m, d))
+            .into_iter()
+            .map(|occurrence| occurrence.name)
+            .collect::<Vec<_>>()
+    };
+
+    assert!(names_at(2021, 5, 15).is_empty());
+    assert_eq!(names_at(2021, 5, 17), vec!["Payment"]);
+    assert_eq!(names_at(2021, 6, 15), vec!["Payment"]);
+    assert_eq!(names_at(2021, 7, 29), vec!["Payroll", "Standup"]);
+    assert!(names_at(2021, 7, 30).is_empty());
+    assert!(names_at(2021, 7, 31).is_empty());
+    assert_eq!(names_at(2021, 8, 2), vec!["Standup"]);
+    assert_eq!(names_at(2021, 8, 31), vec!["Payroll", "Standup"]);
+
+    // May 15, 2021 is a Saturday: the first rent can't move back before the event starts.
+    let rent = EventPartial::new(String::from("Rent"))
+        .whole_day(Utc.ymd(2021, 5, 15))
+        .with_cyclicity(Box::new(Adjusted {
+            rule: Box::new(MonthlyCycle),
+            working_days: working_days(),
+            adjustment: BusinessDayAdjustment::Preceding,
+        }))
+        .complete();
+    let origin = Utc.ymd(2021, 5, 15).and_hms(0, 0, 0);
+    let cyclicity = rent.cyclicity().unwrap();
+
+    assert_eq!(
+        cyclicity.next_start_after(&**rent.period(), origin - Duration::nanoseconds(1)),
+        Some(origin)
+    );
+    assert_eq!(
+        cyclicity.previous_start_before(&**rent.period(), origin + Duration::days(1)),
+        Some(origin)
+    );
+    assert!(rent.get_occurrences_at(Utc.ymd(2021, 5, 14)).is_empty());
+    assert_eq!(rent.get_occurrences_at(Utc.ymd(2021, 5, 15)).len(), 1);
+    assert_eq!(rent.get_occurrences_at(Utc.ymd(2021, 7, 15)).len(), 1);
+    assert_eq!(rent.get_occurrences_at(Utc.ymd(2021, 8, 13)).len(), 1);
+
+    let resting = WorkingDays::new().with_weekend(vec![
+        Weekday::Mon,
+        Weekday::Tue,
+        Weekday::Wed,
+        Weekday::Thu,
+        Weekday::Fri,
+        Weekday::Sat,
+        Weekday::Sun,
+    ]);
+    let period = Moment(Utc.ymd(2021, 7, 26).and_hms(9, 0, 0));
+    let after = period.get_date_time_start();
+
+    assert_eq!(resting.following(after.date()), None);
+    assert_eq!(
+        resting.adjust(after.date(), BusinessDayAdjustment::ModifiedFollowing),
+        None
+    );
+    assert_eq!(
+        WorkingDayCycle(resting.clone()).next_start_after(&period, after),
+        None
+    );
+    assert_eq!(
+        LastWorkingDayOfMonth(resting.clone()).next_start_after(&period, after),
+        None
+    );
+    assert_eq!(
+        Adjusted {
+            rule: Box::new(MonthlyCycle),
+            working_days: resting,
+            adjustment: BusinessDayAdjustment::Preceding,
+        }
+        .next_start_after(&period, after),
+        None
+    );
+}
+
+#[test]
+fn public_holidays() {
+    use crate::{
+        holidays::{self, Country},
+        prelude::*,
+    };
+    use chrono::{TimeZone, Utc};
+
+    let names_at = |calendar: &Calendar, y, m, d| {
+        calendar
+            .day(Utc.ymd(y, m, d))
+            .into_iter()
+            .map(|occurrence| occurrence.name)
+            .collect::<Vec<_>>()
+    };
+
+    let united_states = holidays::calendar(Country::UnitedStates, 2021..=2022);
+    assert_eq!(
+        names_at(&united_states, 2021, 11, 25),
+        vec!["Thanksgiving Day"]
+    );
+    assert_eq!(
+        names_at(&united_states, 2021, 7, 5),
+        vec!["Independence Day (observed)"]
+    );
+    assert_eq!(
+        names_at(&united_states, 2021, 12, 31),
+        vec!["New Year's Day (observed)"]
+    );
+    assert!(names_at(&united_states, 2020, 6, 19).is_empty());
+
+    let united_kingdom = holidays::calendar(Country::UnitedKingdom, 2021..=2021);
+    assert_eq!(names_at(&united_kingdom, 2021, 4, 2), vec!["Good Friday"]);
+    assert_eq!(
+        names_at(&united_kingdom, 2021, 12, 27),
+        vec!["Christmas Day (observed)"]
+    );
+    assert_eq!(
+        names_at(&united_kingdom, 2021, 12, 28),
+        vec!["Boxing Day (observed)"]
+    );
+
+    let poland = holidays::calendar(Country::Poland, 2021..=2021);
+    assert_eq!(names_at(&poland, 2021, 6, 3), vec!["Boże Ciało"]);
+
+    let germany = holidays::calendar(Country::Germany, 2024..=2024);
+    assert_eq!(names_at(&germany, 2024, 5, 9), vec!["Christi Himmelfahrt"]);
+
+    let uuids = |calendar: &Calendar| {
+        calendar
+            .day(Utc.ymd(2021, 7, 14))
+            .into_iter()
+            .map(|occurrence| occurrence.origin)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        uuids(&holidays::calendar(Country::France, 2021..=2021)),
+        uuids(&holidays::calendar(Country::France, 2020..=2022))
+    );
+}
+
+#[test]
+fn easter_relative_cycles() {
+    use crate::prelude::*;
+    use chrono::{TimeZone, Utc};
+
+    let mut calendar = Calendar::new();
+    calendar.add_event(
+        EventPartial::new(String::from("Good Friday service"))
+            .whole_day(Utc.ymd(2021, 4, 2))
+            .with_cyclicity(Box::new(EasterOffsetCycle::western(-2)))
+            .complete(),
+    );
+    calendar.add_event(
+        EventPartial::new(String::from("Pascha"))
+            .whole_day(Utc.ymd(2021, 5, 2))
+            .with_cyclicity(Box::new(EasterOffsetCycle::orthodox(0)))
+            .complete(),
+    );
+
+    let names_at = |y, m, d| {
+        calendar
+            .day(Utc.ymd(y, m, d))
+            .into_iter()
+            .map(|occurrence| occurrence.name)
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(names_at(2022, 4, 15), vec!["Good Friday service"]);
+    assert_eq!(names_at(2024, 3, 29), vec!["Good Friday service"]);
+    assert_eq!(names_at(2022, 4, 24), vec!["Pascha"]);
+    assert_eq!(names_at(2024, 5, 5), vec!["Pascha"]);
+    assert_eq!(names_at(2025, 4, 20), vec!["Pascha"]);
+    assert!(names_at(2020, 4, 19).is_empty());
+    assert!(names_at(2022, 4, 17).is_empty());
+
+    // Starting off Easter, the first instance is the next matching day.
+    let retreat = EventPartial::new(String::from("Retreat"))
+        .whole_day(Utc.ymd(2021, 1, 10))
+        .with_cyclicity(Box::new(EasterOffsetCycle::western(-2)))
+        .complete();
+
+    assert!(retreat.get_occurrences_at(Utc.ymd(2021, 1, 10)).is_empty());
+    assert_eq!(retreat.get_occurrences_at(Utc.ymd(2021, 4, 2)).len(), 1);
+}
+
+#[test]
+fn versioned_calendar_payloads() {
+    use crate::{prelude::*, schema::CURRENT_VERSION};
+    use chrono::{TimeZone, Utc};
+
+    let mut calendar = Calendar::new();
+    calendar.add_event(
+        EventPartial::new(String::from("Standup"))
+            .from_to(
+                Utc.ymd(2021, 5, 3).and_hms(9, 0, 0),
+                Utc.ymd(2021, 5, 3).and_hms(9, 15, 0),
+            )
+            .daily()
+            .complete(),
+    );
+
+    let json: serde_json::Value = serde_json::to_value(&calendar).unwrap();
+    assert_eq!(json["version"], CURRENT_VERSION);
+
+    let legacy = r#"{
+        "events": [{
+            "uuid": "5f3c1c5e-8d0e-4b8e-9c4e-8c0b1e7e6a10",
+            "name": "Standup",
+            "description": null,
+            "cyclicity": { "type": "DailyCycle" },
+            "period": { "type": "StartEnd", "value": [1620032400, 1620033300] }
+        }]
+    }"#;
+    let calendar: Calendar = serde_json::from_str(legacy).unwrap();
+
+    assert_eq!(calendar.day(Utc.ymd(2021, 5, 10))[0].name, "Standup");
+
+    let future = format!(r#"{{ "version": {}, "events": [] }}"#, CURRENT_VERSION + 1);
+    assert!(serde_json::from_str::<Calendar>(&future).is_err());
+}
+
+#[test]
+fn baseline_calendar_payload() {
+    use crate::prelude::*;
+    use chrono::{TimeZone, Utc};
+
+    // Written by the first release, before the format had a version.
+    let fixture = include_str!("../schema/fixtures/calendar-v0.json");
+    let calendar: Calendar = serde_json::from_str(fixture).unwrap();
+
+    let names_at = |calendar: &Calendar, y, m, d| {
+        calendar
+            .day(Utc.ymd(y, m, d))
+            .into_iter()
+            .map(|occurrence| occurrence.name)
+            .collect::<Vec<_>>()
+    };
+    let check = |calendar: &Calendar| {
+        assert_eq!(names_at(calendar, 2021, 5, 4), vec!["Standup"]);
+        assert!(names_at(calendar, 2021, 5, 5).is_empty());
+        assert_eq!(names_at(calendar, 2021, 7, 30), vec!["Standup", "Holiday"]);
+        assert!(names_at(calendar, 2021, 7, 31)
+            .iter()
+            .all(|name| name != "Holiday"));
+        assert_eq!(
+            names_at(calendar, 2022, 5, 8),
+            vec!["Standup", "Anne's birthday"]
+        );
+    };
+
+    check(&calendar);
+
+    let standup = &calendar.events[0];
+
+    assert_eq!(
+        standup.uuid.to_string(),
+        "e08583cd-3774-4939-af8c-53890eff22a0"
+    );
+    assert_eq!(standup.description(), Some("Daily sync"));
+    assert_eq!(
+        standup.period().get_date_time_end(),
+        Utc.ymd(2021, 5, 3).and_hms(9, 15, 0)
+    );
+    assert!(standup.alarms().is_empty());
+
+    let rewritten: Calendar =
+        serde_json::from_str(&serde_json::to_string(&calendar).unwrap()).unwrap();
+
+    check(&rewritten);
+}
+
+#[cfg(feature = "json_schema")]
+#[test]
+fn committed_json_schema_is_up_to_date() {
+    let generated = serde_json::to_value(crate::schema::json_schema()).unwrap();
+    let committed: serde_json::Value =
+        serde_json::from_str(include_str!("../schema/calendar.schema.json")).unwrap();
+
+    assert_eq!(
+        generated, committed,
+        "run `cargo run --example json_schema --features json_schema > schema/calendar.schema.json`"
+    );
+}
+
+#[test]
+fn timestamp_formats_round_trip_losslessly() {
+    use crate::prelude::*;
+    use chrono::{Date, Duration, NaiveDateTime, TimeZone, Utc};
+
+    let round_trip = |calendar: &Calendar, days: Vec<Date<Utc>>| {
+        let json = serde_json::to_string(calendar).unwrap();
+        let restored: Calendar = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(restored.timestamp_format(), calendar.timestamp_format());
+
+        for day in days {
+            let ranges = |calendar: &Calendar| {
+                calendar
+                    .day(day)
+                    .into_iter()
+                    .map(|occurrence| occurrence.period.range())
+                    .collect::<Vec<_>>()
+            };
+
+            assert_eq!(ranges(&restored), ranges(calendar));
+        }
+
+        restored
+    };
+
+    for (format, start) in [
+        (
+            TimestampFormat::Seconds,
+            Utc.ymd(2021, 5, 3).and_hms(9, 0, 0),
+        ),
+        (
+            TimestampFormat::Milliseconds,
+            Utc.ymd(2021, 5, 3).and_hms_milli(9, 0, 0, 250),
+        ),
+        (
+            TimestampFormat::Rfc3339,
+            Utc.ymd(2021, 5, 3).and_hms_nano(9, 0, 0, 123_456_789),
+        ),
+    ] {
+        let end = start + Duration::minutes(15);
+        let floating_start = NaiveDateTime::from_timestamp(start.timestamp(), 987_654_321);
+        let mut calendar = Calendar::new().with_timestamp_format(format);
+
+        calendar.add_event(
+            EventPartial::new(String::from("Standup"))
+                .from_to(start, end)
+                .daily()
+                .with_exception(start + Duration::days(1))
+                .complete(),
+        );
+        calendar.add_event(
+            EventPartial::new(String::from("Holiday"))
+                .whole_days(start.date(), end.date())
+                .complete(),
+        );
+        calendar.add_event(
+            EventPartial::new(String::from("Deadline"))
+                .moment(start)
+                .complete(),
+        );
+        calendar.add_event(
+            EventPartial::new(String::from("Leave"))
+                .open_ended(start)
+                .complete(),
+        );
+        calendar.add_event(
+            EventPartial::new(String::from("Gym"))
+                .floating(floating_start, floating_start + Duration::hours(1))
+                .complete(),
+        );
+        calendar.add_event(
+            EventPartial::new(String::from("Visits"))
+                .from_to(start, end)
+                .with_cyclicity(Box::new(OnDates::new(vec![
+                    start,
+                    start + Duration::days(2),
+                ])))
+                .complete(),
+        );
+
+        let days = (-1..4).map(|days| start.date() + Duration::days(days));
+        let restored = round_trip(&calendar, days.collect());
+        let names_at = |days| {
+            restored
+                .day(start.date() + Duration::days(days))
+                .into_iter()
+                .map(|occurrence| occurrence.name)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(names_at(1), vec!["Leave"]);
+        assert_eq!(names_at(2), vec!["Standup", "Leave", "Visits"]);
+    }
+
+    let offset: Calendar = serde_json::from_str(
+        r#"{ "version": 2, "timestamps": "Rfc3339", "events": [{
+            "name": "Deadline",
+            "description": null,
+            "cyclicity": null,
+            "period": { "type": "Moment", "value": "2021-05-03T11:00:00.5+02:00" }
+        }] }"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        offset.day(Utc.ymd(2021, 5, 3))[0]
+            .period
+            .get_date_time_start(),
+        Utc.ymd(2021, 5, 3).and_hms_milli(9, 0, 0, 500)
+    );
+
+    let reordered = serde_json::from_str::<Calendar>(
+        r#"{ "version": 2, "events": [{
+            "name": "Deadline",
+            "description": null,
+            "cyclicity": null,
+            "period": { "type": "Moment", "value": "2021-05-03T11:00:00.5+02:00" }
+        }], "timestamps": "Rfc3339" }"#,
+    );
+
+    assert!(reordered.is_err());
+
+    let deadline = EventPartial::new(String::from("Deadline"))
+        .moment(Utc.ymd(2021, 5, 3).and_hms(9, 0, 0))
+        .complete();
+
+    assert!(serde_json::to_string(&deadline)
+        .unwrap()
+        .contains("2021-05-03T09:00:00Z"));
+
+    // Holidays of working days are calendars of their own, written in their own format
+    let holidays = Calendar::new().with_timestamp_format(TimestampFormat::Rfc3339);
+    let mut calendar = Calendar::new().with_timestamp_format(TimestampFormat::Milliseconds);
+
+    calendar.add_event(
+        EventPartial::new(String::from("Payroll"))
+            .moment(Utc.ymd(2021, 5, 3).and_hms_milli(9, 0, 0, 250))
+            .with_cyclicity(Box::new(LastWorkingDayOfMonth(
+                WorkingDays::new().with_holidays(holidays),
+            )))
+            .complete(),
+    );
+
+    let json = serde_json::to_string(&calendar).unwrap();
+
+    assert!(json.contains("1620032400250"));
+    assert_eq!(serde_json::from_str::<Calendar>(&json).unwrap(), calendar);
+}
+
+#[test]
+fn binary_formats_round_trip() {
+    use crate::{
+        holidays::{self, Country},
+        prelude::*,
+    };
+    use chrono::{DateTime, Duration, NaiveTime, TimeZone, Utc};
+
+    let start = Utc.ymd(2021, 5, 3).and_hms_milli(9, 0, 0, 250);
+    let working_days =
+        || WorkingDays::new().with_holidays(holidays::calendar(Country::Poland, 2021..=2021));
+    let cyclicities: Vec<Box<dyn Cyclicity>> = vec![
+        Box::new(DailyCycle),
+        Box::new(WeeklyCycle),
+        Box::new(MonthlyCycle),
+        Box::new(AnnualCycle),
+        Box::new(HourlyCycle::new(2).within(TimeWindow(
+            NaiveTime::from_hms(8, 0, 0),
+            NaiveTime::from_hms(17, 0, 0),
+        ))),
+        Box::new(MinutelyCycle::new(15)),
+        Box::new(AnyOf(vec![Box::new(DailyCycle), Box::new(WeeklyCycle)])),
+        Box::new(AllOf(vec![Box::new(DailyCycle), Box::new(MonthlyCycle)])),
+        Box::new(Except(Box::new(DailyCycle), Box::new(WeeklyCycle))),
+        Box::new(Shifted::new(Duration::days(1), Box::new(MonthlyCycle))),
+        Box::new(OnDates::new(vec![start, start + Duration::days(3)])),
+        Box::new(WorkingDayCycle(working_days())),
+        Box::new(LastWorkingDayOfMonth(working_days())),
+        Box::new(Adjusted {
+            rule: Box::new(MonthlyCycle),
+            working_days: working_days(),
+            adjustment: BusinessDayAdjustment::ModifiedFollowing,
+        }),
+        Box::new(EasterOffsetCycle::orthodox(-2)),
+    ];
+
+    for format in [
+        TimestampFormat::Seconds,
+        TimestampFormat::Milliseconds,
+        TimestampFormat::Rfc3339,
+    ] {
+        let mut calendar = Calendar::new().with_timestamp_format(format);
+
+        for cyclicity in &cyclicities {
+            calendar.add_event(
+                EventPartial::new(String::from("Cycle"))
+                    .from_to(start, start + Duration::hours(1))
+                    .with_cyclicity(cyclicity.clone())
+                    .with_exception(start + Duration::days(1))
+                    .with_alarm(Alarm::before_start(Duration::minutes(5)))
+                    .complete(),
+            );
+        }
+
+        calendar.add_event(
+            EventPartial::new(String::from("Holiday"))
+                .whole_days(start.date(), start.date() + Duration::days(2))
+                .complete(),
+        );
+        calendar.add_event(
+            EventPartial::new(String::from("Deadline"))
+                .moment(start)
+                .complete(),
+        );
+        calendar.add_event(
+            EventPartial::new(String::from("Leave"))
+                .open_ended(start)
+                .complete(),
+        );
+        calendar.add_event(
+            EventPartial::new(String::from("Gym"))
+                .floating(start.naive_utc(), start.naive_utc() + Duration::hours(1))
+                .with_alarm(
+                    Alarm::before_start(Duration::days(1)).at(NaiveTime::from_hms(18, 0, 0)),
+                )
+                .complete(),
+        );
+
+        let json = serde_json::to_string(&calendar).unwrap();
+
+        let bytes = bincode::serialize(&calendar).unwrap();
+        let restored: Calendar = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(serde_json::to_string(&restored).unwrap(), json);
+
+        let bytes = postcard::to_allocvec(&calendar).unwrap();
+        let restored: Calendar = postcard::from_bytes(&bytes).unwrap();
+        assert_eq!(serde_json::to_string(&restored).unwrap(), json);
+
+        assert!(bytes.len() < json.len() / 2);
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+    struct Unregistered;
+
+    #[typetag::serde]
+    impl Cyclicity for Unregistered {
+        fn next_start_after(&self, _: &dyn Period, _: DateTime<Utc>) -> Option<DateTime<Utc>> {
+            None
+        }
+
+        fn previous_start_before(&self, _: &dyn Period, _: DateTime<Utc>) -> Option<DateTime<Utc>> {
+            None
+        }
+
+        impl_cloned!(Cyclicity);
+    }
+
+    let event = EventPartial::new(String::from("Custom"))
+        .moment(start)
+        .with_cyclicity(Box::new(Unregistered))
+        .complete();
+
+    assert!(serde_json::to_string(&event).is_ok());
+    assert!(bincode::serialize(&event).is_err());
+
+    // Events of version 2 lack the revision fields, which positional formats can't skip.
+    let legacy = (
+        2u32,
+        TimestampFormat::Seconds,
+        vec![(
+            uuid::Uuid::new_v4(),
+            String::from("Standup"),
+            None::<String>,
+            Some(Recurrence::from(DailyCycle)),
+            vec![crate::chrono::DateTimeDef(start + Duration::days(1))],
+            PeriodKind::from(Moment(start)),
+            vec![Alarm::before_start(Duration::minutes(5))],
+        )],
+    );
+    let epoch = Utc.timestamp(0, 0);
+
+    let formatted = crate::serde::timestamp::Formatted::new(&legacy, TimestampFormat::Seconds);
+
+    for restored in [
+        bincode::deserialize::<Calendar>(&bincode::serialize(&formatted).unwrap()).unwrap(),
+        postcard::from_bytes::<Calendar>(&postcard::to_allocvec(&formatted).unwrap()).unwrap(),
+    ] {
+        let event = &restored.events[0];
+
+        assert_eq!(event.uuid, (legacy.2)[0].0);
+        assert_eq!(event.name(), "Standup");
+        assert_eq!(event.cyclicity(), Some(&Recurrence::from(DailyCycle)));
+        assert_eq!(
+            event.exceptions().collect::<Vec<_>>(),
+            vec![Utc.timestamp(start.timestamp() + 86_400, 0)]
+        );
+        assert_eq!(event.alarms(), &[Alarm::before_start(Duration::minutes(5))]);
+        assert_eq!(event.created(), epoch);
+        assert_eq!(event.last_modified(), epoch);
+        assert_eq!(event.sequence(), 0);
+    }
+}
+
+#[test]
+fn periods_and_cyclicities_held_by_value() {
+    use crate::prelude::*;
+    use chrono::{Duration, TimeZone, Utc};
+
+    let start = Utc.ymd(2021, 5, 3).and_hms(9, 0, 0);
+    let boxed: Box<dyn Period> = Box::new(StartEnd(start, start + Duration::hours(1)));
+    let period = PeriodKind::from(boxed.clone());
+
+    assert_eq!(
+        period,
+        PeriodKind::StartEnd(StartEnd(start, start + Duration::hours(1)))
+    );
+    assert_eq!(&*period, boxed.as_ref());
+    assert_ne!(period, PeriodKind::from(Moment(start)));
+    assert_eq!(
+        period.starting_at(start + Duration::days(1)).range(),
+        boxed
+            .with_new_date_time_start(start + Duration::days(1))
+            .range()
+    );
+
+    let cyclicity: Box<dyn Cyclicity> = Box::new(AnyOf(vec![
+        Box::new(WeeklyCycle),
+        Box::new(Shifted::new(Duration::days(1), Box::new(MonthlyCycle))),
+    ]));
+    let recurrence = Recurrence::from(cyclicity.clone());
+
+    assert!(matches!(recurrence, Recurrence::AnyOf(_)));
+    assert_eq!(&*recurrence, cyclicity.as_ref());
+    assert_ne!(
+        recurrence,
+        Recurrence::from(AnyOf(vec![Box::new(WeeklyCycle)]))
+    );
+
+    let event = EventPartial::new(String::from("Review"))
+        .from_to(start, start + Duration::hours(1))
+        .with_cyclicity(cyclicity)
+        .complete();
+    let mut weekly = EventPartial::new(String::from("Review"))
+        .from_to(start, start + Duration::hours(1))
+        .weekly()
+        .complete();
+    weekly.uuid = event.uuid;
+
+    assert_eq!(event.clone(), event);
+    assert_ne!(weekly, event);
+}
+
+#[test]
+fn occurrences_and_calendars_are_hashable() {
+    use crate::prelude::*;
+    use chrono::{Duration, TimeZone, Utc};
+    use std::collections::HashSet;
+
+    let start = Utc.ymd(2021, 5, 3).and_hms(9, 0, 0);
+    let mut calendar = Calendar::new();
+
+    calendar.add_event(
+        EventPartial::new(String::from("Standup"))
+            .from_to(start, start + Duration::minutes(15))
+            .with_cyclicity(Box::new(AnyOf(vec![
+                Box::new(DailyCycle),
+                Box::new(HourlyCycle::new(4)),
+            ])))
+            .complete(),
+    );
+
+    let occurrences: HashSet<EventOccurrence> = (0..3)
+        .flat_map(|day| calendar.day(start.date() + Duration::days(day)))
+        .chain(calendar.day(start.date()))
+        .collect();
+
+    assert_eq!(occurrences.len(), 16);
+    assert!(occurrences.contains(&calendar.day(start.date() + Duration::days(1))[2]));
+
+    let copy = calendar.clone();
+    let mut changed = calendar.clone();
+    changed.add_event(
+        EventPartial::new(String::from("Review"))
+            .moment(start)
+            .complete(),
+    );
+
+    let calendars: HashSet<Calendar> = vec![calendar, copy, changed].into_iter().collect();
+
+    assert_eq!(calendars.len(), 2);
+
+    let boxed: Box<dyn Period> = Box::new(Moment(start));
+    let periods: HashSet<Box<dyn Period>> = vec![
+        boxed.clone(),
+        Box::new(Moment(start)),
+        Box::new(OpenEnded(start)),
+    ]
+    .into_iter()
+    .collect();
+
+    assert_eq!(periods.len(), 2);
+    assert!(periods.contains(&boxed));
+}
+
+#[test]
+fn calendar_diff_and_apply() {
+    use crate::prelude::*;
+    use chrono::{Duration, TimeZone, Utc};
+
+    let start = Utc.ymd(2021, 5, 3).and_hms(9, 0, 0);
+    let standup = || {
+        EventPartial::new(String::from("Standup"))
+            .from_to(start, start + Duration::minutes(15))
+            .daily()
+            .with_exception(start + Duration::days(1))
+    };
+
+    let mut calendar = Calendar::new();
+    let kept = standup().complete();
+    let removed = EventPartial::new(String::from("Leave"))
+        .whole_day(start.date())
+        .complete();
+    calendar.add_event(kept.clone());
+    calendar.add_event(removed.clone());
+
+    let mut modified = standup()
+        .with_description("Moved to 10:00")
+        .from_to(start + Duration::hours(1), start + Duration::minutes(75))
+        .with_cyclicity(WorkingDayCycle(WorkingDays::new()))
+        .with_exception(start + Duration::days(2))
+        .with_alarm(Alarm::before_start(Duration::minutes(5)))
+        .complete();
+    modified.uuid = kept.uuid;
+    modified.apply(vec![FieldChange::Exceptions {
+        added: vec![],
+        removed: vec![start + Duration::days(1)],
+    }]);
+
+    let added = EventPartial::new(String::from("Review"))
+        .moment(start)
+        .complete();
+
+    let mut updated = Calendar::new();
+    updated.add_event(modified.clone());
+    updated.add_event(added.clone());
+
+    let changes = calendar.diff(&updated);
+
+    assert_eq!(changes.added, vec![added]);
+    assert_eq!(changes.removed, vec![removed.uuid]);
+    assert_eq!(
+        changes.modified,
+        vec![EventChange {
+            uuid: kept.uuid,
+            fields: kept.diff(&modified),
+            sequence: 0,
+            last_modified: modified.last_modified(),
+        }]
+    );
+    assert_eq!(
+        kept.diff(&modified)
+            .into_iter()
+            .filter(|field| !matches!(field, FieldChange::Period { .. }))
+            .collect::<Vec<_>>(),
+        vec![
+            FieldChange::Description {
+                from: None,
+                to: Some(String::from("Moved to 10:00")),
+            },
+            FieldChange::Cyclicity {
+                from: Some(Recurrence::DailyCycle(DailyCycle)),
+                to: Some(Recurrence::WorkingDayCycle(WorkingDayCycle(
+                    WorkingDays::new()
+                ))),
+            },
+            FieldChange::Exceptions {
+                added: vec![start + Duration::days(2)],
+                removed: vec![start + Duration::days(1)],
+            },
+            FieldChange::Alarms {
+                from: vec![],
+                to: vec![Alarm::before_start(Duration::minutes(5))],
+            },
+        ]
+    );
+    assert!(updated.diff(&updated.clone()).is_empty());
+
+    let json = serde_json::to_string(&changes).unwrap();
+    let restored: ChangeSet = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored, changes);
+
+    let precise = ChangeSet {
+        added: vec![EventPartial::new(String::from("Retro"))
+            .moment(start + Duration::milliseconds(250))
+            .complete()],
+        removed: vec![],
+        modified: vec![EventChange {
+            uuid: kept.uuid,
+            fields: vec![FieldChange::Exceptions {
+                added: vec![start + Duration::milliseconds(750)],
+                removed: vec![],
+            }],
+            sequence: 1,
+            last_modified: start + Duration::milliseconds(125),
+        }],
+    };
+    let json = serde_json::to_string(&precise).unwrap();
+    let bytes = bincode::serialize(&precise).unwrap();
+
+    assert_eq!(serde_json::from_str::<ChangeSet>(&json).unwrap(), precise);
+    assert_eq!(bincode::deserialize::<ChangeSet>(&bytes).unwrap(), precise);
+
+    calendar.apply(changes);
+
+    assert_eq!(calendar.diff(&updated), ChangeSet::default());
+
+    for day in 0..3 {
+        let date = start.date() + Duration::days(day);
+
+        assert_eq!(calendar.day(date), updated.day(date));
+    }
+}
+
+#[test]
+fn journaled_calendar_undo_and_redo() {
+    use crate::prelude::*;
+    use chrono::{Duration, TimeZone, Utc};
+
+    let start = Utc.ymd(2021, 5, 3).and_hms(9, 0, 0);
+    let standup = EventPartial::new(String::from("Standup"))
+        .from_to(start, start + Duration::minutes(15))
+        .daily()
+        .complete();
+    let leave = EventPartial::new(String::from("Leave"))
+        .whole_day(start.date())
+        .complete();
+
+    let mut plain = Calendar::new();
+    plain.add_event(standup.clone());
+
+    assert!(!plain.undo());
+
+    let mut calendar = Calendar::new().with_journal(Journal::new());
+    let mut snapshots = vec![calendar.clone()];
+    let mut record = |calendar: &Calendar| snapshots.push(calendar.clone());
+
+    calendar.add_event(standup.clone());
+    record(&calendar);
+    calendar.add_event(leave.clone());
+    record(&calendar);
+    assert!(calendar.add_exception(standup.uuid, start + Duration::days(1)));
+    assert!(!calendar.add_exception(standup.uuid, start + Duration::days(1)));
+    record(&calendar);
+
+    let excepted = calendar.event(standup.uuid).unwrap().clone();
+    let mut renamed = excepted.clone();
+    renamed.apply(vec![FieldChange::Name {
+        from: String::from("Standup"),
+        to: String::from("Daily"),
+    }]);
+    assert_eq!(calendar.update_event(renamed.clone()), Some(excepted));
+    record(&calendar);
+
+    let removed = calendar.remove_event(standup.uuid).unwrap();
+    assert!(removed.diff(&renamed).is_empty());
+    assert_eq!(removed.sequence(), 2);
+    record(&calendar);
+    assert!(calendar.remove_event(standup.uuid).is_none());
+
+    let mut target = Calendar::new();
+    target.add_event(
+        EventPartial::new(String::from("Review"))
+            .moment(start)
+            .complete(),
+    );
+    calendar.apply(calendar.diff(&target));
+    assert_eq!(calendar, target);
+    record(&calendar);
+
+    assert_eq!(
+        calendar.journal().unwrap().done().len(),
+        snapshots.len() - 1
+    );
+    assert_eq!(plain, snapshots[1]);
+
+    let journal = calendar.journal().unwrap();
+    let restored: Journal = serde_json::from_str(&serde_json::to_string(journal).unwrap()).unwrap();
+    assert_eq!(&restored, journal);
+
+    let mut precise = Calendar::new().with_journal(Journal::new());
+    let moment = start + Duration::milliseconds(250);
+    precise.add_event(
+        EventPartial::new(String::from("Retro"))
+            .moment(moment)
+            .daily()
+            .complete(),
+    );
+    precise.add_exception(
+        precise.events[0].uuid,
+        moment + Duration::days(1) + Duration::milliseconds(500),
+    );
+    let precise = precise.journal().unwrap();
+    let json = serde_json::to_string(precise).unwrap();
+    let bytes = bincode::serialize(precise).unwrap();
+
+    assert_eq!(&serde_json::from_str::<Journal>(&json).unwrap(), precise);
+    assert_eq!(&bincode::deserialize::<Journal>(&bytes).unwrap(), precise);
+
+    // Undone and redone modifications are new revisions of the events.
+    for (index, snapshot) in snapshots.iter().enumerate().rev().skip(1) {
+        assert!(calendar.undo());
+        assert!(calendar.diff(snapshot).is_empty());
+
+        if index == 3 {
+            assert_eq!(calendar.event(standup.uuid).unwrap().sequence(), 3);
+        }
+    }
+
+    assert!(!calendar.undo());
+
+    for snapshot in snapshots.iter().skip(1) {
+        assert!(calendar.redo());
+        assert!(calendar.diff(snapshot).is_empty());
+
+        if let Some(event) = snapshot.event(standup.uuid) {
+            assert!(calendar.event(standup.uuid).unwrap().sequence() >= event.sequence());
+        }
+    }
+
+    assert!(!calendar.redo());
+
+    calendar.undo();
+    calendar.add_event(leave);
+
+    assert!(calendar.journal().unwrap().undone().is_empty());
+    assert!(!calendar.redo());
+}
+
+#[test]
+fn event_revisions_and_merging() {
+    use crate::prelude::*;
+    use chrono::{Duration, TimeZone, Utc};
+
+    let start = Utc.ymd(2021, 5, 3).and_hms(9, 0, 0);
+    let event = EventPartial::new(String::from("Standup"))
+        .from_to(start, start + Duration::minutes(15))
+        .daily()
+        .complete();
+    let uuid = event.uuid;
+
+    assert_eq!(event.sequence(), 0);
+    assert_eq!(event.created(), event.last_modified());
+
+    let mut phone = Calendar::new();
+    phone.add_event(event.clone());
+
+    let mut laptop: Calendar =
+        serde_json::from_str(&serde_json::to_string(&phone).unwrap()).unwrap();
+    assert_eq!(laptop, phone);
+
+    assert!(laptop.add_exception(uuid, start + Duration::days(1)));
+    assert!(laptop.add_exception(uuid, start + Duration::days(2)));
+    assert!(!phone.remove_exception(uuid, start + Duration::days(1)));
+
+    let mut renamed = EventPartial::new(String::from("Daily"))
+        .from_to(start, start + Duration::minutes(15))
+        .complete();
+    renamed.uuid = uuid;
+    phone.update_event(renamed);
+
+    let on_phone = phone.event(uuid).unwrap();
+    let on_laptop = laptop.event(uuid).unwrap();
+
+    assert_eq!(on_phone.sequence(), 1);
+    assert_eq!(on_phone.created(), event.created());
+    assert_eq!(on_laptop.sequence(), 2);
+    assert!(on_laptop.is_newer_than(on_phone));
+    assert!(!on_phone.is_newer_than(on_laptop));
+
+    let review = EventPartial::new(String::from("Review"))
+        .moment(start)
+        .complete();
+    phone.add_event(review.clone());
+
+    let mut merged = phone.clone();
+    merged.merge(&laptop);
+    laptop.merge(&phone);
+
+    assert_eq!(merged.event(uuid), laptop.event(uuid));
+    assert_eq!(merged.event(review.uuid), Some(&review));
+    assert_eq!(laptop.event(review.uuid), Some(&review));
+
+    let legacy: Calendar = serde_json::from_str(
+        r#"{ "version": 2, "events": [{
+            "uuid": "5f3c1c5e-8d0e-4b8e-9c4e-8c0b1e7e6a10",
+            "name": "Legacy",
+            "description": null,
+            "cyclicity": null,
+            "period": { "type": "Moment", "value": 1620032400 }
+        }] }"#,
+    )
+    .unwrap();
+    let legacy = &legacy.events[0];
+
+    assert_eq!(legacy.sequence(), 0);
+    assert_eq!(legacy.created(), Utc.timestamp_opt(0, 0).unwrap());
+    assert!(event.is_newer_than(legacy));
+}
+
+#[cfg(feature = "caldav")]
+#[test]
+fn icalendar_round_trips() {
+    use crate::caldav::ical::{self, IcalError};
+    use crate::prelude::*;
+    use chrono::{Duration, NaiveTime, TimeZone, Utc};
+    use uuid::Uuid;
+
+    let start = Utc.ymd(2021, 5, 3).and_hms(9, 0, 0);
+    let events = vec![
+        EventPartial::new(String::from("Standup; daily, mostly"))
+            .with_description("Yesterday, today\nand blockers \\ nothing else")
+            .from_to(start, start + Duration::minutes(15))
+            .weekly()
+            .with_exception(start + Duration::weeks(1))
+            .with_alarm(Alarm::before_start(Duration::minutes(10)))
+            .with_alarm(Alarm::before_end(Duration::days(1)).at(NaiveTime::from_hms(8, 30, 0)))
+            .complete(),
+        EventPartial::new(String::from("Holidays"))
+            .whole_days(Utc.ymd(2021, 8, 2), Utc.ymd(2021, 8, 13))
+            .annual()
+            .with_exception(Utc.ymd(2022, 8, 2).and_hms(0, 0, 0))
+            .complete(),
+        EventPartial::new(String::from("Medication"))
+            .floating(start.naive_utc(), start.naive_utc())
+            .hourly(8)
+            .complete(),
+        EventPartial::new(String::from("Sabbatical"))
+            .open_ended(start)
+            .complete(),
+        EventPartial::new(String::from("Deliveries"))
+            .moment(start)
+            .with_cyclicity(OnDates::new(vec![start + Duration::days(3)]))
+            .complete(),
+        EventPartial::new(
+            "A name long enough to be folded over several lines, with ünicode".repeat(3),
+        )
+        .whole_day(Utc.ymd(2021, 5, 8))
+        .complete(),
+    ];
+
+    for event in &events {
+        let text = ical::to_ical(event).unwrap();
+
+        assert!(text.split("\r\n").all(|line| line.len() <= 75));
+        assert_eq!(&ical::from_ical(&text).unwrap(), event);
+    }
+
+    let whole_days = ical::to_ical(&events[1]).unwrap();
+    assert!(whole_days.contains("DTSTART;VALUE=DATE:20210802\r\n"));
+    assert!(whole_days.contains("DTEND;VALUE=DATE:20210814\r\n"));
+    assert!(ical::to_ical(&events[4])
+        .unwrap()
+        .contains("X-DATELESS-CYCLICITY:"));
+
+    let foreign = "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nBEGIN:VEVENT\r\n\
+        UID:1234@example.com\r\nDTSTAMP:20210501T120000Z\r\nSEQUENCE:4\r\n\
+        SUMMARY:Review\r\nDTSTART:20210503T140000Z\r\nDURATION:PT1H30M\r\n\
+        RRULE:FREQ=DAILY;INTERVAL=1\r\nBEGIN:VALARM\r\nACTION:AUDIO\r\n\
+        TRIGGER:-P1DT2H\r\nEND:VALARM\r\nEND:VEVENT\r\nEND:VCALENDAR\r\n";
+    let event = ical::from_ical(foreign).unwrap();
+    let at = Utc.ymd(2021, 5, 3).and_hms(14, 0, 0);
+
+    assert_eq!(
+        event.uuid,
+        Uuid::new_v5(&Uuid::NAMESPACE_OID, b"1234@example.com")
+    );
+    assert_eq!(ical::from_ical(foreign).unwrap().uuid, event.uuid);
+    assert_eq!(
+        event.period(),
+        &PeriodKind::from(StartEnd(at, at + Duration::minutes(90)))
+    );
+    assert_eq!(event.cyclicity(), Some(&Recurrence::from(DailyCycle)));
+    assert_eq!(event.alarms(), &[Alarm::before_start(Duration::hours(26))]);
+    assert_eq!(event.sequence(), 4);
+    assert_eq!(event.last_modified(), Utc.ymd(2021, 5, 1).and_hms(12, 0, 0));
+
+    let unsupported = |from: &str, to: &str| ical::from_ical(&foreign.replace(from, to));
+
+    assert!(matches!(
+        unsupported("DTSTART:", "DTSTART;TZID=Europe/Paris:"),
+        Err(IcalError::Unsupported(_))
+    ));
+    assert!(matches!(
+        unsupported("INTERVAL=1", "BYDAY=MO"),
+        Err(IcalError::Unsupported(_))
+    ));
+    assert!(matches!(
+        unsupported("DTSTART:2021", "DTSTART:garbage"),
+        Err(IcalError::Malformed(_))
+    ));
+    assert!(matches!(
+        unsupported("FREQ=DAILY;INTERVAL=1", "FREQ=HOURLY;INTERVAL=0"),
+        Err(IcalError::Malformed(_))
+    ));
+    assert!(matches!(
+        ical::from_ical("BEGIN:VCALENDAR\r\nEND:VCALENDAR\r\n"),
+        Err(IcalError::Malformed(_))
+    ));
+}
+
+#[cfg(feature = "caldav")]
+#[test]
+fn caldav_sync_against_mock_server() {
+    use crate::caldav::*;
+    use crate::prelude::*;
+    use chrono::{Duration, TimeZone, Utc};
+
+    let start = Utc.ymd(2021, 5, 3).and_hms(9, 0, 0);
+    let standup = EventPartial::new(String::from("Standup"))
+        .from_to(start, start + Duration::minutes(15))
+        .daily()
+        .complete();
+    let review = EventPartial::new(String::from("Review"))
+        .moment(start)
+        .weekly()
+        .complete();
+    let (standup_uuid, review_uuid) = (standup.uuid, review.uuid);
+
+    let server = MockServer::new();
+    let mut phone = Calendar::new();
+    phone.add_event(standup.clone());
+    phone.add_event(review.clone());
+
+    let mut phone_sync = CalDavSync::new(server.clone());
+    assert_eq!(phone_sync.sync(&mut phone).unwrap().pushed, 2);
+    assert_eq!(phone_sync.sync(&mut phone).unwrap(), SyncReport::default());
+    assert_eq!(server.resources().len(), 2);
+    assert_eq!(
+        phone_sync.state().href(standup_uuid),
+        Some(format!("{}.ics", standup_uuid).as_str())
+    );
+
+    let mut laptop = Calendar::new();
+    let mut laptop_sync = CalDavSync::new(server.clone());
+    assert_eq!(laptop_sync.sync(&mut laptop).unwrap().pulled, 2);
+    assert!(laptop.diff(&phone).is_empty());
+
+    // An edit on one side is pulled by the other.
+    assert!(laptop.add_exception(standup_uuid, start + Duration::days(1)));
+    assert_eq!(laptop_sync.sync(&mut laptop).unwrap().pushed, 1);
+    assert_eq!(phone_sync.sync(&mut phone).unwrap().pulled, 1);
+    assert_eq!(phone.event(standup_uuid), laptop.event(standup_uuid));
+
+    // Concurrent edits keep the newer revision, whichever side syncs first.
+    let renamed = |name: &str| {
+        let mut event = review.clone();
+        event.apply(vec![FieldChange::Name {
+            from: String::from("Review"),
+            to: String::from(name),
+        }]);
+        event
+    };
+    phone.update_event(renamed("Phone review"));
+    laptop.update_event(renamed("Laptop review"));
+    laptop.update_event(renamed("Laptop review, again"));
+
+    assert_eq!(phone_sync.sync(&mut phone).unwrap().pushed, 1);
+
+    let report = laptop_sync.sync(&mut laptop).unwrap();
+    assert_eq!(
+        report.conflicts,
+        vec![Conflict {
+            uuid: review_uuid,
+            kept: Side::Local,
+        }]
+    );
+    assert_eq!((report.pulled, report.pushed), (0, 1));
+
+    assert_eq!(phone_sync.sync(&mut phone).unwrap().pulled, 1);
+    assert_eq!(
+        phone.event(review_uuid).unwrap().name(),
+        "Laptop review, again"
+    );
+
+    // Deletions propagate, but lose against modifications.
+    phone.remove_event(review_uuid);
+    assert_eq!(phone_sync.sync(&mut phone).unwrap().deleted, 1);
+    assert_eq!(laptop_sync.sync(&mut laptop).unwrap().removed, 1);
+    assert!(laptop.event(review_uuid).is_none());
+
+    laptop.remove_event(standup_uuid);
+    assert!(phone.add_exception(standup_uuid, start + Duration::days(2)));
+    assert_eq!(laptop_sync.sync(&mut laptop).unwrap().deleted, 1);
+
+    let report = phone_sync.sync(&mut phone).unwrap();
+    assert_eq!(
+        report.conflicts,
+        vec![Conflict {
+            uuid: standup_uuid,
+            kept: Side::Local,
+        }]
+    );
+    assert_eq!(report.pushed, 1);
+    assert_eq!(laptop_sync.sync(&mut laptop).unwrap().pulled, 1);
+    assert_eq!(laptop.event(standup_uuid), phone.event(standup_uuid));
+
+    // Expired tokens fall back to a full sync.
+    server.expire_tokens();
+    let lunch = EventPartial::new(String::from("Lunch"))
+        .from_to(start + Duration::hours(3), start + Duration::hours(4))
+        .complete();
+    phone.add_event(lunch.clone());
+
+    let report = phone_sync.sync(&mut phone).unwrap();
+    assert_eq!((report.pulled, report.pushed), (0, 1));
+    assert_eq!(
+        server.requests()[server.requests().len() - 3..],
+        [
+            String::from("REPORT sync-collection"),
+            String::from("REPORT sync-collection"),
+            format!("PUT {}.ics", lunch.uuid),
+        ]
+    );
+
+    // Resources dateless cannot represent are left alone.
+    let mut other_client = server.clone();
+    other_client
+        .put(
+            "meeting.ics",
+            &ical::to_ical(&lunch)
+                .unwrap()
+                .replace(&lunch.uuid.to_string(), "meeting@example.com")
+                .replace("DTSTART:", "DTSTART;TZID=Europe/Paris:"),
+            Condition::Create,
+        )
+        .unwrap();
+
+    let report = laptop_sync.sync(&mut laptop).unwrap();
+    assert_eq!(report.pulled, 1);
+    assert_eq!(report.skipped.len(), 1);
+    assert_eq!(report.skipped[0].href, "meeting.ics");
+    assert!(laptop.diff(&phone).is_empty());
+
+    // Events whose resource can no longer be read are still written over the latest version.
+    let href = laptop_sync.state().href(standup_uuid).unwrap().to_string();
+    other_client
+        .put(
+            &href,
+            &ical::to_ical(laptop.event(standup_uuid).unwrap())
+                .unwrap()
+                .replace("DTSTART:", "DTSTART;TZID=Europe/Paris:"),
+            Condition::Overwrite,
+        )
+        .unwrap();
+
+    assert_eq!(laptop_sync.sync(&mut laptop).unwrap().skipped.len(), 1);
+    assert!(laptop.add_exception(standup_uuid, start + Duration::days(3)));
+
+    let report = laptop_sync.sync(&mut laptop).unwrap();
+    assert_eq!((report.pushed, report.deferred.len()), (1, 0));
+    assert_eq!(phone_sync.sync(&mut phone).unwrap().pulled, 1);
+
+    // Servers without sync-collection are queried in full, and the state survives a restart.
+    let server = server.without_sync_collection();
+    let state: SyncState =
+        serde_json::from_str(&serde_json::to_string(laptop_sync.state()).unwrap()).unwrap();
+    let mut laptop_sync = CalDavSync::new(server.clone()).with_state(state);
+
+    phone.remove_event(lunch.uuid);
+    assert_eq!(phone_sync.sync(&mut phone).unwrap().deleted, 1);
+
+    let report = laptop_sync.sync(&mut laptop).unwrap();
+    assert_eq!((report.pulled, report.removed), (0, 1));
+    assert_eq!(server.requests().last().unwrap(), "REPORT calendar-query");
+    assert!(laptop.diff(&phone).is_empty());
+}
+
+fn exercise_store<S: crate::store::CalendarStore>(store: &mut S)
+where
+    S::Error: std::fmt::Debug,
+{
+    use crate::prelude::*;
+    use chrono::{DateTime, Duration, NaiveTime, TimeZone, Utc};
+
+    let start = Utc.ymd(2021, 5, 3).and_hms_nano(9, 0, 0, 123_456_789);
+    let standup = EventPartial::new(String::from("Standup"))
+        .with_description("Yesterday, today, blockers")
+        .from_to(start, start + Duration::minutes(15))
+        .weekly()
+        .with_exception(start + Duration::weeks(1))
+        .with_alarm(Alarm::before_start(Duration::minutes(5)).at(NaiveTime::from_hms(8, 0, 0)))
+        .complete();
+    let trip = EventPartial::new(String::from("Trip"))
+        .whole_days(Utc.ymd(2021, 5, 10), Utc.ymd(2021, 5, 12))
+        .complete();
+    let launch = EventPartial::new(String::from("Launch"))
+        .moment(Utc.ymd(2021, 5, 20).and_hms(12, 0, 0))
+        .complete();
+    let leave = EventPartial::new(String::from("Leave"))
+        .open_ended(Utc.ymd(2021, 6, 1).and_hms(0, 0, 0))
+        .complete();
+
+    let mut calendar = Calendar::new().with_timestamp_format(TimestampFormat::Milliseconds);
+    for event in &[&standup, &trip, &launch] {
+        calendar.add_event((*event).clone());
+    }
+
+    store.save(&calendar).unwrap();
+    assert_eq!(store.load().unwrap(), calendar);
+    assert_eq!(store.event(trip.uuid).unwrap(), Some(trip.clone()));
+
+    let between = |store: &S, from: DateTime<Utc>, to: DateTime<Utc>| {
+        store
+            .events_between(from, to)
+            .unwrap()
+            .into_iter()
+            .map(|event| event.name().to_string())
+            .collect::<Vec<_>>()
+    };
+    let day = |day: u32| Utc.ymd(2021, 5, day).and_hms(0, 0, 0);
+
+    assert_eq!(between(store, day(3), day(4)), ["Standup"]);
+    assert_eq!(between(store, day(10), day(11)), ["Trip"]);
+    assert!(between(store, day(4), day(10)).is_empty());
+    assert_eq!(between(store, day(12), day(13)), ["Trip"]);
+    assert_eq!(between(store, day(17), day(21)), ["Standup", "Launch"]);
+    assert!(
+        between(store, day(20) + Duration::hours(12), day(21)).contains(&String::from("Launch"))
+    );
+    assert!(
+        !between(store, day(1), day(20) + Duration::hours(12)).contains(&String::from("Launch"))
+    );
+
+    let mut renamed = standup.clone();
+    renamed.apply(vec![FieldChange::Name {
+        from: String::from("Standup"),
+        to: String::from("Daily"),
+    }]);
+    store.upsert_event(&renamed).unwrap();
+    store.upsert_event(&leave).unwrap();
+
+    assert_eq!(
+        between(store, day(31), day(31) + Duration::days(365)),
+        ["Daily", "Leave"]
+    );
+    assert!(store.delete_event(trip.uuid).unwrap());
+    assert!(!store.delete_event(trip.uuid).unwrap());
+    assert_eq!(store.event(trip.uuid).unwrap(), None);
+
+    let loaded = store.load().unwrap();
+    let names: Vec<_> = loaded.events.iter().map(|event| event.name()).collect();
+
+    assert_eq!(names, ["Daily", "Launch", "Leave"]);
+    assert_eq!(loaded.event(standup.uuid), Some(&renamed));
+    assert_eq!(loaded.timestamp_format(), TimestampFormat::Milliseconds);
+}
+
+#[test]
+fn memory_store() {
+    exercise_store(&mut crate::store::MemoryStore::new());
+}
+
+#[cfg(feature = "sqlite")]
+#[test]
+fn sqlite_store() {
+    use crate::prelude::*;
+    use crate::store::{CalendarStore, SqliteStore, StoreError};
+    use chrono::Utc;
+
+    exercise_store(&mut SqliteStore::open_in_memory().unwrap());
+
+    let path = std::env::temp_dir().join(format!("dateless-{}.sqlite", uuid::Uuid::new_v4()));
+    let event = EventPartial::new(String::from("Standup"))
+        .whole_day(Utc::today())
+        .daily()
+        .complete();
+
+    SqliteStore::open(&path)
+        .unwrap()
+        .upsert_event(&event)
+        .unwrap();
+
+    let store = SqliteStore::open(&path).unwrap();
+    let kinds: (String, String) = store
+        .connection()
+        .query_row(
+            "SELECT period_kind, cyclicity_kind FROM events WHERE uuid = ?1",
+            [event.uuid.to_string()],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .unwrap();
+
+    assert_eq!(store.event(event.uuid).unwrap(), Some(event.clone()));
+    assert_eq!(
+        kinds,
+        (String::from("WholeDays"), String::from("DailyCycle"))
+    );
+
+    store
+        .connection()
+        .execute("UPDATE events SET created = ?1", [i64::MAX])
+        .unwrap();
+
+    assert!(matches!(
+        store.event(event.uuid),
+        Err(StoreError::Sqlite(_))
+    ));
+    assert!(store.load().is_err());
+
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn describing_events_and_cycles() {
+    use crate::prelude::*;
+    use chrono::{Duration, NaiveDate, NaiveTime, TimeZone, Utc, Weekday};
+
+    let start = Utc.ymd(2021, 5, 3).and_hms(9, 0, 0);
+    let standup = StartEnd(start, start + Duration::minutes(15));
+    let describe = |cycle: &dyn Cyclicity| cycle.describe(&standup);
+
+    assert_eq!(describe(&DailyCycle), "every day");
+    assert_eq!(describe(&WeeklyCycle), "every week on Monday");
+    assert_eq!(describe(&MonthlyCycle), "every month on the 3rd");
+    assert_eq!(describe(&AnnualCycle), "every year on May 3");
+    assert_eq!(describe(&HourlyCycle::new(1)), "every hour");
+    assert_eq!(
+        describe(&MinutelyCycle::new(15).within(TimeWindow(
+            NaiveTime::from_hms(9, 0, 0),
+            NaiveTime::from_hms(17, 0, 0)
+        ))),
+        "every 15 minutes between 09:00 and 17:00"
+    );
+    assert_eq!(
+        describe(&EasterOffsetCycle::western(-2)),
+        "every year 2 days before Easter Sunday"
+    );
+    assert_eq!(
+        describe(&AnyOf(vec![
+            Box::new(MonthlyCycle),
+            Box::new(Shifted::new(Duration::days(18), Box::new(MonthlyCycle))),
+        ])),
+        "every month on the 3rd and the 21st"
+    );
+    assert_eq!(
+        describe(&AnyOf(vec![
+            Box::new(Shifted::new(Duration::days(4), Box::new(WeeklyCycle))),
+            Box::new(WeeklyCycle),
+        ])),
+        "every week on Monday and Friday"
+    );
+    assert_eq!(
+        describe(&AnyOf(vec![Box::new(WeeklyCycle), Box::new(AnnualCycle)])),
+        "every week on Monday or every year on May 3"
+    );
+    assert_eq!(
+        describe(&Except(
+            Box::new(WorkingDayCycle(WorkingDays::new())),
+            Box::new(LastWorkingDayOfMonth(WorkingDays::new()))
+        )),
+        "every working day, except on the last working day of every month"
+    );
+    assert_eq!(
+        describe(&Shifted::new(Duration::hours(2), Box::new(DailyCycle))),
+        "every day, 2 hours later"
+    );
+    assert_eq!(
+        describe(&Shifted::new(Duration::seconds(-90), Box::new(DailyCycle))),
+        "every day, 90 seconds earlier"
+    );
+    assert_eq!(
+        describe(&Shifted::new(Duration::zero(), Box::new(DailyCycle))),
+        "every day"
+    );
+    assert_eq!(
+        Recurrence::from(WeeklyCycle).describe(&Moment(start + Duration::days(2))),
+        "every week on Wednesday"
+    );
+
+    let fortnight = || Box::new(HourlyCycle::new(14 * 24));
+    assert_eq!(describe(&HourlyCycle::new(48)), "every 2 days");
+    assert_eq!(describe(&MinutelyCycle::new(120)), "every 2 hours");
+    assert_eq!(describe(&*fortnight()), "every 2 weeks on Monday");
+    assert_eq!(
+        describe(&AnyOf(vec![
+            fortnight(),
+            Box::new(Shifted::new(Duration::days(2), fortnight())),
+        ])),
+        "every 2 weeks on Monday and Wednesday"
+    );
+    assert_eq!(
+        describe(&AnyOf(vec![
+            fortnight(),
+            Box::new(Shifted::new(Duration::days(9), fortnight())),
+        ])),
+        "every 2 weeks on Monday or every 2 weeks on Monday, 9 days later"
+    );
+    assert_eq!(
+        describe(&HourlyCycle::new(48).within(TimeWindow(
+            NaiveTime::from_hms(8, 0, 0),
+            NaiveTime::from_hms(18, 0, 0)
+        ))),
+        "every 48 hours between 08:00 and 18:00"
+    );
+    assert_eq!(describe(&AnyOf(vec![])), "never");
+    assert_eq!(describe(&AllOf(vec![])), "never");
+    assert_eq!(
+        describe(&AllOf(vec![Box::new(DailyCycle), Box::new(WeeklyCycle)])),
+        "every day, when also every week on Monday"
+    );
+    assert_eq!(
+        describe(&Except(Box::new(DailyCycle), Box::new(AnyOf(vec![])))),
+        "every day"
+    );
+    assert_eq!(
+        EventPartial::new(String::from("Nothing"))
+            .from_to(start, start + Duration::minutes(15))
+            .with_cyclicity(AllOf(vec![]))
+            .complete()
+            .describe(),
+        "Never"
+    );
+
+    let weekly = EventPartial::new(String::from("Standup"))
+        .from_to(start, start + Duration::minutes(15))
+        .weekly()
+        .complete();
+    let holidays = EventPartial::new(String::from("Holidays"))
+        .whole_days(Utc.ymd(2021, 8, 2), Utc.ymd(2021, 8, 13))
+        .annual()
+        .complete();
+    let birthday = EventPartial::new(String::from("Birthday"))
+        .whole_day(Utc.ymd(2021, 5, 8))
+        .annual()
+        .with_exception(Utc.ymd(2022, 5, 8).and_hms(0, 0, 0))
+        .complete();
+    let shift = EventPartial::new(String::from("Night shift"))
+        .from_to(start + Duration::hours(13), start + Duration::hours(21))
+        .with_cyclicity(WorkingDayCycle(WorkingDays::new()))
+        .complete();
+    let flight = EventPartial::new(String::from("Flight"))
+        .floating(
+            NaiveDate::from_ymd(2021, 5, 3).and_hms(22, 0, 0),
+            NaiveDate::from_ymd(2021, 5, 4).and_hms(6, 30, 0),
+        )
+        .complete();
+    let call = EventPartial::new(String::from("Call"))
+        .from_to(start, start + Duration::minutes(30))
+        .complete();
+    let leave = EventPartial::new(String::from("Leave"))
+        .open_ended(start)
+        .complete();
+
+    assert_eq!(
+        weekly.describe(),
+        "Every week on Monday from 09:00 to 09:15, starting May 3, 2021"
+    );
+    assert_eq!(
+        holidays.describe(),
+        "Every year on August 2 for 12 days, starting August 2, 2021"
+    );
+    assert_eq!(
+        birthday.describe(),
+        "Every year on May 8, starting May 8, 2021, except on May 8, 2022"
+    );
+    assert_eq!(
+        shift.describe(),
+        "Every working day from 22:00 to 06:00 the next day, starting May 3, 2021"
+    );
+    assert_eq!(
+        flight.describe(),
+        "From May 3, 2021 at 22:00 to May 4, 2021 at 06:30, local time"
+    );
+    assert_eq!(call.describe(), "On May 3, 2021 from 09:00 to 09:30");
+    assert_eq!(
+        EventPartial::new(String::from("Medication"))
+            .moment(start)
+            .hourly(8)
+            .with_exception(start + Duration::hours(8))
+            .complete()
+            .describe(),
+        "Every 8 hours at 09:00, starting May 3, 2021, except on May 3, 2021 at 17:00"
+    );
+    assert_eq!(
+        leave.describe(),
+        "From May 3, 2021 at 09:00 until further notice"
+    );
+
+    struct Terse;
+
+    impl Localization for Terse {
+        fn date(&self, date: NaiveDate) -> String {
+            date.format("%Y-%m-%d").to_string()
+        }
+        fn time(&self, time: NaiveTime) -> String {
+            time.format("%H:%M").to_string()
+        }
+        fn weekday(&self, weekday: Weekday) -> String {
+            weekday.to_string()
+        }
+        fn daily(&self, interval: u32) -> String {
+            format!("{}d", interval)
+        }
+        fn weekly(&self, interval: u32, weekdays: &[Weekday]) -> String {
+            let days: Vec<_> = weekdays.iter().map(|day| self.weekday(*day)).collect();
+            format!("{}w/{}", interval, days.join("+"))
+        }
+        fn monthly(&self, days: &[u32]) -> String {
+            format!("monthly/{:?}", days)
+        }
+        fn annual(&self, month: u32, day: u32) -> String {
+            format!("yearly/{}-{}", month, day)
+        }
+        fn hourly(&self, interval: u32, _: Option<&TimeWindow>) -> String {
+            format!("{}h", interval)
+        }
+        fn minutely(&self, interval: u32, _: Option<&TimeWindow>) -> String {
+            format!("{}min", interval)
+        }
+        fn working_days(&self) -> String {
+            String::from("workdays")
+        }
+        fn last_working_day_of_month(&self) -> String {
+            String::from("last workday")
+        }
+        fn easter(&self, offset: i64, _: EasterReckoning) -> String {
+            format!("easter{:+}", offset)
+        }
+        fn week_of_year(&self, week: i32, weekday: Weekday) -> String {
+            format!("yearly/W{}-{}", week, weekday)
+        }
+        fn on_dates(&self, dates: &[NaiveDate]) -> String {
+            format!("{} dates", dates.len())
+        }
+        fn any_of(&self, cycles: &[String]) -> String {
+            cycles.join("|")
+        }
+        fn all_of(&self, cycles: &[String]) -> String {
+            cycles.join("&")
+        }
+        fn except(&self, cycle: &str, excluded: &str) -> String {
+            format!("{}-{}", cycle, excluded)
+        }
+        fn shifted(&self, cycle: &str, by: Duration) -> String {
+            format!("{}>>{}", cycle, by)
+        }
+        fn adjusted(&self, cycle: &str, _: BusinessDayAdjustment) -> String {
+            format!("{}~", cycle)
+        }
+        fn custom_cycle(&self) -> String {
+            String::from("custom")
+        }
+        fn never(&self) -> String {
+            String::from("never")
+        }
+        fn whole_days(&self, from: NaiveDate, to: NaiveDate) -> String {
+            format!("{}..{}", self.date(from), self.date(to))
+        }
+        fn between(&self, from: NaiveDateTime, to: NaiveDateTime) -> String {
+            format!("{}..{}", from, to)
+        }
+        fn open_ended(&self, from: NaiveDateTime) -> String {
+            format!("{}..", from)
+        }
+        fn local_time(&self, described: &str) -> String {
+            format!("{} (local)", described)
+        }
+        fn all_day(&self, days: i64) -> String {
+            format!("{}d", days)
+        }
+        fn times(&self, from: NaiveTime, to: NaiveTime, _: i64) -> String {
+            format!("{}-{}", self.time(from), self.time(to))
+        }
+        fn recurring(&self, cycle: &str, times: &str, first: NaiveDate) -> String {
+            format!("{} {} from {}", cycle, times, self.date(first))
+        }
+        fn except_on(&self, described: &str, dates: &[NaiveDate]) -> String {
+            format!("{} except {}", described, dates.len())
+        }
+        fn except_at(&self, described: &str, instants: &[NaiveDateTime]) -> String {
+            format!("{} except {}!", described, instants.len())
+        }
+        fn sentence(&self, described: &str) -> String {
+            described.to_string()
+        }
+    }
+
+    use chrono::NaiveDateTime;
+
+    assert_eq!(
+        weekly.describe_in(&Terse),
+        "1w/Mon 09:00-09:15 from 2021-05-03"
+    );
+    assert_eq!(
+        birthday.describe_in(&Terse),
+        "yearly/5-8 1d from 2021-05-08 except 1"
+    );
+}
+
+#[test]
+fn parsing_quick_add_entries() {
+    use crate::prelude::*;
+    use chrono::{Date, DateTime, Duration, TimeZone, Utc};
+
+    // A Wednesday
+    let reference = Utc.ymd(2021, 5, 5).and_hms(10, 0, 0);
+    let parse = |entry| EventPartial::parse(entry, reference).map(|partial| partial.complete());
+    let at = |month, day, hour, minute| Utc.ymd(2021, month, day).and_hms(hour, minute, 0);
+    let between = |from: DateTime<Utc>, to: DateTime<Utc>| PeriodKind::from(StartEnd(from, to));
+    let days = |from: Date<Utc>, to: Date<Utc>| PeriodKind::from(WholeDays(from, to));
+
+    let lunch = parse("Lunch with Anne 12:30-13:30 every Friday").unwrap();
+
+    assert_eq!(lunch.name(), "Lunch with Anne");
+    assert_eq!(lunch.period(), &between(at(5, 7, 12, 30), at(5, 7, 13, 30)));
+    assert_eq!(lunch.cyclicity(), Some(&Recurrence::from(WeeklyCycle)));
+
+    let standup = parse("Standup every Monday and Wednesday at 9:30 for 15 min").unwrap();
+
+    assert_eq!(standup.name(), "Standup");
+    assert_eq!(
+        standup.describe(),
+        "Every week on Monday and Wednesday from 09:30 to 09:45, starting May 5, 2021"
+    );
+
+    let gym = parse("Gym every weekday 7am").unwrap();
+
+    assert_eq!(gym.period(), &between(at(5, 5, 7, 0), at(5, 5, 8, 0)));
+    assert_eq!(
+        gym.cyclicity(),
+        Some(&Recurrence::from(WorkingDayCycle(WorkingDays::new())))
+    );
+
+    let review = parse("Review every 2 weeks").unwrap();
+
+    assert_eq!(
+        review.period(),
+        &days(Utc.ymd(2021, 5, 5), Utc.ymd(2021, 5, 5))
+    );
+    assert_eq!(
+        review.cyclicity(),
+        Some(&Recurrence::from(HourlyCycle::new(336)))
+    );
+
+    let sync = parse("Sync 10:00 every other Friday").unwrap();
+
+    assert_eq!(sync.period(), &between(at(5, 7, 10, 0), at(5, 7, 11, 0)));
+    assert_eq!(
+        sync.cyclicity(),
+        Some(&Recurrence::from(HourlyCycle::new(336)))
+    );
+
+    let planning = parse("Planning 9:30 every 2 weeks on Monday and Wednesday").unwrap();
+    let starts: Vec<_> = (0..4)
+        .scan(at(5, 5, 9, 30), |after, _| {
+            *after = planning
+                .next_occurrence_after(*after)?
+                .period
+                .get_date_time_start();
+            Some(*after)
+        })
+        .collect();
+
+    assert_eq!(planning.period().get_date_time_start(), at(5, 5, 9, 30));
+    assert_eq!(
+        starts,
+        vec![
+            at(5, 17, 9, 30),
+            at(5, 19, 9, 30),
+            at(5, 31, 9, 30),
+            at(6, 2, 9, 30)
+        ]
+    );
+
+    let yoga = parse("Yoga every Monday starting May 5").unwrap();
+
+    assert_eq!(
+        yoga.period(),
+        &days(Utc.ymd(2021, 5, 10), Utc.ymd(2021, 5, 10))
+    );
+
+    let stretch = parse("Stretch every 2 hours from 9am").unwrap();
+
+    assert_eq!(stretch.period(), &between(at(5, 5, 9, 0), at(5, 5, 10, 0)));
+    assert_eq!(
+        stretch.cyclicity(),
+        Some(&Recurrence::from(HourlyCycle::new(2)))
+    );
+
+    let rent = parse("Rent on the 1st, monthly").unwrap();
+
+    assert_eq!(rent.name(), "Rent");
+    assert_eq!(
+        rent.period(),
+        &days(Utc.ymd(2021, 6, 1), Utc.ymd(2021, 6, 1))
+    );
+    assert_eq!(rent.cyclicity(), Some(&Recurrence::from(MonthlyCycle)));
+
+    let cases = vec![
+        (
+            "Dinner for 5 at 7pm",
+            "Dinner for 5",
+            between(at(5, 5, 19, 0), at(5, 5, 20, 0)),
+        ),
+        (
+            "Call mom in 2 hours",
+            "Call mom",
+            between(at(5, 5, 12, 0), at(5, 5, 13, 0)),
+        ),
+        (
+            "Release party next Friday 9-11pm",
+            "Release party",
+            between(at(5, 7, 21, 0), at(5, 7, 23, 0)),
+        ),
+        (
+            "Night shift on Monday from 22:00 to 6:00",
+            "Night shift",
+            between(at(5, 10, 22, 0), at(5, 11, 6, 0)),
+        ),
+        (
+            "Review Friday 10am until noon",
+            "Review",
+            between(at(5, 7, 10, 0), at(5, 7, 12, 0)),
+        ),
+        (
+            "Conference 3rd of March, 2022 at noon",
+            "Conference",
+            between(
+                Utc.ymd(2022, 3, 3).and_hms(12, 0, 0),
+                Utc.ymd(2022, 3, 3).and_hms(13, 0, 0),
+            ),
+        ),
+        (
+            "Holidays in 3 days for 2 weeks",
+            "Holidays",
+            days(Utc.ymd(2021, 5, 8), Utc.ymd(2021, 5, 21)),
+        ),
+        (
+            "Anne's birthday May 8",
+            "Anne's birthday",
+            days(Utc.ymd(2021, 5, 8), Utc.ymd(2021, 5, 8)),
+        ),
+        (
+            "New year party Jan 1st",
+            "New year party",
+            days(Utc.ymd(2022, 1, 1), Utc.ymd(2022, 1, 1)),
+        ),
+        (
+            "Deadline 2021-05-31",
+            "Deadline",
+            days(Utc.ymd(2021, 5, 31), Utc.ymd(2021, 5, 31)),
+        ),
+        (
+            "Trip 15/6",
+            "Trip",
+            days(Utc.ymd(2021, 6, 15), Utc.ymd(2021, 6, 15)),
+        ),
+    ];
+
+    for (entry, name, period) in cases {
+        let event = parse(entry).unwrap();
+
+        assert_eq!(event.name(), name, "{}", entry);
+        assert_eq!(event.period(), &period, "{}", entry);
+        assert_eq!(event.cyclicity(), None, "{}", entry);
+    }
+
+    let errors = vec![
+        ("Meeting 3/4", ParseError::Ambiguous(String::from("3/4"))),
+        (
+            "Dentist tomorrow on Friday",
+            ParseError::Conflicting(String::from("tomorrow"), String::from("on Friday")),
+        ),
+        (
+            "Meeting 9am 10am",
+            ParseError::Conflicting(String::from("9am"), String::from("10am")),
+        ),
+        ("Call at 25:00", ParseError::Invalid(String::from("25:00"))),
+        (
+            "Party February 30",
+            ParseError::Invalid(String::from("February 30")),
+        ),
+        (
+            "Lunch with Anne tomorrow 12:30-13:30 every Friday",
+            ParseError::Conflicting(String::from("tomorrow"), String::from("every Friday")),
+        ),
+        (
+            "Gym Saturday every weekday",
+            ParseError::Conflicting(String::from("Saturday"), String::from("every weekday")),
+        ),
+        (
+            "Review every 2 months",
+            ParseError::Unsupported(String::from("every 2 months")),
+        ),
+        (
+            "Sync every other year",
+            ParseError::Unsupported(String::from("every other year")),
+        ),
+        (
+            "Ping every 4294967296 minutes",
+            ParseError::Invalid(String::from("every 4294967296 minutes")),
+        ),
+        (
+            "Ping 9:00 every 4294967297 hours",
+            ParseError::Invalid(String::from("every 4294967297 hours")),
+        ),
+        (
+            "Trip in 100000000 days",
+            ParseError::Invalid(String::from("in 100000000 days")),
+        ),
+        (
+            "Call 9:00 for 99999999999 hours",
+            ParseError::Invalid(String::from("for 99999999999 hours")),
+        ),
+        (
+            "Nap 9:00 for 9999999999999999h30",
+            ParseError::Invalid(String::from("9999999999999999h30")),
+        ),
+        ("tomorrow at 9", ParseError::MissingName),
+        ("Buy milk", ParseError::MissingStart),
+        ("Water plants every hour", ParseError::MissingStart),
+        ("Nap for 20 minutes", ParseError::MissingStart),
+    ];
+
+    for (entry, error) in errors {
+        assert_eq!(parse(entry).unwrap_err(), error, "{}", entry);
+    }
+
+    assert!(parse("Lunch tomorrow at noon").unwrap().period().duration() == Duration::hours(1));
+}
+
+#[test]
+fn week_numbering_and_week_start() {
+    use crate::prelude::*;
+    use chrono::{Datelike, Duration, TimeZone, Utc, Weekday};
+
+    let sunday = WeekStart(Weekday::Sun);
+
+    assert_eq!(sunday.number(Weekday::Sun), 1);
+    assert_eq!(sunday.number(Weekday::Sat), 7);
+    assert_eq!(WeekStart::ISO.number(Weekday::Sun), 7);
+    assert_eq!(sunday.first_day(Utc.ymd(2021, 5, 5)), Utc.ymd(2021, 5, 2));
+    assert_eq!(WeekStart::ISO.weeks_in_year(2020), 53);
+    assert_eq!(WeekStart::ISO.weeks_in_year(2021), 52);
+
+    let mut date = Utc.ymd(2015, 1, 1);
+
+    while date.year() < 2031 {
+        let iso = date.iso_week();
+
+        assert_eq!(
+            WeekStart::ISO.week(date),
+            (iso.year(), iso.week()),
+            "{}",
+            date
+        );
+        date += Duration::days(1);
+    }
+
+    // From Sunday to Tuesday, across the end of ISO weeks
+    let start = Utc.ymd(2021, 5, 2).and_hms(18, 0, 0);
+    let retreat = EventPartial::new(String::from("Retreat"))
+        .from_to(start, start + Duration::days(2))
+        .weekly()
+        .complete();
+    let mut calendar = Calendar::new();
+
+    calendar.add_event(retreat);
+
+    let starts = |calendar: &Calendar| -> Vec<_> {
+        calendar
+            .week(Utc.ymd(2021, 5, 12))
+            .iter()
+            .map(|occurrence| occurrence.period.get_date_time_start())
+            .collect()
+    };
+
+    assert_eq!(calendar.week_number(Utc.ymd(2021, 5, 16)), (2021, 19));
+    assert_eq!(
+        starts(&calendar),
+        vec![start + Duration::weeks(1), start + Duration::weeks(2)]
+    );
+
+    let calendar = calendar.with_week_start(sunday);
+
+    assert_eq!(calendar.week_number(Utc.ymd(2021, 5, 16)), (2021, 20));
+    assert_eq!(starts(&calendar), vec![start + Duration::weeks(1)]);
+
+    let json = serde_json::to_string(&calendar).unwrap();
+
+    assert!(json.contains(r#""week_start":"Sun""#));
+    assert_eq!(serde_json::from_str::<Calendar>(&json).unwrap(), calendar);
+
+    let legacy: Calendar = serde_json::from_str(r#"{ "version": 3, "events": [] }"#).unwrap();
+
+    assert_eq!(legacy.week_start(), WeekStart::ISO);
+
+    let next_start = |period: PeriodKind, cycle: WeekOfYearCycle| {
+        let event = EventPartial::new(String::from("Review"))
+            .with_period(period)
+            .with_cyclicity(cycle)
+            .complete();
+
+        event
+            .next_occurrence_after(event.period().get_date_time_end())
+            .map(|occurrence| occurrence.period.get_date_time_start())
+    };
+    let monday = |year, month, day| {
+        let start = Utc.ymd(year, month, day).and_hms(9, 0, 0);
+
+        PeriodKind::from(StartEnd(start, start + Duration::hours(1)))
+    };
+
+    assert_eq!(
+        next_start(monday(2021, 3, 8), WeekOfYearCycle::new(10)),
+        Some(Utc.ymd(2022, 3, 7).and_hms(9, 0, 0))
+    );
+    assert_eq!(
+        next_start(monday(2020, 12, 28), WeekOfYearCycle::new(53)),
+        Some(Utc.ymd(2026, 12, 28).and_hms(9, 0, 0))
+    );
+    assert_eq!(
+        next_start(monday(2021, 12, 27), WeekOfYearCycle::new(-1)),
+        Some(Utc.ymd(2022, 12, 26).and_hms(9, 0, 0))
+    );
+    assert_eq!(
+        next_start(
+            PeriodKind::from(WholeDays(Utc.ymd(2023, 1, 1), Utc.ymd(2023, 1, 1))),
+            WeekOfYearCycle::new(1).with_week_start(sunday)
+        ),
+        Some(Utc.ymd(2023, 12, 31).and_hms(0, 0, 0))
+    );
+
+    let review = EventPartial::new(String::from("Quarterly review"))
+        .with_period(monday(2021, 3, 8))
+        .with_cyclicity(WeekOfYearCycle::new(10))
+        .complete();
+
+    // Starting in another week, the first instance is in the next matching one.
+    let early = EventPartial::new(String::from("Quarterly review"))
+        .with_period(monday(2021, 3, 1))
+        .with_cyclicity(WeekOfYearCycle::new(10))
+        .complete();
+
+    assert!(early.get_occurrences_at(Utc.ymd(2021, 3, 1)).is_empty());
+    assert_eq!(early.get_occurrences_at(Utc.ymd(2021, 3, 8)).len(), 1);
+
+    assert_eq!(
+        WeekOfYearCycle::new(-2).describe(&StartEnd(start, start)),
+        "every year on Sunday of the 2nd last week"
+    );
+    assert_eq!(
+        review.describe(),
+        "Every year on Monday of week 10 from 09:00 to 10:00, starting March 8, 2021"
+    );
+
+    #[cfg(feature = "caldav")]
+    {
+        use crate::caldav::ical;
+
+        let text = ical::to_ical(&review).unwrap();
+
+        assert!(text.contains("RRULE:FREQ=YEARLY;BYWEEKNO=10;BYDAY=MO;WKST=MO\r\n"));
+        assert_eq!(ical::from_ical(&text).unwrap(), review);
+
+        let other_day = text.replace("BYDAY=MO", "BYDAY=TU");
+
+        assert!(matches!(
+            ical::from_ical(&other_day),
+            Err(ical::IcalError::Unsupported(_))
+        ));
+    }
+}
@@ -0,0 +1,98 @@
+use chrono::{Date, Datelike, Duration, TimeZone, Utc, Weekday};
+
+#[cfg(feature = "serde_support")]
+use serde::{Deserialize, Serialize};
+
+/**
+   First day of the week, by which weeks are laid out and numbered, like the `WKST` of
+   iCalendar.
+
+   Whatever day they start on, weeks are numbered within a year the way ISO 8601 does: week 1
+   is the first one with at least 4 days in the year, so the first and last weeks may start
+   or end in the neighbouring years. With `WeekStart::ISO`, numbers are ISO week numbers.
+
+   # Examples
+
+   ```rust
+   use dateless::prelude::*;
+   use chrono::{TimeZone, Utc, Weekday};
+
+   let new_year = Utc.ymd(2023, 1, 1); // A Sunday
+
+   assert_eq!(WeekStart::ISO.week(new_year), (2022, 52));
+   assert_eq!(WeekStart(Weekday::Sun).week(new_year), (2023, 1));
+   ```
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
+pub struct WeekStart(pub Weekday);
+
+impl Default for WeekStart {
+    fn default() -> Self {
+        Self::ISO
+    }
+}
+
+impl WeekStart {
+    /// Weeks starting on Monday, as ISO 8601 has them.
+    pub const ISO: WeekStart = WeekStart(Weekday::Mon);
+
+    /// Position of the weekday in the week, from 1 for the first day to 7 for the last.
+    pub fn number(self, weekday: Weekday) -> u32 {
+        (weekday.num_days_from_monday() + 7 - self.0.num_days_from_monday()) % 7 + 1
+    }
+
+    /// First day of the week the date falls in.
+    pub fn first_day(self, date: Date<Utc>) -> Date<Utc> {
+        date - Duration::days(self.number(date.weekday()) as i64 - 1)
+    }
+
+    /// The year the week of the date is numbered in, along with the number of the week.
+    pub fn week(self, date: Date<Utc>) -> (i32, u32) {
+        let first_day = self.first_day(date);
+        // A week belongs to the year holding most of its days, hence its 4th one.
+        let year = (first_day + Duration::days(3)).year();
+
+        (
+            year,
+            (first_day - self.first_week(year)).num_weeks() as u32 + 1,
+        )
+    }
+
+    /// 52 or 53.
+    pub fn weeks_in_year(self, year: i32) -> u32 {
+        (self.first_week(year + 1) - self.first_week(year)).num_weeks() as u32
+    }
+
+    /**
+       First day of the given week of the year, counting from the end of the year when
+       negative, `-1` being the last week. `None` when the year has no such week.
+
+       # Examples
+
+       ```rust
+       use dateless::prelude::*;
+       use chrono::{TimeZone, Utc};
+
+       assert_eq!(WeekStart::ISO.first_day_of_week(2021, 1), Some(Utc.ymd(2021, 1, 4)));
+       assert_eq!(WeekStart::ISO.first_day_of_week(2020, -1), Some(Utc.ymd(2020, 12, 28)));
+       assert_eq!(WeekStart::ISO.first_day_of_week(2021, 53), None);
+       ```
+    */
+    pub fn first_day_of_week(self, year: i32, week: i32) -> Option<Date<Utc>> {
+        let weeks = self.weeks_in_year(year) as i32;
+        let week = if week < 0 { weeks + week + 1 } else { week };
+
+        if week < 1 || week > weeks {
+            return None;
+        }
+
+        Some(self.first_week(year) + Duration::weeks(week as i64 - 1))
+    }
+
+    /// First day of week 1 of the year, which always holds January 4th.
+    fn first_week(self, year: i32) -> Date<Utc> {
+        self.first_day(Utc.ymd(year, 1, 4))
+    }
+}
@@ -0,0 +1,124 @@
+use super::calendar::Calendar;
+use chrono::{Date, Datelike, Duration, Utc, Weekday};
+
+#[cfg(feature = "serde_support")]
+use serde::{Deserialize, Serialize};
+
+/// Upper bound of days searched for a working day before giving up.
+const SEARCH_LIMIT: i64 = 366;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
+pub enum BusinessDayAdjustment {
+    /// The first working day after.
+    Following,
+    /// The last working day before.
+    Preceding,
+    /// The first working day after, unless it falls into the next month; the last working
+    /// day before otherwise.
+    ModifiedFollowing,
+}
+
+/**
+   Definition of working days: every day except the weekend days and days on which any event
+   of the `holidays` calendar occurs.
+*/
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "json_schema", derive(schemars::JsonSchema))]
+pub struct WorkingDays {
+    pub weekend: Vec<Weekday>,
+    #[cfg_attr(feature = "serde_support", serde(default))]
+    pub holidays: Calendar,
+}
+
+impl Default for WorkingDays {
+    fn default() -> Self {
+        Self {
+            weekend: vec![Weekday::Sat, Weekday::Sun],
+            holidays: Calendar::new(),
+        }
+    }
+}
+
+impl WorkingDays {
+    /**
+       Creates working days from Monday to Friday, without holidays.
+    */
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn with_weekend(self, weekend: Vec<Weekday>) -> Self {
+        Self { weekend, ..self }
+    }
+
+    pub fn with_holidays(self, holidays: Calendar) -> Self {
+        Self { holidays, ..self }
+    }
+
+    pub fn is_working_day(&self, date: Date<Utc>) -> bool {
+        !self.weekend.contains(&date.weekday()) && self.holidays.day(date).is_empty()
+    }
+
+    /**
+       The given date if it is a working day, the first working day after it otherwise. `None`
+       when there is no working day within a year, e.g. every weekday being weekend.
+    */
+    pub fn following(&self, date: Date<Utc>) -> Option<Date<Utc>> {
+        self.search(date, Duration::days(1))
+    }
+
+    /**
+       The given date if it is a working day, the last working day before it otherwise. `None`
+       when there is no working day within a year.
+    */
+    pub fn preceding(&self, date: Date<Utc>) -> Option<Date<Utc>> {
+        self.search(date, Duration::days(-1))
+    }
+
+    /**
+       Moves the date onto a working day according to the adjustment rule, `None` when there
+       is no working day within a year.
+
+       # Examples
+
+       ```rust
+       use dateless::prelude::*;
+       use chrono::{TimeZone, Utc};
+
+       let working_days = WorkingDays::new();
+       let saturday = Utc.ymd(2021, 7, 31);
+
+       assert_eq!(
+           working_days.adjust(saturday, BusinessDayAdjustment::ModifiedFollowing),
+           Some(Utc.ymd(2021, 7, 30))
+       );
+       ```
+    */
+    pub fn adjust(&self, date: Date<Utc>, adjustment: BusinessDayAdjustment) -> Option<Date<Utc>> {
+        match adjustment {
+            BusinessDayAdjustment::Following => self.following(date),
+            BusinessDayAdjustment::Preceding => self.preceding(date),
+            BusinessDayAdjustment::ModifiedFollowing => match self.following(date) {
+                Some(following) if following.month() == date.month() => Some(following),
+                _ => self.preceding(date),
+            },
+        }
+    }
+
+    fn search(&self, date: Date<Utc>, step: Duration) -> Option<Date<Utc>> {
+        let mut candidate = date;
+
+        for _ in 0..SEARCH_LIMIT {
+            if self.is_working_day(candidate) {
+                return Some(candidate);
+            }
+
+            candidate += step;
+        }
+
+        None
+    }
+}